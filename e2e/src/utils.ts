@@ -79,7 +79,9 @@ export async function setup(identity: Identity): Promise<ISetup> {
         union_name: "Union #1",
         union_description: "Test description",
         wallet_creator: identity.getPrincipal(),
-        history_ledger: ledgerCanisterId
+        history_ledger: ledgerCanisterId,
+        // local replica has its own root key
+        ic_root_key_opt: [Array.from(new Uint8Array(agent.rootKey))],
     };
     const walletCanisterId = await deployCanister(
         "wallet",
@@ -90,6 +92,7 @@ export async function setup(identity: Identity): Promise<ISetup> {
                     union_description: IDL.Text,
                     wallet_creator: IDL.Principal,
                     history_ledger: IDL.Principal,
+                    ic_root_key_opt: IDL.Opt(IDL.Vec(IDL.Nat8)),
                 })],
                 [init]
             )
@@ -42,7 +42,7 @@ describe('nested votings', () => {
             permissions: [ALLOW_WRITE_PERMISSION_ID],
             winners_count: [],
             choices_count: [],
-            round: {round_delay: 0n, round_duration: getMinsNano(1)},
+            round: {round_delay: 0n, round_duration: getMinsNano(1), execution_delay: 0n, anti_sniping: [], early_finish: false},
             approval: {
                 // 500 shares is enough to approve this voting
                 QuantityOf: {
@@ -158,7 +158,8 @@ describe('nested votings', () => {
             voting_config_id: wallet1VotingConfigId,
             name: 'test',
             description: 'test',
-            winners_need: 1
+            winners_need: 1,
+            pipeline_id: []
         });
 
         // add choices
@@ -212,20 +213,29 @@ describe('nested votings', () => {
 
         // voter21 wants to check new votings of wallet1
         // he needs a query delegation proof in order to do that
-        const {proof} = await voter21.walletPersonal.actor.get_my_query_delegation_proof({
+        await voter21.walletPersonal.actor.issue_my_query_delegation_proof({
             requested_targets: [
                 {
                     Endpoint: { canister_id: user1.wallet.canisterId, method_name: 'list_votings' },
                 },
             ]
         });
+        const {proof} = await voter21.walletPersonal.actor.get_my_query_delegation_proof();
 
         const voter21Wallet1 = await connectSetup(voter21Identity, user1.wallet.canisterId, user1.historyLedger.canisterId);
         const {page} = await voter21Wallet1.walletPersonal.actor.list_votings({
             page_req: {
                 page_size: 100,
                 page_index: 0,
-                filter: null,
+                filter: {
+                    status: [],
+                    voting_config: [],
+                    proposer: [],
+                    created_from: [],
+                    created_to: [],
+                    not_voted_by: [],
+                    search: [],
+                },
                 sort: null
             },
             query_delegation_proof_opt: [proof]
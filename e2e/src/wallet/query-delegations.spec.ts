@@ -72,14 +72,23 @@ describe('query delegations', () => {
       });
 
       // fetch a query delegation proof
-      const {proof} = await user2.walletPersonal.actor.get_my_query_delegation_proof({requested_targets: [{Endpoint : {canister_id: user1.wallet.canisterId, method_name: 'list_votings'}}]});
+      await user2.walletPersonal.actor.issue_my_query_delegation_proof({requested_targets: [{Endpoint : {canister_id: user1.wallet.canisterId, method_name: 'list_votings'}}]});
+      const {proof} = await user2.walletPersonal.actor.get_my_query_delegation_proof();
 
       // validate that user2 can't read anything in wallet1 without the proof
       await expectThrowsAsync(user2Wallet1.walletPersonal.actor.list_votings({
          page_req: {
             page_index: 0,
             page_size: 1,
-            filter: null,
+            filter: {
+               status: [],
+               voting_config: [],
+               proposer: [],
+               created_from: [],
+               created_to: [],
+               not_voted_by: [],
+               search: [],
+            },
             sort: null,
          },
          query_delegation_proof_opt: []
@@ -90,7 +99,15 @@ describe('query delegations', () => {
          page_req: {
             page_index: 0,
             page_size: 1,
-            filter: null,
+            filter: {
+               status: [],
+               voting_config: [],
+               proposer: [],
+               created_from: [],
+               created_to: [],
+               not_voted_by: [],
+               search: [],
+            },
             sort: null,
          },
          query_delegation_proof_opt: [proof]
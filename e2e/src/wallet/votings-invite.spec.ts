@@ -83,7 +83,7 @@ describe("votings", () => {
       quorum: {
         QuantityOf: { quantity: 1n, target: { Group: HAS_PROFILE_GROUP_ID } },
       },
-      round: { round_delay: 0n, round_duration: BigInt(30 * 10 ** 9) },
+      round: { round_delay: 0n, round_duration: BigInt(30 * 10 ** 9), execution_delay: 0n, anti_sniping: [], early_finish: false },
     });
 
     // THERE IS NOTHING SPECIAL ABOUT WALLET CREATOR ANYMORE - ALL OF THEM ARE JUST 'Has profile' GROUP MEMBERS NOW
@@ -93,6 +93,7 @@ describe("votings", () => {
       description: "Lets make us a new king!",
       voting_config_id: vcId,
       winners_need: 1,
+      pipeline_id: [],
     });
 
     const updateAccessConfigRequestType = IDL.Record({
@@ -62,7 +62,8 @@ describe('votings', () => {
             name: 'Test',
             description: 'Lets make us a new king!',
             voting_config_id: EMERGENCY_VOTING_CONFIG_ID,
-            winners_need: 1
+            winners_need: 1,
+            pipeline_id: []
         });
 
         const updateAccessConfigRequestType = IDL.Record({
@@ -202,7 +203,7 @@ describe('votings', () => {
         const {voting: voting1} = await user1.wallet.actor.get_voting({id: votingId, query_delegation_proof_opt: []});
 
         console.log(stringify(voting1));
-        assert(voting1.status.hasOwnProperty('Success'));
+        assert(voting1.status.hasOwnProperty('AwaitingExecution') || voting1.status.hasOwnProperty('Executing') || voting1.status.hasOwnProperty('Executed'));
 
         // waiting for program execution and events propagation
         await delay(1000 * 30);
@@ -61,9 +61,17 @@ export const SubmitWizzard = styled(({ data, unionId, onSuccess, ...p }: SubmitW
     union.canister.list_votings({
       page_req: {
         page_index: 0,
-        page_size: 100, // FIXME need filters by status
+        page_size: 100,
         sort: { UpdatedAt: false },
-        filter: null,
+        filter: {
+          status: [{ Round: [0] }],
+          voting_config: [],
+          proposer: [],
+          created_from: [],
+          created_to: [],
+          not_voted_by: [],
+          search: [],
+        },
       },
       query_delegation_proof_opt: [],
     });
@@ -18,10 +18,10 @@ export const StatusChips = styled(({ status, ...p }: StatusChipsProps) => {
     if ('PreRound' in status) {
       postfix = String(status.PreRound);
     }
-    if ('Success' in status) {
+    if ('AwaitingExecution' in status || 'Executed' in status) {
       color = 'green';
     }
-    if ('Fail' in status || 'Rejected' in status) {
+    if ('Fail' in status || 'Rejected' in status || 'Vetoed' in status) {
       color = 'red';
     }
 
@@ -78,7 +78,15 @@ export const Votings = styled(({ ...p }: VotingsProps) => {
             page_req: {
               page_index: index,
               page_size: size,
-              filter: null,
+              filter: {
+                status: [],
+                voting_config: [],
+                proposer: [],
+                created_from: [],
+                created_to: [],
+                not_voted_by: [],
+                search: [],
+              },
               sort: { CreatedAt: false },
             },
             query_delegation_proof_opt: [],
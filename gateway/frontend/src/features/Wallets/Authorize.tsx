@@ -76,7 +76,8 @@ export const AuthorizeWallet = () => {
         const union = initUnionController(wallet.toString());
         const encoder = buildEncoder<_SERVICE>(unionIdl, 'retTypes');
 
-        const response = await union.canister.get_my_query_delegation_proof(request);
+        await union.canister.issue_my_query_delegation_proof(request);
+        const response = await union.canister.get_my_query_delegation_proof();
 
         const encoded = encoder.get_my_query_delegation_proof(response);
 
@@ -19,7 +19,7 @@ impl SharesMoveService {
                     group_id,
                     principal_id: of,
                     timestamp: at,
-                    signature: (),
+                    signature: None,
                 });
             }
         }
@@ -32,7 +32,7 @@ impl SharesMoveService {
                     timestamp: at,
                     group_id,
                     principal_id: of,
-                    signature: (),
+                    signature: None,
                 });
             }
         }
@@ -0,0 +1,345 @@
+use candid::types::{Serializer, Type};
+use candid::{CandidType, Deserialize, Principal};
+use ic_certification::{
+    labeled, labeled_hash, AsHashTree, Certificate, HashTree, LookupResult, RbTree,
+};
+use ic_verify_bls_signature::verify_bls_signature;
+use serde::Deserializer;
+use serde_bytes::ByteBuf;
+
+pub type Hash = [u8; 32];
+
+// all the certified hashes are stored under this label of the canister's certified data
+const CERTIFIED_HASHES_LABEL: &[u8] = b"certified_hashes";
+const IC_STATE_ROOT_DOMAIN_SEPARATOR: &[u8] = b"\x0Dic-state-root";
+
+// DER prefix of a BLS12-381 public key, which is used by the IC to sign certificates
+pub const BLS_PUBLIC_KEY_DER_PREFIX: &[u8] = &[
+    0x30, 0x81, 0x82, 0x30, 0x1d, 0x06, 0x0d, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05,
+    0x03, 0x01, 0x02, 0x01, 0x06, 0x0c, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05, 0x03,
+    0x02, 0x01, 0x03, 0x61, 0x00,
+];
+
+// DER-encoded root key of the IC mainnet
+pub const IC_ROOT_KEY: &[u8] = &[
+    0x30, 0x81, 0x82, 0x30, 0x1d, 0x06, 0x0d, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05,
+    0x03, 0x01, 0x02, 0x01, 0x06, 0x0c, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xdc, 0x7c, 0x05, 0x03,
+    0x02, 0x01, 0x03, 0x61, 0x00, 0x81, 0x4c, 0x0e, 0x6e, 0xc7, 0x1f, 0xab, 0x58, 0x3b, 0x08, 0xbd,
+    0x81, 0x37, 0x3c, 0x25, 0x5c, 0x3c, 0x37, 0x1b, 0x2e, 0x84, 0x86, 0x3c, 0x98, 0xa4, 0xf1, 0xe0,
+    0x8b, 0x74, 0x23, 0x5d, 0x14, 0xfb, 0x5d, 0x9c, 0x0c, 0xd5, 0x46, 0xd9, 0x68, 0x5f, 0x91, 0x3a,
+    0x0c, 0x0b, 0x2c, 0xc5, 0x34, 0x15, 0x83, 0xbf, 0x4b, 0x43, 0x92, 0xe4, 0x67, 0xdb, 0x96, 0xd6,
+    0x5b, 0x9b, 0xb4, 0xcb, 0x71, 0x71, 0x12, 0xf8, 0x47, 0x2e, 0x0d, 0x5a, 0x4d, 0x14, 0x50, 0x5f,
+    0xfd, 0x74, 0x84, 0xb0, 0x12, 0x91, 0x09, 0x1c, 0x5f, 0x87, 0xb9, 0x88, 0x83, 0x46, 0x3f, 0x98,
+    0x09, 0x1a, 0x0b, 0xaa, 0xae,
+];
+
+#[derive(CandidType, Deserialize, Debug, Clone)]
+pub struct CertifiedSignature {
+    // CBOR-encoded certificate of the issuer canister, obtained during a query call
+    pub certificate: Vec<u8>,
+    // CBOR-encoded hash tree, proving that the hash is a part of the issuer's certified data
+    pub witness: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum CertificationError {
+    SignatureNotFound,
+    MalformedCertificate(String),
+    MalformedWitness(String),
+    MalformedPublicKey,
+    InvalidSignature,
+    NestedDelegationNotAllowed,
+    CanisterIsOutOfSubnetRanges,
+    CertifiedDataNotFound,
+    CertifiedDataMismatch,
+    HashNotCertified,
+}
+
+// Hashes of everything a canister has issued. The root hash should be set as the canister's
+// certified data after each modification, so the IC could vouch for each of the hashes.
+#[derive(Default)]
+pub struct CertifiedHashes {
+    tree: RbTree<Vec<u8>, Vec<u8>>,
+}
+
+impl CertifiedHashes {
+    pub fn insert(&mut self, hash: Hash) {
+        self.tree.insert(hash.to_vec(), Vec::new());
+    }
+
+    pub fn remove(&mut self, hash: &Hash) {
+        self.tree.delete(hash);
+    }
+
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.tree.get(hash).is_some()
+    }
+
+    pub fn root_hash(&self) -> Hash {
+        labeled_hash(CERTIFIED_HASHES_LABEL, &self.tree.root_hash())
+    }
+
+    // the certificate should be obtained with data_certificate() during a query call
+    pub fn sign(&self, hash: &Hash, certificate: Vec<u8>) -> CertifiedSignature {
+        let witness = labeled(CERTIFIED_HASHES_LABEL, self.tree.witness(hash));
+
+        CertifiedSignature {
+            certificate,
+            witness: serde_cbor::to_vec(&witness).expect("Unable to encode a witness"),
+        }
+    }
+
+    fn list(&self) -> Vec<Vec<u8>> {
+        self.tree.iter().map(|(hash, _)| hash.clone()).collect()
+    }
+}
+
+// only hashes are persisted, the tree is rebuilt on deserialization
+impl CandidType for CertifiedHashes {
+    fn _ty() -> Type {
+        Vec::<Vec<u8>>::ty()
+    }
+
+    fn idl_serialize<S>(&self, serializer: S) -> Result<(), S::Error>
+    where
+        S: Serializer,
+    {
+        self.list().idl_serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CertifiedHashes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hashes = Vec::<Vec<u8>>::deserialize(deserializer)?;
+
+        Ok(Self {
+            tree: hashes.into_iter().map(|hash| (hash, Vec::new())).collect(),
+        })
+    }
+}
+
+// checks that the issuer's certified data contains the hash and that the IC (with the provided
+// root key) vouches for it - works without the IC, so could be checked with locally generated keys
+pub fn verify_certified_hash(
+    hash: &Hash,
+    signature: &CertifiedSignature,
+    issuer: &Principal,
+    root_key: &[u8],
+) -> Result<(), CertificationError> {
+    let certificate: Certificate = serde_cbor::from_slice(&signature.certificate)
+        .map_err(|e| CertificationError::MalformedCertificate(e.to_string()))?;
+
+    verify_certificate(&certificate, issuer, root_key)?;
+
+    let witness: HashTree = serde_cbor::from_slice(&signature.witness)
+        .map_err(|e| CertificationError::MalformedWitness(e.to_string()))?;
+
+    let certified_data_path: [&[u8]; 3] = [b"canister", issuer.as_slice(), b"certified_data"];
+    let certified_data = match certificate.tree.lookup_path(certified_data_path) {
+        LookupResult::Found(data) => data,
+        _ => return Err(CertificationError::CertifiedDataNotFound),
+    };
+
+    if certified_data != witness.digest() {
+        return Err(CertificationError::CertifiedDataMismatch);
+    }
+
+    match witness.lookup_path([CERTIFIED_HASHES_LABEL, hash]) {
+        LookupResult::Found(_) => Ok(()),
+        _ => Err(CertificationError::HashNotCertified),
+    }
+}
+
+fn verify_certificate(
+    certificate: &Certificate,
+    issuer: &Principal,
+    root_key: &[u8],
+) -> Result<(), CertificationError> {
+    let key = match &certificate.delegation {
+        None => extract_bls_public_key(root_key)?.to_vec(),
+        Some(delegation) => {
+            let delegation_certificate: Certificate =
+                serde_cbor::from_slice(&delegation.certificate)
+                    .map_err(|e| CertificationError::MalformedCertificate(e.to_string()))?;
+
+            if delegation_certificate.delegation.is_some() {
+                return Err(CertificationError::NestedDelegationNotAllowed);
+            }
+
+            verify_certificate_signature(
+                &delegation_certificate,
+                extract_bls_public_key(root_key)?,
+            )?;
+
+            let ranges_path: [&[u8]; 3] = [b"subnet", &delegation.subnet_id, b"canister_ranges"];
+            let ranges = match delegation_certificate.tree.lookup_path(ranges_path) {
+                LookupResult::Found(ranges) => ranges,
+                _ => {
+                    return Err(CertificationError::MalformedCertificate(String::from(
+                        "Subnet canister ranges not found",
+                    )))
+                }
+            };
+
+            let ranges: Vec<(ByteBuf, ByteBuf)> = serde_cbor::from_slice(ranges)
+                .map_err(|e| CertificationError::MalformedCertificate(e.to_string()))?;
+
+            let issuer = issuer.as_slice();
+            if !ranges
+                .iter()
+                .any(|(from, to)| from.as_slice() <= issuer && issuer <= to.as_slice())
+            {
+                return Err(CertificationError::CanisterIsOutOfSubnetRanges);
+            }
+
+            let key_path: [&[u8]; 3] = [b"subnet", &delegation.subnet_id, b"public_key"];
+            match delegation_certificate.tree.lookup_path(key_path) {
+                LookupResult::Found(key) => extract_bls_public_key(key)?.to_vec(),
+                _ => {
+                    return Err(CertificationError::MalformedCertificate(String::from(
+                        "Subnet public key not found",
+                    )))
+                }
+            }
+        }
+    };
+
+    verify_certificate_signature(certificate, &key)
+}
+
+fn verify_certificate_signature(
+    certificate: &Certificate,
+    key: &[u8],
+) -> Result<(), CertificationError> {
+    let mut message = IC_STATE_ROOT_DOMAIN_SEPARATOR.to_vec();
+    message.extend_from_slice(&certificate.tree.digest());
+
+    verify_bls_signature(&certificate.signature, &message, key)
+        .map_err(|_| CertificationError::InvalidSignature)
+}
+
+fn extract_bls_public_key(der_key: &[u8]) -> Result<&[u8], CertificationError> {
+    if !der_key.starts_with(BLS_PUBLIC_KEY_DER_PREFIX) {
+        return Err(CertificationError::MalformedPublicKey);
+    }
+
+    Ok(&der_key[BLS_PUBLIC_KEY_DER_PREFIX.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::certification::{
+        verify_certified_hash, CertificationError, CertifiedHashes, Hash, BLS_PUBLIC_KEY_DER_PREFIX,
+    };
+    use candid::Principal;
+    use ic_certification::{labeled, leaf, Certificate};
+    use ic_verify_bls_signature::PrivateKey;
+
+    // a key pair, which is used instead of the IC root key to sign certificates locally
+    fn root_key(seed: u8) -> (PrivateKey, Vec<u8>) {
+        let mut secret = [0u8; PrivateKey::BYTES];
+        secret[PrivateKey::BYTES - 1] = seed.max(1);
+
+        let private_key = PrivateKey::deserialize(&secret).unwrap();
+
+        let mut root_key = BLS_PUBLIC_KEY_DER_PREFIX.to_vec();
+        root_key.extend_from_slice(&private_key.public_key().serialize());
+
+        (private_key, root_key)
+    }
+
+    // a certificate, which vouches for the canister's certified data the same way the IC does
+    fn certificate(
+        canister_id: Principal,
+        certified_data: &Hash,
+        private_key: &PrivateKey,
+    ) -> Vec<u8> {
+        let tree = labeled(
+            "canister",
+            labeled(
+                canister_id.as_slice(),
+                labeled("certified_data", leaf(certified_data.to_vec())),
+            ),
+        );
+
+        let mut message = b"\x0Dic-state-root".to_vec();
+        message.extend_from_slice(&tree.digest());
+
+        let certificate = Certificate {
+            tree,
+            signature: private_key.sign(&message).serialize().to_vec(),
+            delegation: None,
+        };
+
+        serde_cbor::to_vec(&certificate).unwrap()
+    }
+
+    fn issuer() -> Principal {
+        Principal::from_slice(&[1, 2, 3])
+    }
+
+    // hashes of the issuer and a certificate of them, signed with the root key of the given seed
+    fn certified_hashes(hash: Hash, seed: u8) -> (CertifiedHashes, Vec<u8>) {
+        let mut hashes = CertifiedHashes::default();
+        hashes.insert(hash);
+        hashes.insert([2u8; 32]);
+
+        let (private_key, _) = root_key(seed);
+        let certificate = certificate(issuer(), &hashes.root_hash(), &private_key);
+
+        (hashes, certificate)
+    }
+
+    #[test]
+    fn valid_proof_is_verified() {
+        let hash = [1u8; 32];
+        let (hashes, certificate) = certified_hashes(hash, 1);
+        let signature = hashes.sign(&hash, certificate);
+
+        assert!(verify_certified_hash(&hash, &signature, &issuer(), &root_key(1).1).is_ok());
+    }
+
+    #[test]
+    fn wrong_hash_is_not_verified() {
+        let hash = [1u8; 32];
+        let (hashes, certificate) = certified_hashes(hash, 1);
+        let signature = hashes.sign(&hash, certificate);
+
+        assert!(matches!(
+            verify_certified_hash(&[3u8; 32], &signature, &issuer(), &root_key(1).1),
+            Err(CertificationError::HashNotCertified)
+        ));
+    }
+
+    #[test]
+    fn wrong_root_key_is_not_verified() {
+        let hash = [1u8; 32];
+        let (hashes, certificate) = certified_hashes(hash, 1);
+        let signature = hashes.sign(&hash, certificate);
+
+        assert!(matches!(
+            verify_certified_hash(&hash, &signature, &issuer(), &root_key(2).1),
+            Err(CertificationError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn wrong_issuer_is_not_verified() {
+        let hash = [1u8; 32];
+        let (hashes, certificate) = certified_hashes(hash, 1);
+        let signature = hashes.sign(&hash, certificate);
+
+        assert!(matches!(
+            verify_certified_hash(
+                &hash,
+                &signature,
+                &Principal::from_slice(&[4, 5, 6]),
+                &root_key(1).1
+            ),
+            Err(CertificationError::CertifiedDataNotFound)
+        ));
+    }
+}
@@ -163,6 +163,18 @@ pub enum ProgramExecutionResult {
     RemoteCallSequence(Vec<CandidCallResult<Blob>>),
 }
 
+impl ProgramExecutionResult {
+    // the sequence is always interrupted right after the failed call
+    pub fn get_failed_call_index(&self) -> Option<usize> {
+        match self {
+            ProgramExecutionResult::Empty => None,
+            ProgramExecutionResult::RemoteCallSequence(results) => {
+                results.iter().position(|it| it.is_err())
+            }
+        }
+    }
+}
+
 impl Program {
     pub fn validate(&self) -> Result<(), ValidationError> {
         match self {
@@ -178,12 +190,17 @@ impl Program {
     }
 
     pub async fn execute(&self) -> ProgramExecutionResult {
+        self.execute_from(0).await
+    }
+
+    // skips first "start" calls of the sequence, useful to continue an interrupted execution
+    pub async fn execute_from(&self, start: usize) -> ProgramExecutionResult {
         match self {
             Program::Empty => ProgramExecutionResult::Empty,
             Program::RemoteCallSequence(seq) => {
                 let mut results = vec![];
 
-                for call in seq {
+                for call in seq.iter().skip(start) {
                     let result = call.do_call_raw().await;
 
                     if result.is_err() {
@@ -1,5 +1,7 @@
-use candid::{CandidType, Deserialize, Principal};
+use crate::certification::{CertifiedSignature, Hash};
 use crate::types::wallet::{GroupId, Shares};
+use candid::{CandidType, Deserialize, Encode, Principal};
+use sha2::{Digest, Sha256};
 
 #[derive(Clone, CandidType, Deserialize)]
 pub struct SharesInfo {
@@ -8,13 +10,22 @@ pub struct SharesInfo {
     pub timestamp: u64,
     pub group_id: GroupId,
     pub principal_id: Principal,
-    // TODO: implement subnet signature
-    pub signature: (),
+    // is only present, when the shares info is fetched from its issuer with a query call
+    pub signature: Option<CertifiedSignature>,
 }
 
 impl SharesInfo {
-    pub fn is_signature_valid(&self) -> bool {
-        // TODO: implement subnet signature
-        true
+    // the signature itself is not a part of the hash
+    pub fn hash(&self) -> Hash {
+        let encoded = Encode!(
+            &self.balance,
+            &self.total_supply,
+            &self.timestamp,
+            &self.group_id,
+            &self.principal_id
+        )
+        .expect("Unable to encode shares info");
+
+        Sha256::digest(encoded).into()
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
-use crate::repository::{set_repositories, take_repositories};
+use crate::certification::{set_certification, take_certification, Certification};
+use crate::repository::{set_repositories, take_repositories, LegacyRepositories, Repositories};
 use crate::service::access_config::types::AccessConfigService;
 use crate::service::cron::CronService;
 use crate::service::events::EventsService;
@@ -17,6 +18,7 @@ use ic_event_hub::{implement_event_emitter, implement_subscribe, implement_unsub
 use shared::time::secs;
 use shared::types::wallet::Shares;
 
+pub mod certification;
 pub mod client;
 pub mod common;
 pub mod controller;
@@ -31,6 +33,7 @@ pub struct InitRequest {
     pub wallet_creator: Principal,
     pub union_name: String,
     pub union_description: String,
+    pub ic_root_key_opt: Option<Vec<u8>>,
 }
 
 #[init]
@@ -39,6 +42,7 @@ fn init(req: InitRequest) {
         req.history_ledger,
         req.union_name,
         req.union_description,
+        req.ic_root_key_opt,
         time(),
     );
 
@@ -58,10 +62,37 @@ fn init(req: InitRequest) {
 
 #[post_upgrade]
 fn post_upgrade_hook() {
-    let (repos, cron, events, settings) = stable_restore().expect("Unable to stable restore");
+    let (repos, cron, events, settings, certification): (
+        Option<Repositories>,
+        _,
+        _,
+        _,
+        Option<Certification>,
+    ) = stable_restore().expect("Unable to stable restore");
+
+    // the state of older unions doesn't fit the current repositories, so it is decoded as None
+    let repos = repos.or_else(|| {
+        let (legacy_repos,): (Option<LegacyRepositories>,) =
+            stable_restore().expect("Unable to stable restore legacy repositories");
+
+        legacy_repos.map(Repositories::from)
+    });
 
     set_repositories(repos);
+
+    if let Some((comment_permission_id, moderate_comments_permission_id)) =
+        PermissionService::init_missing_comment_permissions()
+    {
+        AccessConfigService::grant_comment_permissions(
+            comment_permission_id,
+            moderate_comments_permission_id,
+        );
+    }
+
     set_settings(settings);
+    // unions upgraded from versions without certification have nothing to restore
+    set_certification(certification.unwrap_or_default());
+    Certification::with(|it| it.update_certified_data());
     _put_cron_state(cron);
     _put_event_hub_state(events);
 }
@@ -73,6 +104,7 @@ fn pre_upgrade_hook() {
         _take_cron_state(),
         _take_event_hub_state(),
         take_settings(),
+        Some(take_certification()),
     ))
     .expect("Unable to stable save");
 }
@@ -0,0 +1,207 @@
+use crate::service::access_config::types::QueryDelegationProof;
+use crate::settings::Settings;
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::{data_certificate, set_certified_data};
+use shared::certification::{
+    verify_certified_hash, CertificationError, CertifiedHashes, CertifiedSignature, Hash,
+};
+use shared::types::history_ledger::SharesInfo;
+use shared::types::wallet::GroupId;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+// every member could request shares infos at any moments, so only the latest ones are kept
+pub const MAX_CERTIFIED_SHARES_INFOS_PER_MEMBER: usize = 20;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct Certification {
+    hashes: CertifiedHashes,
+
+    // only the latest proof of each delegate stays certified
+    query_delegation_proofs: BTreeMap<Principal, QueryDelegationProof>,
+    shares_infos: BTreeMap<(GroupId, Principal, u64), SharesInfo>,
+}
+
+impl Certification {
+    pub fn certify_query_delegation_proof(&mut self, proof: QueryDelegationProof) {
+        if let Some(prev_proof) = self.query_delegation_proofs.remove(&proof.delegate_id) {
+            self.hashes.remove(&prev_proof.hash());
+        }
+
+        self.hashes.insert(proof.hash());
+        self.query_delegation_proofs
+            .insert(proof.delegate_id, proof);
+
+        self.update_certified_data();
+    }
+
+    pub fn certify_shares_info(&mut self, shares_info: SharesInfo) {
+        self.insert_shares_info(shares_info);
+        self.update_certified_data();
+    }
+
+    // signatures are only available during query calls
+    pub fn get_query_delegation_proof(&self, delegate: &Principal) -> Option<QueryDelegationProof> {
+        let mut proof = self.query_delegation_proofs.get(delegate).cloned()?;
+        proof.signature = self.sign(&proof.hash());
+
+        Some(proof)
+    }
+
+    pub fn get_shares_info(
+        &self,
+        group_id: GroupId,
+        principal: Principal,
+        at: u64,
+    ) -> Option<SharesInfo> {
+        let mut shares_info = self.shares_infos.get(&(group_id, principal, at)).cloned()?;
+        shares_info.signature = self.sign(&shares_info.hash());
+
+        Some(shares_info)
+    }
+
+    pub fn is_certified(&self, hash: &Hash) -> bool {
+        self.hashes.contains(hash)
+    }
+
+    // data issued by this union is checked against its own hashes, the rest should be signed by the issuer
+    pub fn verify(
+        &self,
+        hash: &Hash,
+        signature_opt: &Option<CertifiedSignature>,
+        issuer: Principal,
+        this_union_id: Principal,
+    ) -> Result<(), CertificationError> {
+        if issuer == this_union_id {
+            return if self.is_certified(hash) {
+                Ok(())
+            } else {
+                Err(CertificationError::HashNotCertified)
+            };
+        }
+
+        let signature = signature_opt
+            .as_ref()
+            .ok_or(CertificationError::SignatureNotFound)?;
+
+        verify_certified_hash(hash, signature, &issuer, Settings::get().get_ic_root_key())
+    }
+
+    // certified data should be restored after each upgrade
+    pub fn update_certified_data(&self) {
+        set_certified_data(&self.hashes.root_hash());
+    }
+
+    pub fn with<R>(f: impl FnOnce(&mut Certification) -> R) -> R {
+        CERTIFICATION.with(|it| f(&mut it.borrow_mut()))
+    }
+
+    fn insert_shares_info(&mut self, shares_info: SharesInfo) {
+        let key = (
+            shares_info.group_id,
+            shares_info.principal_id,
+            shares_info.timestamp,
+        );
+
+        // the same moment could be requested again - its balance could change, if it is in the future
+        if let Some(prev_shares_info) = self.shares_infos.remove(&key) {
+            self.hashes.remove(&prev_shares_info.hash());
+        }
+
+        self.hashes.insert(shares_info.hash());
+        self.shares_infos.insert(key, shares_info);
+
+        // the oldest moments are evicted first, except for the one which is just requested
+        let (group_id, principal, at) = key;
+        let keys: Vec<_> = self
+            .shares_infos
+            .range((group_id, principal, u64::MIN)..=(group_id, principal, u64::MAX))
+            .map(|(key, _)| *key)
+            .filter(|(_, _, timestamp)| *timestamp != at)
+            .collect();
+
+        for key in keys
+            .iter()
+            .take((keys.len() + 1).saturating_sub(MAX_CERTIFIED_SHARES_INFOS_PER_MEMBER))
+        {
+            let evicted = self.shares_infos.remove(key).unwrap();
+            self.hashes.remove(&evicted.hash());
+        }
+    }
+
+    fn sign(&self, hash: &Hash) -> Option<CertifiedSignature> {
+        let certificate = data_certificate()?;
+
+        Some(self.hashes.sign(hash, certificate))
+    }
+}
+
+thread_local! {
+    static CERTIFICATION: RefCell<Certification> = RefCell::new(Certification::default());
+}
+
+pub fn take_certification() -> Certification {
+    CERTIFICATION.with(|it| it.take())
+}
+
+pub fn set_certification(certification: Certification) {
+    CERTIFICATION.with(|it| it.replace(certification));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::certification::{Certification, MAX_CERTIFIED_SHARES_INFOS_PER_MEMBER};
+    use candid::{decode_args, encode_args, Principal};
+    use shared::types::history_ledger::SharesInfo;
+    use shared::types::wallet::Shares;
+
+    fn shares_info(balance: u64, timestamp: u64) -> SharesInfo {
+        SharesInfo {
+            balance: Shares::from(balance),
+            total_supply: Shares::from(100),
+            timestamp,
+            group_id: 0,
+            principal_id: Principal::anonymous(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn same_moment_is_certified_once() {
+        let mut certification = Certification::default();
+
+        certification.insert_shares_info(shares_info(10, 1));
+        certification.insert_shares_info(shares_info(20, 1));
+
+        assert_eq!(certification.shares_infos.len(), 1);
+        assert!(!certification.is_certified(&shares_info(10, 1).hash()));
+        assert!(certification.is_certified(&shares_info(20, 1).hash()));
+    }
+
+    #[test]
+    fn oldest_moments_are_evicted() {
+        let mut certification = Certification::default();
+        let max = MAX_CERTIFIED_SHARES_INFOS_PER_MEMBER as u64;
+
+        for timestamp in 1..=max + 1 {
+            certification.insert_shares_info(shares_info(10, timestamp));
+        }
+        certification.insert_shares_info(shares_info(10, 0));
+
+        assert_eq!(certification.shares_infos.len(), max as usize);
+        assert!(certification.is_certified(&shares_info(10, 0).hash()));
+        assert!(!certification.is_certified(&shares_info(10, 1).hash()));
+        assert!(!certification.is_certified(&shares_info(10, 2).hash()));
+        assert!(certification.is_certified(&shares_info(10, max + 1).hash()));
+    }
+
+    #[test]
+    fn state_without_certification_is_restored() {
+        // stable state of unions, which are upgraded from versions without certification
+        let bytes = encode_args((1u64, String::from("settings"))).unwrap();
+        let (_, _, certification): (u64, String, Option<Certification>) =
+            decode_args(&bytes).unwrap();
+
+        assert!(certification.is_none());
+    }
+}
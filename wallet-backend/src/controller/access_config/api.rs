@@ -71,7 +71,7 @@ pub struct ListAccessConfigsResponse {
 // ----------- PERSONAL ------------
 
 #[derive(CandidType, Deserialize)]
-pub struct GetMyQueryDelegationProofRequest {
+pub struct IssueMyQueryDelegationProofRequest {
     pub requested_targets: BTreeSet<PermissionTarget>,
 }
 
@@ -1,8 +1,8 @@
 use crate::controller::access_config::api::{
     CreateAccessConfigRequest, CreateAccessConfigResponse, DeleteAccessConfigRequest,
     ExecuteRequest, ExecuteResponse, GetAccessConfigRequest, GetAccessConfigResponse,
-    GetMyQueryDelegationProofRequest, GetMyQueryDelegationProofResponse, ListAccessConfigsRequest,
-    ListAccessConfigsResponse, UpdateAccessConfigRequest,
+    GetMyQueryDelegationProofResponse, IssueMyQueryDelegationProofRequest,
+    ListAccessConfigsRequest, ListAccessConfigsResponse, UpdateAccessConfigRequest,
 };
 use crate::guards::{only_self, only_self_or_with_access};
 use crate::service::access_config::types::AccessConfigService;
@@ -75,15 +75,19 @@ fn list_access_configs(req: ListAccessConfigsRequest) -> ListAccessConfigsRespon
 
 // ------------- PERSONAL --------------
 
-#[query]
-fn get_my_query_delegation_proof(
-    req: GetMyQueryDelegationProofRequest,
-) -> GetMyQueryDelegationProofResponse {
-    let proof = AccessConfigService::get_query_delegation_proof(
+#[update]
+fn issue_my_query_delegation_proof(req: IssueMyQueryDelegationProofRequest) {
+    AccessConfigService::issue_query_delegation_proof(
         id(),
         caller(),
         req.requested_targets,
         time(),
     );
+}
+
+#[query]
+fn get_my_query_delegation_proof() -> GetMyQueryDelegationProofResponse {
+    let proof = AccessConfigService::get_query_delegation_proof(&caller())
+        .expect("Unable to get my query delegation proof");
     GetMyQueryDelegationProofResponse { proof }
 }
@@ -0,0 +1,57 @@
+use crate::repository::choice_amendment::model::ChoiceAmendment;
+use crate::repository::choice_amendment::types::{ChoiceAmendmentFilter, ChoiceAmendmentId};
+use crate::service::access_config::types::QueryDelegationProof;
+use candid::{CandidType, Deserialize};
+use shared::pageable::{Page, PageRequest};
+use shared::remote_call::Program;
+use shared::types::wallet::ChoiceId;
+
+#[derive(CandidType, Deserialize)]
+pub struct ProposeChoiceAmendmentRequest {
+    pub choice_id: ChoiceId,
+    pub name: String,
+    pub description: Option<String>,
+    pub program: Option<Program>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ProposeChoiceAmendmentResponse {
+    pub id: ChoiceAmendmentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct AcceptChoiceAmendmentRequest {
+    pub id: ChoiceAmendmentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct AcceptChoiceAmendmentResponse {
+    pub choice_id: ChoiceId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RejectChoiceAmendmentRequest {
+    pub id: ChoiceAmendmentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetChoiceAmendmentRequest {
+    pub id: ChoiceAmendmentId,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetChoiceAmendmentResponse {
+    pub amendment: ChoiceAmendment,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListChoiceAmendmentsRequest {
+    pub page_req: PageRequest<ChoiceAmendmentFilter, ()>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListChoiceAmendmentsResponse {
+    pub page: Page<ChoiceAmendment>,
+}
@@ -0,0 +1,63 @@
+use crate::controller::choice_amendment::api::{
+    AcceptChoiceAmendmentRequest, AcceptChoiceAmendmentResponse, GetChoiceAmendmentRequest,
+    GetChoiceAmendmentResponse, ListChoiceAmendmentsRequest, ListChoiceAmendmentsResponse,
+    ProposeChoiceAmendmentRequest, ProposeChoiceAmendmentResponse, RejectChoiceAmendmentRequest,
+};
+use crate::guards::only_self_or_with_access;
+use crate::service::choice_amendment::types::ChoiceAmendmentService;
+use ic_cdk::api::time;
+use ic_cdk::caller;
+use ic_cdk_macros::{query, update};
+
+pub mod api;
+
+#[update]
+fn propose_choice_amendment(req: ProposeChoiceAmendmentRequest) -> ProposeChoiceAmendmentResponse {
+    only_self_or_with_access("propose_choice_amendment", None);
+
+    let id = ChoiceAmendmentService::propose_amendment(
+        &req.choice_id,
+        req.name,
+        req.description,
+        req.program,
+        caller(),
+        time(),
+    )
+    .expect("Unable to propose choice amendment");
+
+    ProposeChoiceAmendmentResponse { id }
+}
+
+// only the voting proposer or the union itself can accept or reject amendments
+#[update]
+fn accept_choice_amendment(req: AcceptChoiceAmendmentRequest) -> AcceptChoiceAmendmentResponse {
+    let choice_id = ChoiceAmendmentService::accept_amendment(&req.id, caller(), time())
+        .expect("Unable to accept choice amendment");
+
+    AcceptChoiceAmendmentResponse { choice_id }
+}
+
+#[update]
+fn reject_choice_amendment(req: RejectChoiceAmendmentRequest) {
+    ChoiceAmendmentService::reject_amendment(&req.id, caller(), time())
+        .expect("Unable to reject choice amendment");
+}
+
+#[query]
+fn get_choice_amendment(req: GetChoiceAmendmentRequest) -> GetChoiceAmendmentResponse {
+    only_self_or_with_access("get_choice_amendment", req.query_delegation_proof_opt);
+
+    let amendment =
+        ChoiceAmendmentService::get_amendment(&req.id).expect("Unable to get choice amendment");
+
+    GetChoiceAmendmentResponse { amendment }
+}
+
+#[query]
+fn list_choice_amendments(req: ListChoiceAmendmentsRequest) -> ListChoiceAmendmentsResponse {
+    only_self_or_with_access("list_choice_amendments", req.query_delegation_proof_opt);
+
+    let page = ChoiceAmendmentService::list_amendments(&req.page_req);
+
+    ListChoiceAmendmentsResponse { page }
+}
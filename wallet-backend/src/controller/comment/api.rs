@@ -0,0 +1,53 @@
+use crate::repository::comment::model::Comment;
+use crate::repository::comment::types::{CommentFilter, CommentId, CommentTarget};
+use crate::repository::profile::model::Profile;
+use crate::service::access_config::types::QueryDelegationProof;
+use candid::{CandidType, Deserialize};
+use shared::pageable::{Page, PageRequest};
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateCommentRequest {
+    pub target: CommentTarget,
+    pub reply_to: Option<CommentId>,
+    pub text: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateCommentResponse {
+    pub id: CommentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UpdateCommentRequest {
+    pub id: CommentId,
+    pub new_text: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct DeleteCommentRequest {
+    pub id: CommentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetCommentRequest {
+    pub id: CommentId,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetCommentResponse {
+    pub comment: Comment,
+    pub author_profile: Option<Profile>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListCommentsRequest {
+    pub page_req: PageRequest<CommentFilter, ()>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListCommentsResponse {
+    pub page: Page<Comment>,
+    pub author_profiles: Vec<Profile>,
+}
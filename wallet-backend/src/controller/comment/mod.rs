@@ -0,0 +1,58 @@
+use crate::controller::comment::api::{
+    CreateCommentRequest, CreateCommentResponse, DeleteCommentRequest, GetCommentRequest,
+    GetCommentResponse, ListCommentsRequest, ListCommentsResponse, UpdateCommentRequest,
+};
+use crate::guards::only_self_or_with_access;
+use crate::service::comment::types::CommentService;
+use ic_cdk::api::time;
+use ic_cdk::caller;
+use ic_cdk_macros::{query, update};
+
+pub mod api;
+
+// update methods are guarded by the comment permission targets inside the service
+
+#[update]
+fn create_comment(req: CreateCommentRequest) -> CreateCommentResponse {
+    let id = CommentService::create_comment(req.target, req.reply_to, req.text, caller(), time())
+        .expect("Unable to create comment");
+
+    CreateCommentResponse { id }
+}
+
+#[update]
+fn update_comment(req: UpdateCommentRequest) {
+    CommentService::update_comment(&req.id, req.new_text, caller(), time())
+        .expect("Unable to update comment");
+}
+
+#[update]
+fn delete_comment(req: DeleteCommentRequest) {
+    CommentService::delete_comment(&req.id, caller(), time()).expect("Unable to delete comment");
+}
+
+#[query]
+fn get_comment(req: GetCommentRequest) -> GetCommentResponse {
+    only_self_or_with_access("get_comment", req.query_delegation_proof_opt);
+
+    let comment = CommentService::get_comment(&req.id).expect("Unable to get comment");
+    let author_profile = CommentService::get_author_profiles(std::slice::from_ref(&comment)).pop();
+
+    GetCommentResponse {
+        comment,
+        author_profile,
+    }
+}
+
+#[query]
+fn list_comments(req: ListCommentsRequest) -> ListCommentsResponse {
+    only_self_or_with_access("list_comments", req.query_delegation_proof_opt);
+
+    let page = CommentService::list_comments(&req.page_req);
+    let author_profiles = CommentService::get_author_profiles(&page.data);
+
+    ListCommentsResponse {
+        page,
+        author_profiles,
+    }
+}
@@ -5,7 +5,7 @@ use crate::controller::history_ledger::api::{
 use crate::guards::only_self_or_with_access;
 use crate::service::history_ledger::HistoryLedgerService;
 use ic_cdk::caller;
-use ic_cdk_macros::update;
+use ic_cdk_macros::{query, update};
 
 pub mod api;
 
@@ -13,7 +13,7 @@ pub mod api;
 async fn get_shares_info_of_at(req: GetSharesInfoOfAtRequest) -> GetSharesInfoOfAtResponse {
     only_self_or_with_access("get_shares_info_of_at", req.query_delegation_proof_opt);
 
-    let shares_info = HistoryLedgerService::get_shares_info_of_at(req.group_id, req.of, req.at)
+    let shares_info = HistoryLedgerService::issue_shares_info_of_at(req.group_id, req.of, req.at)
         .await
         .expect("Unable to get shares info of at");
 
@@ -44,9 +44,17 @@ async fn list_program_execution_entry_ids(
 
 #[update]
 async fn get_my_shares_info_at(req: GetMySharesInfoAtRequest) -> GetSharesInfoOfAtResponse {
-    let shares_info = HistoryLedgerService::get_shares_info_of_at(req.group_id, caller(), req.at)
+    let shares_info = HistoryLedgerService::issue_shares_info_of_at(req.group_id, caller(), req.at)
         .await
         .expect("Unable to get my shares info at");
 
     GetSharesInfoOfAtResponse { shares_info }
 }
+
+#[query]
+fn get_my_certified_shares_info_at(req: GetMySharesInfoAtRequest) -> GetSharesInfoOfAtResponse {
+    let shares_info =
+        HistoryLedgerService::get_certified_shares_info_of_at(req.group_id, caller(), req.at);
+
+    GetSharesInfoOfAtResponse { shares_info }
+}
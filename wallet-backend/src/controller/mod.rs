@@ -1,11 +1,16 @@
 pub mod access_config;
+pub mod choice_amendment;
+pub mod comment;
 pub mod group;
 pub mod history_ledger;
 pub mod nested_voting;
 pub mod nested_voting_config;
 pub mod permission;
+pub mod pipeline;
 pub mod profile;
 pub mod settings;
 pub mod streaming;
+pub mod vote_delegation;
 pub mod voting;
 pub mod voting_config;
+pub mod voting_template;
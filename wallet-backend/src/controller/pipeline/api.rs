@@ -0,0 +1,53 @@
+use crate::repository::pipeline::model::Pipeline;
+use crate::repository::pipeline::types::{PipelineFilter, PipelineId};
+use crate::service::access_config::types::QueryDelegationProof;
+use candid::{CandidType, Deserialize};
+use shared::pageable::{Page, PageRequest};
+use shared::types::wallet::VotingConfigId;
+
+#[derive(CandidType, Deserialize)]
+pub struct CreatePipelineRequest {
+    pub name: String,
+    pub description: String,
+    pub stages: Vec<VotingConfigId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreatePipelineResponse {
+    pub id: PipelineId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UpdatePipelineRequest {
+    pub id: PipelineId,
+    pub name_opt: Option<String>,
+    pub description_opt: Option<String>,
+    pub stages_opt: Option<Vec<VotingConfigId>>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct DeletePipelineRequest {
+    pub id: PipelineId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetPipelineRequest {
+    pub id: PipelineId,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetPipelineResponse {
+    pub pipeline: Pipeline,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListPipelinesRequest {
+    pub page_req: PageRequest<PipelineFilter, ()>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListPipelinesResponse {
+    pub page: Page<Pipeline>,
+}
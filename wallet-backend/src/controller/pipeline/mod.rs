@@ -0,0 +1,52 @@
+use crate::controller::pipeline::api::{
+    CreatePipelineRequest, CreatePipelineResponse, DeletePipelineRequest, GetPipelineRequest,
+    GetPipelineResponse, ListPipelinesRequest, ListPipelinesResponse, UpdatePipelineRequest,
+};
+use crate::guards::{only_self, only_self_or_with_access};
+use crate::service::pipeline::types::PipelineService;
+use ic_cdk_macros::{query, update};
+
+pub mod api;
+
+#[update]
+fn create_pipeline(req: CreatePipelineRequest) -> CreatePipelineResponse {
+    only_self();
+
+    let id = PipelineService::create_pipeline(req.name, req.description, req.stages)
+        .expect("Unable to create pipeline");
+
+    CreatePipelineResponse { id }
+}
+
+#[update]
+fn update_pipeline(req: UpdatePipelineRequest) {
+    only_self();
+
+    PipelineService::update_pipeline(&req.id, req.name_opt, req.description_opt, req.stages_opt)
+        .expect("Unable to update pipeline");
+}
+
+#[update]
+fn delete_pipeline(req: DeletePipelineRequest) {
+    only_self();
+
+    PipelineService::delete_pipeline(&req.id).expect("Unable to delete pipeline");
+}
+
+#[query]
+fn get_pipeline(req: GetPipelineRequest) -> GetPipelineResponse {
+    only_self_or_with_access("get_pipeline", req.query_delegation_proof_opt);
+
+    let pipeline = PipelineService::get_pipeline(&req.id).expect("Unable to get pipeline");
+
+    GetPipelineResponse { pipeline }
+}
+
+#[query]
+fn list_pipelines(req: ListPipelinesRequest) -> ListPipelinesResponse {
+    only_self_or_with_access("list_pipelines", req.query_delegation_proof_opt);
+
+    let page = PipelineService::list_pipelines(&req.page_req);
+
+    ListPipelinesResponse { page }
+}
@@ -0,0 +1,46 @@
+use crate::repository::vote_delegation::model::VoteDelegation;
+use crate::repository::vote_delegation::types::{VoteDelegationFilter, VoteDelegationId};
+use crate::service::access_config::types::QueryDelegationProof;
+use candid::{CandidType, Deserialize, Principal};
+use shared::pageable::{Page, PageRequest};
+use shared::types::wallet::{GroupId, VotingConfigId};
+
+#[derive(CandidType, Deserialize)]
+pub struct ListVoteDelegationsRequest {
+    pub page_req: PageRequest<VoteDelegationFilter, ()>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListVoteDelegationsResponse {
+    pub page: Page<VoteDelegation>,
+}
+
+// ------------------- PERSONAL ------------------
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateMyVoteDelegationRequest {
+    pub delegate: Principal,
+    pub group_id: GroupId,
+    pub voting_config_id: Option<VotingConfigId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateMyVoteDelegationResponse {
+    pub id: VoteDelegationId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RevokeMyVoteDelegationRequest {
+    pub id: VoteDelegationId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListMyVoteDelegationsRequest {
+    pub page_req: PageRequest<(), ()>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListMyVoteDelegationsResponse {
+    pub page: Page<VoteDelegation>,
+}
@@ -0,0 +1,78 @@
+use crate::controller::vote_delegation::api::{
+    CreateMyVoteDelegationRequest, CreateMyVoteDelegationResponse, ListMyVoteDelegationsRequest,
+    ListMyVoteDelegationsResponse, ListVoteDelegationsRequest, ListVoteDelegationsResponse,
+    RevokeMyVoteDelegationRequest,
+};
+use crate::guards::only_self_or_with_access;
+use crate::repository::vote_delegation::types::VoteDelegationFilter;
+use crate::service::vote_delegation::types::VoteDelegationService;
+use ic_cdk::api::time;
+use ic_cdk::caller;
+use ic_cdk_macros::{query, update};
+use shared::pageable::PageRequest;
+
+pub mod api;
+
+#[query]
+fn list_vote_delegations(req: ListVoteDelegationsRequest) -> ListVoteDelegationsResponse {
+    only_self_or_with_access("list_vote_delegations", req.query_delegation_proof_opt);
+
+    let page = VoteDelegationService::list_vote_delegations(&req.page_req);
+    ListVoteDelegationsResponse { page }
+}
+
+// ------------------- PERSONAL ----------------------
+
+#[update]
+fn create_my_vote_delegation(req: CreateMyVoteDelegationRequest) -> CreateMyVoteDelegationResponse {
+    let id = VoteDelegationService::create_vote_delegation(
+        caller(),
+        req.delegate,
+        req.group_id,
+        req.voting_config_id,
+        time(),
+    )
+    .expect("Unable to create vote delegation");
+
+    CreateMyVoteDelegationResponse { id }
+}
+
+#[update]
+fn revoke_my_vote_delegation(req: RevokeMyVoteDelegationRequest) {
+    VoteDelegationService::revoke_vote_delegation(&req.id, caller())
+        .expect("Unable to revoke vote delegation");
+}
+
+#[query]
+fn list_my_delegates(req: ListMyVoteDelegationsRequest) -> ListMyVoteDelegationsResponse {
+    let page_req = PageRequest {
+        page_index: req.page_req.page_index,
+        page_size: req.page_req.page_size,
+        filter: VoteDelegationFilter {
+            delegator: Some(caller()),
+            delegate: None,
+            group: None,
+        },
+        sort: (),
+    };
+
+    let page = VoteDelegationService::list_vote_delegations(&page_req);
+    ListMyVoteDelegationsResponse { page }
+}
+
+#[query]
+fn list_my_delegators(req: ListMyVoteDelegationsRequest) -> ListMyVoteDelegationsResponse {
+    let page_req = PageRequest {
+        page_index: req.page_req.page_index,
+        page_size: req.page_req.page_size,
+        filter: VoteDelegationFilter {
+            delegator: None,
+            delegate: Some(caller()),
+            group: None,
+        },
+        sort: (),
+    };
+
+    let page = VoteDelegationService::list_vote_delegations(&page_req);
+    ListMyVoteDelegationsResponse { page }
+}
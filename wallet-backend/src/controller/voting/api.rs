@@ -1,13 +1,16 @@
 use crate::repository::choice::model::Choice;
 use crate::repository::choice::types::ChoiceFilter;
 use crate::repository::nested_voting::types::RemoteVotingId;
+use crate::repository::pipeline::types::PipelineId;
 use crate::repository::voting::model::Voting;
-use crate::repository::voting::types::VotingSort;
+use crate::repository::voting::types::{VotingFilter, VotingSort};
+use crate::repository::voting_config::types::Fraction;
 use crate::service::access_config::types::QueryDelegationProof;
-use crate::service::voting::types::Vote;
-use candid::{CandidType, Deserialize};
+use crate::service::voting::types::{Vote, VotingError};
+use candid::{CandidType, Deserialize, Principal};
 use shared::pageable::{Page, PageRequest};
-use shared::remote_call::Program;
+use shared::remote_call::{Program, ProgramExecutionResult};
+use shared::types::history_ledger::SharesInfo;
 use shared::types::wallet::{ChoiceId, GroupId, Shares, VotingConfigId, VotingId};
 use std::collections::BTreeMap;
 
@@ -17,6 +20,7 @@ pub struct CreateVotingRequest {
     pub name: String,
     pub description: String,
     pub winners_need: u32,
+    pub pipeline_id: Option<PipelineId>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -43,6 +47,43 @@ pub struct CastMyVoteRequest {
     pub vote: Vote,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct CastMyVotesRequest {
+    pub votes: Vec<CastMyVoteRequest>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CastMyVotesResponse {
+    // in the same order as the requested votes
+    pub results: Vec<Result<(), VotingError>>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RetryVotingExecutionRequest {
+    pub id: VotingId,
+    pub choice_id: ChoiceId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RetryVotingExecutionResponse {
+    pub result: ProgramExecutionResult,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CommitMyVoteRequest {
+    pub id: VotingId,
+    pub shares_info: SharesInfo,
+    pub hash: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RevealMyVoteRequest {
+    pub id: VotingId,
+    pub group_id: GroupId,
+    pub vote: BTreeMap<ChoiceId, Fraction>,
+    pub salt: Vec<u8>,
+}
+
 #[derive(CandidType, Deserialize)]
 pub struct GetVotingRequest {
     pub id: VotingId,
@@ -56,7 +97,7 @@ pub struct GetVotingResponse {
 
 #[derive(CandidType, Deserialize)]
 pub struct ListVotingsRequest {
-    pub page_req: PageRequest<(), VotingSort>,
+    pub page_req: PageRequest<VotingFilter, VotingSort>,
     pub query_delegation_proof_opt: Option<QueryDelegationProof>,
 }
 
@@ -124,6 +165,21 @@ pub struct GetVotingResultsRequest {
 #[derive(CandidType, Deserialize)]
 pub struct GetVotingResultsResponse {
     pub results: BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>,
+    pub spent_shares: BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetVotePowerRequest {
+    pub voting_id: VotingId,
+    pub group_id: GroupId,
+    pub voter: Principal,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetVotePowerResponse {
+    pub own: Shares,
+    pub delegated: BTreeMap<Principal, Shares>,
 }
 
 // ------------------- PERSONAL ------------------
@@ -1,9 +1,12 @@
 use crate::controller::voting::api::{
-    CastMyVoteRequest, CreateVotingChoiceRequest, CreateVotingChoiceResponse, CreateVotingRequest,
+    CastMyVoteRequest, CastMyVotesRequest, CastMyVotesResponse, CommitMyVoteRequest,
+    CreateVotingChoiceRequest, CreateVotingChoiceResponse, CreateVotingRequest,
     CreateVotingResponse, DeleteVotingChoiceRequest, DeleteVotingRequest, GetMyVoteRequest,
-    GetMyVoteResponse, GetVotingChoiceRequest, GetVotingChoiceResponse, GetVotingRequest,
-    GetVotingResponse, GetVotingResultsRequest, GetVotingResultsResponse, ListVotingChoicesRequest,
-    ListVotingChoicesResponse, ListVotingsRequest, ListVotingsResponse, UpdateVotingChoiceRequest,
+    GetMyVoteResponse, GetVotePowerRequest, GetVotePowerResponse, GetVotingChoiceRequest,
+    GetVotingChoiceResponse, GetVotingRequest, GetVotingResponse, GetVotingResultsRequest,
+    GetVotingResultsResponse, ListVotingChoicesRequest, ListVotingChoicesResponse,
+    ListVotingsRequest, ListVotingsResponse, RetryVotingExecutionRequest,
+    RetryVotingExecutionResponse, RevealMyVoteRequest, UpdateVotingChoiceRequest,
     UpdateVotingRequest,
 };
 use crate::guards::{only_self, only_self_or_with_access};
@@ -24,6 +27,7 @@ fn create_voting(req: CreateVotingRequest) -> CreateVotingResponse {
         req.name,
         req.description,
         req.winners_need,
+        req.pipeline_id,
         caller(),
         time(),
     )
@@ -46,6 +50,17 @@ fn update_voting(req: UpdateVotingRequest) {
     .expect("Unable to update voting");
 }
 
+#[update]
+async fn retry_voting_execution(req: RetryVotingExecutionRequest) -> RetryVotingExecutionResponse {
+    only_self();
+
+    let result = VotingService::retry_execution(&req.id, &req.choice_id, time())
+        .await
+        .expect("Unable to retry voting execution");
+
+    RetryVotingExecutionResponse { result }
+}
+
 #[update]
 fn create_voting_choice(req: CreateVotingChoiceRequest) -> CreateVotingChoiceResponse {
     only_self();
@@ -86,7 +101,7 @@ fn delete_voting_choice(req: DeleteVotingChoiceRequest) {
 fn delete_voting(req: DeleteVotingRequest) {
     only_self();
 
-    VotingService::delete_voting(&req.id).expect("Unable to delete voting");
+    VotingService::delete_voting(&req.id, time()).expect("Unable to delete voting");
 }
 
 #[query]
@@ -128,14 +143,56 @@ fn get_voting_results(req: GetVotingResultsRequest) -> GetVotingResultsResponse
 
     let results =
         VotingService::get_voting_results(&req.voting_id).expect("Unable to get voting results");
-    GetVotingResultsResponse { results }
+    let spent_shares = VotingService::get_voting_spent_shares(&req.voting_id)
+        .expect("Unable to get voting spent shares");
+
+    GetVotingResultsResponse {
+        results,
+        spent_shares,
+    }
+}
+
+#[query]
+fn get_vote_power(req: GetVotePowerRequest) -> GetVotePowerResponse {
+    only_self_or_with_access("get_vote_power", req.query_delegation_proof_opt);
+
+    let (own, delegated) = VotingService::get_vote_power(&req.voting_id, req.group_id, req.voter)
+        .expect("Unable to get vote power");
+
+    GetVotePowerResponse { own, delegated }
 }
 
 // ------------------- PERSONAL ----------------------
 
 #[update]
-fn cast_my_vote(req: CastMyVoteRequest) {
-    VotingService::cast_vote(&req.id, req.vote, caller(), time()).expect("Unable to cast vote");
+async fn cast_my_vote(req: CastMyVoteRequest) {
+    VotingService::cast_my_vote(&req.id, req.vote, caller(), time())
+        .await
+        .expect("Unable to cast vote");
+}
+
+#[update]
+async fn cast_my_votes(req: CastMyVotesRequest) -> CastMyVotesResponse {
+    let votes = req.votes.into_iter().map(|it| (it.id, it.vote)).collect();
+
+    let results = VotingService::cast_my_votes(votes, caller(), time())
+        .await
+        .expect("Unable to cast votes");
+
+    CastMyVotesResponse { results }
+}
+
+#[update]
+async fn commit_my_vote(req: CommitMyVoteRequest) {
+    VotingService::commit_my_vote(&req.id, req.shares_info, req.hash, caller(), time())
+        .await
+        .expect("Unable to commit vote");
+}
+
+#[update]
+fn reveal_my_vote(req: RevealMyVoteRequest) {
+    VotingService::reveal_vote(&req.id, req.group_id, req.vote, req.salt, caller(), time())
+        .expect("Unable to reveal vote");
 }
 
 #[query]
@@ -1,13 +1,16 @@
 use crate::repository::permission::types::PermissionId;
+use crate::repository::voting::types::RoundId;
 use crate::repository::voting_config::model::VotingConfig;
 use crate::repository::voting_config::types::{
-    LenInterval, RoundSettings, ThresholdValue, VotingConfigFilter,
+    CommitRevealSettings, Fraction, LenInterval, OverSubscriptionPolicy, ProposalBond,
+    RoundSettings, TallyMode, ThresholdValue, VotingConfigFilter, VotingPowerCap,
 };
 use crate::service::access_config::types::QueryDelegationProof;
+use crate::service::voting::types::RoundSimulation;
 use candid::{CandidType, Deserialize};
 use shared::pageable::{Page, PageRequest};
-use shared::types::wallet::VotingConfigId;
-use std::collections::BTreeSet;
+use shared::types::wallet::{ChoiceId, GroupId, Shares, VotingConfigId};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(CandidType, Deserialize)]
 pub struct CreateVotingConfigRequest {
@@ -17,6 +20,14 @@ pub struct CreateVotingConfigRequest {
     pub winners_count: Option<LenInterval>,
     pub permissions: BTreeSet<PermissionId>,
     pub round: RoundSettings,
+    pub tally_mode: TallyMode,
+    pub commit_reveal: Option<CommitRevealSettings>,
+    pub over_subscription: OverSubscriptionPolicy,
+    pub voting_power_cap: Option<VotingPowerCap>,
+    pub group_weights: BTreeMap<GroupId, Fraction>,
+    pub proposal_bond: Option<ProposalBond>,
+    pub max_open_votings_per_proposer: Option<u32>,
+    pub veto: Option<ThresholdValue>,
     pub approval: ThresholdValue,
     pub quorum: ThresholdValue,
     pub rejection: ThresholdValue,
@@ -38,6 +49,14 @@ pub struct UpdateVotingConfigRequest {
     pub winners_count_opt: Option<Option<LenInterval>>,
     pub permissions_opt: Option<BTreeSet<PermissionId>>,
     pub round_opt: Option<RoundSettings>,
+    pub tally_mode_opt: Option<TallyMode>,
+    pub commit_reveal_opt: Option<Option<CommitRevealSettings>>,
+    pub over_subscription_opt: Option<OverSubscriptionPolicy>,
+    pub voting_power_cap_opt: Option<Option<VotingPowerCap>>,
+    pub group_weights_opt: Option<BTreeMap<GroupId, Fraction>>,
+    pub proposal_bond_opt: Option<Option<ProposalBond>>,
+    pub max_open_votings_per_proposer_opt: Option<Option<u32>>,
+    pub veto_opt: Option<Option<ThresholdValue>>,
     pub approval_opt: Option<ThresholdValue>,
     pub quorum_opt: Option<ThresholdValue>,
     pub rejection_opt: Option<ThresholdValue>,
@@ -71,3 +90,29 @@ pub struct ListVotingConfigsRequest {
 pub struct ListVotingConfigsResponse {
     pub page: Page<VotingConfig>,
 }
+
+#[derive(CandidType, Deserialize)]
+pub enum SimulatedVotingConfig {
+    Existing(VotingConfigId),
+    New(Box<VotingConfig>),
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SimulateVotingConfigRequest {
+    pub voting_config: SimulatedVotingConfig,
+    pub round: RoundId,
+    pub winners_left: u32,
+    pub total_voting_power_by_group: BTreeMap<GroupId, Shares>,
+    pub approval_votes: BTreeMap<GroupId, Shares>,
+    pub rejection_votes: BTreeMap<GroupId, Shares>,
+    pub abstain_votes: BTreeMap<GroupId, Shares>,
+    pub votes_by_choice: BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>,
+    pub spent_shares_by_group_opt: Option<BTreeMap<GroupId, Shares>>,
+    pub unrevealed_shares_by_group: BTreeMap<GroupId, Shares>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SimulateVotingConfigResponse {
+    pub simulation: RoundSimulation,
+}
@@ -1,9 +1,12 @@
 use crate::controller::voting_config::api::{
     CreateVotingConfigRequest, CreateVotingConfigResponse, DeleteVotingConfigRequest,
     GetVotingConfigRequest, GetVotingConfigResponse, ListVotingConfigsRequest,
-    ListVotingConfigsResponse, UpdateVotingConfigRequest,
+    ListVotingConfigsResponse, SimulateVotingConfigRequest, SimulateVotingConfigResponse,
+    SimulatedVotingConfig, UpdateVotingConfigRequest,
 };
 use crate::guards::{only_self, only_self_or_with_access};
+use crate::repository::voting_config::types::{VotingConfigParams, VotingConfigUpdate};
+use crate::service::voting::types::{SimulatedRound, VotingService};
 use crate::service::voting_config::types::VotingConfigService;
 use ic_cdk_macros::{query, update};
 
@@ -13,19 +16,27 @@ pub mod api;
 fn create_voting_config(req: CreateVotingConfigRequest) -> CreateVotingConfigResponse {
     only_self();
 
-    let id = VotingConfigService::create_voting_config(
-        req.name,
-        req.description,
-        req.choices_count,
-        req.winners_count,
-        req.permissions,
-        req.round,
-        req.approval,
-        req.quorum,
-        req.rejection,
-        req.win,
-        req.next_round,
-    )
+    let id = VotingConfigService::create_voting_config(VotingConfigParams {
+        name: req.name,
+        description: req.description,
+        choices_count: req.choices_count,
+        winners_count: req.winners_count,
+        permissions: req.permissions,
+        round: req.round,
+        tally_mode: req.tally_mode,
+        commit_reveal: req.commit_reveal,
+        over_subscription: req.over_subscription,
+        voting_power_cap: req.voting_power_cap,
+        group_weights: req.group_weights,
+        proposal_bond: req.proposal_bond,
+        max_open_votings_per_proposer: req.max_open_votings_per_proposer,
+        veto: req.veto,
+        approval: req.approval,
+        quorum: req.quorum,
+        rejection: req.rejection,
+        win: req.win,
+        next_round: req.next_round,
+    })
     .expect("Unable to create voting config");
     CreateVotingConfigResponse { id }
 }
@@ -36,17 +47,27 @@ fn update_voting_config(req: UpdateVotingConfigRequest) {
 
     VotingConfigService::update_voting_config(
         req.id,
-        req.name_opt,
-        req.description_opt,
-        req.choices_count_opt,
-        req.winners_count_opt,
-        req.permissions_opt,
-        req.round_opt,
-        req.approval_opt,
-        req.quorum_opt,
-        req.rejection_opt,
-        req.win_opt,
-        req.next_round_opt,
+        VotingConfigUpdate {
+            name_opt: req.name_opt,
+            description_opt: req.description_opt,
+            choices_count_opt: req.choices_count_opt,
+            winners_count_opt: req.winners_count_opt,
+            permissions_opt: req.permissions_opt,
+            round_opt: req.round_opt,
+            tally_mode_opt: req.tally_mode_opt,
+            commit_reveal_opt: req.commit_reveal_opt,
+            over_subscription_opt: req.over_subscription_opt,
+            voting_power_cap_opt: req.voting_power_cap_opt,
+            group_weights_opt: req.group_weights_opt,
+            proposal_bond_opt: req.proposal_bond_opt,
+            max_open_votings_per_proposer_opt: req.max_open_votings_per_proposer_opt,
+            veto_opt: req.veto_opt,
+            approval_opt: req.approval_opt,
+            quorum_opt: req.quorum_opt,
+            rejection_opt: req.rejection_opt,
+            win_opt: req.win_opt,
+            next_round_opt: req.next_round_opt,
+        },
     )
     .expect("Unable to update voting config");
 }
@@ -74,3 +95,33 @@ fn list_voting_configs(req: ListVotingConfigsRequest) -> ListVotingConfigsRespon
     let page = VotingConfigService::list_voting_configs(&req.page_req);
     ListVotingConfigsResponse { page }
 }
+
+#[query]
+fn simulate_voting_config(req: SimulateVotingConfigRequest) -> SimulateVotingConfigResponse {
+    only_self_or_with_access("simulate_voting_config", req.query_delegation_proof_opt);
+
+    let voting_config = match req.voting_config {
+        SimulatedVotingConfig::Existing(id) => {
+            VotingConfigService::get_voting_config(&id).expect("Unable to get voting config")
+        }
+        SimulatedVotingConfig::New(voting_config) => *voting_config,
+    };
+
+    let simulation = VotingService::simulate_round(
+        &voting_config,
+        SimulatedRound {
+            round: req.round,
+            winners_left: req.winners_left,
+            total_voting_power_by_group: req.total_voting_power_by_group,
+            approval_votes: req.approval_votes,
+            rejection_votes: req.rejection_votes,
+            abstain_votes: req.abstain_votes,
+            votes_by_choice: req.votes_by_choice,
+            spent_shares_by_group_opt: req.spent_shares_by_group_opt,
+            unrevealed_shares_by_group: req.unrevealed_shares_by_group,
+        },
+    )
+    .expect("Unable to simulate voting config");
+
+    SimulateVotingConfigResponse { simulation }
+}
@@ -0,0 +1,72 @@
+use crate::repository::voting_template::model::VotingTemplate;
+use crate::repository::voting_template::types::{
+    TemplateChoice, TemplateParamKind, TemplateParamValue, VotingTemplateFilter, VotingTemplateId,
+};
+use crate::service::access_config::types::QueryDelegationProof;
+use candid::{CandidType, Deserialize};
+use shared::pageable::{Page, PageRequest};
+use shared::types::wallet::{VotingConfigId, VotingId};
+use std::collections::BTreeMap;
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateVotingTemplateRequest {
+    pub voting_config_id: VotingConfigId,
+    pub name: String,
+    pub description: String,
+    pub winners_need: u32,
+    pub params: BTreeMap<String, TemplateParamKind>,
+    pub choices: Vec<TemplateChoice>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateVotingTemplateResponse {
+    pub id: VotingTemplateId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UpdateVotingTemplateRequest {
+    pub id: VotingTemplateId,
+    pub name_opt: Option<String>,
+    pub description_opt: Option<String>,
+    pub winners_need_opt: Option<u32>,
+    pub params_opt: Option<BTreeMap<String, TemplateParamKind>>,
+    pub choices_opt: Option<Vec<TemplateChoice>>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct DeleteVotingTemplateRequest {
+    pub id: VotingTemplateId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetVotingTemplateRequest {
+    pub id: VotingTemplateId,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetVotingTemplateResponse {
+    pub voting_template: VotingTemplate,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListVotingTemplatesRequest {
+    pub page_req: PageRequest<VotingTemplateFilter, ()>,
+    pub query_delegation_proof_opt: Option<QueryDelegationProof>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListVotingTemplatesResponse {
+    pub page: Page<VotingTemplate>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateVotingFromTemplateRequest {
+    pub template_id: VotingTemplateId,
+    pub params: BTreeMap<String, TemplateParamValue>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateVotingFromTemplateResponse {
+    pub id: VotingId,
+}
@@ -0,0 +1,89 @@
+use crate::controller::voting_template::api::{
+    CreateVotingFromTemplateRequest, CreateVotingFromTemplateResponse, CreateVotingTemplateRequest,
+    CreateVotingTemplateResponse, DeleteVotingTemplateRequest, GetVotingTemplateRequest,
+    GetVotingTemplateResponse, ListVotingTemplatesRequest, ListVotingTemplatesResponse,
+    UpdateVotingTemplateRequest,
+};
+use crate::guards::{only_self, only_self_or_with_access};
+use crate::service::voting_template::types::VotingTemplateService;
+use ic_cdk::api::time;
+use ic_cdk::caller;
+use ic_cdk_macros::{query, update};
+
+pub mod api;
+
+#[update]
+fn create_voting_template(req: CreateVotingTemplateRequest) -> CreateVotingTemplateResponse {
+    only_self();
+
+    let id = VotingTemplateService::create_voting_template(
+        req.voting_config_id,
+        req.name,
+        req.description,
+        req.winners_need,
+        req.params,
+        req.choices,
+    )
+    .expect("Unable to create voting template");
+
+    CreateVotingTemplateResponse { id }
+}
+
+#[update]
+fn update_voting_template(req: UpdateVotingTemplateRequest) {
+    only_self();
+
+    VotingTemplateService::update_voting_template(
+        &req.id,
+        req.name_opt,
+        req.description_opt,
+        req.winners_need_opt,
+        req.params_opt,
+        req.choices_opt,
+    )
+    .expect("Unable to update voting template");
+}
+
+#[update]
+fn delete_voting_template(req: DeleteVotingTemplateRequest) {
+    only_self();
+
+    VotingTemplateService::delete_voting_template(&req.id)
+        .expect("Unable to delete voting template");
+}
+
+#[query]
+fn get_voting_template(req: GetVotingTemplateRequest) -> GetVotingTemplateResponse {
+    only_self_or_with_access("get_voting_template", req.query_delegation_proof_opt);
+
+    let voting_template =
+        VotingTemplateService::get_voting_template(&req.id).expect("Unable to get voting template");
+
+    GetVotingTemplateResponse { voting_template }
+}
+
+#[query]
+fn list_voting_templates(req: ListVotingTemplatesRequest) -> ListVotingTemplatesResponse {
+    only_self_or_with_access("list_voting_templates", req.query_delegation_proof_opt);
+
+    let page = VotingTemplateService::list_voting_templates(&req.page_req);
+
+    ListVotingTemplatesResponse { page }
+}
+
+#[update]
+fn create_voting_from_template(
+    req: CreateVotingFromTemplateRequest,
+) -> CreateVotingFromTemplateResponse {
+    only_self();
+
+    let id = VotingTemplateService::create_voting_from_template(
+        &req.template_id,
+        req.params,
+        caller(),
+        time(),
+    )
+    .expect("Unable to create voting from template");
+
+    CreateVotingFromTemplateResponse { id }
+}
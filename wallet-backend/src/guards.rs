@@ -1,3 +1,4 @@
+use crate::certification::Certification;
 use crate::repository::permission::types::PermissionTarget;
 use crate::service::access_config::types::{AccessConfigService, QueryDelegationProof};
 use ic_cdk::api::time;
@@ -39,6 +40,16 @@ pub fn only_self_or_with_access(method_name: &str, proof_opt: Option<QueryDelega
                 .validate(caller, time(), &targets)
                 .expect("Access denied");
 
+            Certification::with(|it| {
+                it.verify(
+                    &proof.hash(),
+                    &proof.signature,
+                    proof.union_id,
+                    this_union_id,
+                )
+            })
+            .expect("Access denied: query delegation proof is not certified");
+
             return;
         } else {
             trap(
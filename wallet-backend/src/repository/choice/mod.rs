@@ -1,4 +1,4 @@
-use crate::repository::choice::model::Choice;
+use crate::repository::choice::model::{Choice, LegacyChoice};
 use crate::repository::choice::types::ChoiceFilter;
 use candid::{CandidType, Deserialize};
 use shared::mvc::{IdGenerator, Model, Repository};
@@ -93,3 +93,27 @@ impl Repository<Choice, ChoiceId, ChoiceFilter, ()> for ChoiceRepository {
         }
     }
 }
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct LegacyChoiceRepository {
+    pub choices: HashMap<ChoiceId, LegacyChoice>,
+    pub id_gen: IdGenerator,
+
+    pub choices_by_voting_index: BTreeMap<VotingId, BTreeSet<ChoiceId>>,
+    pub choices_by_nested_voting_index: BTreeMap<NestedVotingId, BTreeSet<ChoiceId>>,
+}
+
+impl From<LegacyChoiceRepository> for ChoiceRepository {
+    fn from(it: LegacyChoiceRepository) -> Self {
+        Self {
+            choices: it
+                .choices
+                .into_iter()
+                .map(|(id, choice)| (id, Choice::from(choice)))
+                .collect(),
+            id_gen: it.id_gen,
+            choices_by_voting_index: it.choices_by_voting_index,
+            choices_by_nested_voting_index: it.choices_by_nested_voting_index,
+        }
+    }
+}
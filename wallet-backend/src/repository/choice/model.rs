@@ -19,6 +19,9 @@ pub struct Choice {
     description: String,
     program: Program,
     voting_power_by_group: BTreeMap<GroupId, TokenId>,
+    spent_shares_by_group: BTreeMap<GroupId, TokenId>,
+    raw_shares_by_group: BTreeMap<GroupId, TokenId>,
+    amendment_of: Option<ChoiceId>,
 }
 
 impl Choice {
@@ -35,6 +38,9 @@ impl Choice {
             description: Self::process_description(description)?,
             program,
             voting_power_by_group: BTreeMap::new(),
+            spent_shares_by_group: BTreeMap::new(),
+            raw_shares_by_group: BTreeMap::new(),
+            amendment_of: None,
         })
     }
 
@@ -58,6 +64,26 @@ impl Choice {
         .unwrap()
     }
 
+    pub fn new_abstain(voting_id: VotingId) -> Self {
+        Self::new(
+            String::from("Abstain"),
+            String::from("Count me in, but I don't support any choice"),
+            Program::Empty,
+            RemoteVotingId::Common(voting_id),
+        )
+        .unwrap()
+    }
+
+    pub fn new_veto(voting_id: VotingId) -> Self {
+        Self::new(
+            String::from("Veto"),
+            String::from("This decision should not be executed"),
+            Program::Empty,
+            RemoteVotingId::Common(voting_id),
+        )
+        .unwrap()
+    }
+
     pub fn update(
         &mut self,
         new_name: Option<String>,
@@ -92,6 +118,32 @@ impl Choice {
         &self.voting_power_by_group
     }
 
+    pub fn set_spent_shares_by_group_token(&mut self, group_id: GroupId, token_id: TokenId) {
+        assert!(!self.spent_shares_by_group.contains_key(&group_id));
+        self.spent_shares_by_group.insert(group_id, token_id);
+    }
+
+    pub fn get_spent_shares_by_group_token(&self, group_id: &GroupId) -> Option<&TokenId> {
+        self.spent_shares_by_group.get(group_id)
+    }
+
+    pub fn list_spent_tokens_by_group(&self) -> &BTreeMap<GroupId, TokenId> {
+        &self.spent_shares_by_group
+    }
+
+    pub fn set_raw_shares_by_group_token(&mut self, group_id: GroupId, token_id: TokenId) {
+        assert!(!self.raw_shares_by_group.contains_key(&group_id));
+        self.raw_shares_by_group.insert(group_id, token_id);
+    }
+
+    pub fn get_raw_shares_by_group_token(&self, group_id: &GroupId) -> Option<&TokenId> {
+        self.raw_shares_by_group.get(group_id)
+    }
+
+    pub fn list_raw_tokens_by_group(&self) -> &BTreeMap<GroupId, TokenId> {
+        &self.raw_shares_by_group
+    }
+
     pub fn get_voting_id(&self) -> RemoteVotingId {
         self.voting_id
     }
@@ -100,6 +152,22 @@ impl Choice {
         &self.program
     }
 
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn set_amendment_of(&mut self, original_choice_id: ChoiceId) {
+        self.amendment_of = Some(original_choice_id);
+    }
+
+    pub fn get_amendment_of(&self) -> Option<ChoiceId> {
+        self.amendment_of
+    }
+
     fn process_name(name: String) -> Result<String, ValidationError> {
         validate_and_trim_str(
             name,
@@ -133,3 +201,30 @@ impl Model<ChoiceId> for Choice {
         self.id.is_none()
     }
 }
+
+// the shape of a choice, as it is stored by unions from before quadratic votes and amendments
+#[derive(CandidType, Deserialize)]
+pub struct LegacyChoice {
+    pub id: Option<ChoiceId>,
+    pub voting_id: RemoteVotingId,
+    pub name: String,
+    pub description: String,
+    pub program: Program,
+    pub voting_power_by_group: BTreeMap<GroupId, TokenId>,
+}
+
+impl From<LegacyChoice> for Choice {
+    fn from(it: LegacyChoice) -> Self {
+        Self {
+            id: it.id,
+            voting_id: it.voting_id,
+            name: it.name,
+            description: it.description,
+            program: it.program,
+            voting_power_by_group: it.voting_power_by_group,
+            spent_shares_by_group: BTreeMap::new(),
+            raw_shares_by_group: BTreeMap::new(),
+            amendment_of: None,
+        }
+    }
+}
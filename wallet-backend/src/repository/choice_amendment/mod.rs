@@ -0,0 +1,89 @@
+use crate::repository::choice_amendment::model::ChoiceAmendment;
+use crate::repository::choice_amendment::types::{ChoiceAmendmentFilter, ChoiceAmendmentId};
+use candid::{CandidType, Deserialize};
+use shared::mvc::{IdGenerator, Model, Repository};
+use shared::pageable::{Page, PageRequest, Pageable};
+use shared::types::wallet::{ChoiceId, VotingId};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub mod model;
+pub mod types;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct ChoiceAmendmentRepository {
+    amendments: HashMap<ChoiceAmendmentId, ChoiceAmendment>,
+    id_gen: IdGenerator,
+
+    amendments_by_voting_index: BTreeMap<VotingId, BTreeSet<ChoiceAmendmentId>>,
+    amendments_by_choice_index: BTreeMap<ChoiceId, BTreeSet<ChoiceAmendmentId>>,
+}
+
+impl Repository<ChoiceAmendment, ChoiceAmendmentId, ChoiceAmendmentFilter, ()>
+    for ChoiceAmendmentRepository
+{
+    fn save(&mut self, mut it: ChoiceAmendment) -> ChoiceAmendmentId {
+        if it.is_transient() {
+            it._init_id(self.id_gen.generate());
+        }
+
+        let id = it.get_id().unwrap();
+
+        self.amendments_by_voting_index
+            .entry(it.get_voting_id())
+            .or_default()
+            .insert(id);
+
+        self.amendments_by_choice_index
+            .entry(it.get_choice_id())
+            .or_default()
+            .insert(id);
+
+        self.amendments.insert(id, it);
+
+        id
+    }
+
+    fn delete(&mut self, id: &ChoiceAmendmentId) -> Option<ChoiceAmendment> {
+        let it = self.amendments.remove(id)?;
+
+        if let Some(index) = self.amendments_by_voting_index.get_mut(&it.get_voting_id()) {
+            index.remove(id);
+        }
+
+        if let Some(index) = self.amendments_by_choice_index.get_mut(&it.get_choice_id()) {
+            index.remove(id);
+        }
+
+        Some(it)
+    }
+
+    fn get(&self, id: &ChoiceAmendmentId) -> Option<ChoiceAmendment> {
+        self.amendments.get(id).cloned()
+    }
+
+    fn list(&self, page_req: &PageRequest<ChoiceAmendmentFilter, ()>) -> Page<ChoiceAmendment> {
+        let index = if let Some(choice_id) = &page_req.filter.choice_id {
+            self.amendments_by_choice_index.get(choice_id).cloned()
+        } else if let Some(voting_id) = &page_req.filter.voting_id {
+            self.amendments_by_voting_index.get(voting_id).cloned()
+        } else {
+            // if no filter set - return all
+            Some(self.amendments.keys().cloned().collect())
+        }
+        .unwrap_or_default();
+
+        let (has_next, iter) = index.iter().get_page(page_req);
+        let data = iter.map(|id| self.get(id).unwrap()).collect();
+
+        Page::new(data, has_next)
+    }
+}
+
+impl ChoiceAmendmentRepository {
+    pub fn list_ids_of_choice(&self, choice_id: &ChoiceId) -> Vec<ChoiceAmendmentId> {
+        self.amendments_by_choice_index
+            .get(choice_id)
+            .map(|it| it.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::repository::choice::types::{
+    VOTING_CHOICE_DESCRIPTION_MAX_LEN, VOTING_CHOICE_DESCRIPTION_MIN_LEN,
+    VOTING_CHOICE_NAME_MAX_LEN, VOTING_CHOICE_NAME_MIN_LEN,
+};
+use crate::repository::choice_amendment::types::{ChoiceAmendmentId, ChoiceAmendmentStatus};
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::Model;
+use shared::remote_call::Program;
+use shared::types::wallet::{ChoiceId, VotingId};
+use shared::validation::{validate_and_trim_str, ValidationError};
+
+// an alternative version of a choice - if accepted, it becomes a new choice of the same voting
+#[derive(Clone, CandidType, Deserialize)]
+pub struct ChoiceAmendment {
+    id: Option<ChoiceAmendmentId>,
+    voting_id: VotingId,
+    choice_id: ChoiceId,
+    author: Principal,
+    status: ChoiceAmendmentStatus,
+
+    name: String,
+    // the original's description or program is used, if not set
+    description: Option<String>,
+    program: Option<Program>,
+
+    created_at: u64,
+    updated_at: u64,
+}
+
+impl ChoiceAmendment {
+    pub fn new(
+        voting_id: VotingId,
+        choice_id: ChoiceId,
+        author: Principal,
+        name: String,
+        description: Option<String>,
+        program: Option<Program>,
+        timestamp: u64,
+    ) -> Result<Self, ValidationError> {
+        if description.is_none() && program.is_none() {
+            return Err(ValidationError(
+                "Amendment should change either the description or the program".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            id: None,
+            voting_id,
+            choice_id,
+            author,
+            status: ChoiceAmendmentStatus::Proposed,
+            name: Self::process_name(name)?,
+            description: description.map(Self::process_description).transpose()?,
+            program,
+            created_at: timestamp,
+            updated_at: timestamp,
+        })
+    }
+
+    pub fn accept(&mut self, new_choice_id: ChoiceId, timestamp: u64) {
+        self.status = ChoiceAmendmentStatus::Accepted(new_choice_id);
+        self.updated_at = timestamp;
+    }
+
+    pub fn reject(&mut self, timestamp: u64) {
+        self.status = ChoiceAmendmentStatus::Rejected;
+        self.updated_at = timestamp;
+    }
+
+    pub fn get_voting_id(&self) -> VotingId {
+        self.voting_id
+    }
+
+    pub fn get_choice_id(&self) -> ChoiceId {
+        self.choice_id
+    }
+
+    pub fn get_status(&self) -> &ChoiceAmendmentStatus {
+        &self.status
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn get_program(&self) -> &Option<Program> {
+        &self.program
+    }
+
+    fn process_name(name: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            name,
+            VOTING_CHOICE_NAME_MIN_LEN,
+            VOTING_CHOICE_NAME_MAX_LEN,
+            "Amendment name",
+        )
+    }
+
+    fn process_description(description: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            description,
+            VOTING_CHOICE_DESCRIPTION_MIN_LEN,
+            VOTING_CHOICE_DESCRIPTION_MAX_LEN,
+            "Amendment description",
+        )
+    }
+}
+
+impl Model<ChoiceAmendmentId> for ChoiceAmendment {
+    fn get_id(&self) -> Option<ChoiceAmendmentId> {
+        self.id
+    }
+
+    fn _init_id(&mut self, id: ChoiceAmendmentId) {
+        assert!(self.is_transient());
+        self.id = Some(id);
+    }
+
+    fn is_transient(&self) -> bool {
+        self.id.is_none()
+    }
+}
@@ -0,0 +1,19 @@
+use candid::{CandidType, Deserialize};
+use shared::mvc::Id;
+use shared::types::wallet::{ChoiceId, VotingId};
+
+pub type ChoiceAmendmentId = Id;
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug)]
+pub enum ChoiceAmendmentStatus {
+    Proposed,
+    // with the id of the choice created from the amendment
+    Accepted(ChoiceId),
+    Rejected,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ChoiceAmendmentFilter {
+    pub voting_id: Option<VotingId>,
+    pub choice_id: Option<ChoiceId>,
+}
@@ -0,0 +1,151 @@
+use crate::repository::comment::model::Comment;
+use crate::repository::comment::types::{CommentFilter, CommentId, CommentTarget};
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::{IdGenerator, Model, Repository};
+use shared::pageable::{Page, PageRequest, Pageable};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub mod model;
+pub mod types;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct CommentRepository {
+    comments: HashMap<CommentId, Comment>,
+    id_gen: IdGenerator,
+
+    // none of these fields change, so the indexes are only updated on creation and deletion
+    comments_by_target_index: BTreeMap<CommentTarget, BTreeSet<CommentId>>,
+    replies_index: BTreeMap<CommentId, BTreeSet<CommentId>>,
+    comments_by_author_index: BTreeMap<Principal, BTreeSet<CommentId>>,
+}
+
+impl Repository<Comment, CommentId, CommentFilter, ()> for CommentRepository {
+    fn save(&mut self, mut it: Comment) -> CommentId {
+        if it.is_transient() {
+            it._init_id(self.id_gen.generate());
+            self.add_to_index(&it);
+        }
+
+        let id = it.get_id().unwrap();
+        self.comments.insert(id, it);
+
+        id
+    }
+
+    fn delete(&mut self, id: &CommentId) -> Option<Comment> {
+        let it = self.comments.remove(id)?;
+        self.remove_from_index(&it);
+
+        Some(it)
+    }
+
+    fn get(&self, id: &CommentId) -> Option<Comment> {
+        self.comments.get(id).cloned()
+    }
+
+    // comments are listed from the oldest to the newest
+    fn list(&self, page_req: &PageRequest<CommentFilter, ()>) -> Page<Comment> {
+        let index = self
+            .filter_ids(&page_req.filter)
+            .unwrap_or_else(|| self.comments.keys().cloned().collect());
+
+        let (has_next, iter) = index.iter().get_page(page_req);
+        let data = iter.map(|id| self.get(id).unwrap()).collect();
+
+        Page::new(data, has_next)
+    }
+}
+
+impl CommentRepository {
+    pub fn list_ids_of_target(&self, target: &CommentTarget) -> Vec<CommentId> {
+        self.comments_by_target_index
+            .get(target)
+            .map(|it| it.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // returns None, if no filter is set
+    fn filter_ids(&self, filter: &CommentFilter) -> Option<BTreeSet<CommentId>> {
+        let mut index_opt: Option<BTreeSet<CommentId>> = None;
+
+        if let Some(target) = &filter.target {
+            let index = self
+                .comments_by_target_index
+                .get(target)
+                .cloned()
+                .unwrap_or_default();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if let Some(reply_to) = &filter.reply_to {
+            let index = self
+                .replies_index
+                .get(reply_to)
+                .cloned()
+                .unwrap_or_default();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if let Some(author) = &filter.author {
+            let index = self
+                .comments_by_author_index
+                .get(author)
+                .cloned()
+                .unwrap_or_default();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        index_opt
+    }
+
+    fn intersect(
+        index_opt: Option<BTreeSet<CommentId>>,
+        index: BTreeSet<CommentId>,
+    ) -> BTreeSet<CommentId> {
+        match index_opt {
+            Some(prev) => prev.intersection(&index).cloned().collect(),
+            None => index,
+        }
+    }
+
+    fn add_to_index(&mut self, it: &Comment) {
+        let id = it.get_id().unwrap();
+
+        self.comments_by_target_index
+            .entry(*it.get_target())
+            .or_default()
+            .insert(id);
+
+        if let Some(reply_to) = it.get_reply_to() {
+            self.replies_index.entry(reply_to).or_default().insert(id);
+        }
+
+        self.comments_by_author_index
+            .entry(it.get_author())
+            .or_default()
+            .insert(id);
+    }
+
+    fn remove_from_index(&mut self, it: &Comment) {
+        let id = it.get_id().unwrap();
+
+        if let Some(index) = self.comments_by_target_index.get_mut(it.get_target()) {
+            index.remove(&id);
+        }
+
+        if let Some(reply_to) = it.get_reply_to() {
+            if let Some(index) = self.replies_index.get_mut(&reply_to) {
+                index.remove(&id);
+            }
+        }
+
+        self.replies_index.remove(&id);
+
+        if let Some(index) = self.comments_by_author_index.get_mut(&it.get_author()) {
+            index.remove(&id);
+        }
+    }
+}
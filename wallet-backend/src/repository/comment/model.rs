@@ -0,0 +1,105 @@
+use crate::repository::comment::types::{
+    CommentEdit, CommentId, CommentTarget, COMMENT_TEXT_MAX_LEN, COMMENT_TEXT_MIN_LEN,
+};
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::Model;
+use shared::validation::{validate_and_trim_str, ValidationError};
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Comment {
+    id: Option<CommentId>,
+    target: CommentTarget,
+    reply_to: Option<CommentId>,
+    author: Principal,
+    text: String,
+
+    // previous versions of the text, from the oldest to the newest
+    history: Vec<CommentEdit>,
+    deleted_by: Option<Principal>,
+
+    created_at: u64,
+    updated_at: u64,
+}
+
+impl Comment {
+    pub fn new(
+        target: CommentTarget,
+        reply_to: Option<CommentId>,
+        author: Principal,
+        text: String,
+        timestamp: u64,
+    ) -> Result<Self, ValidationError> {
+        Ok(Self {
+            id: None,
+            target,
+            reply_to,
+            author,
+            text: Self::process_text(text)?,
+            history: Vec::new(),
+            deleted_by: None,
+            created_at: timestamp,
+            updated_at: timestamp,
+        })
+    }
+
+    pub fn update(&mut self, new_text: String, timestamp: u64) -> Result<(), ValidationError> {
+        let text = Self::process_text(new_text)?;
+        let prev_text = std::mem::replace(&mut self.text, text);
+
+        self.history.push(CommentEdit {
+            text: prev_text,
+            edited_at: timestamp,
+        });
+        self.updated_at = timestamp;
+
+        Ok(())
+    }
+
+    // the comment stays in place to keep its replies in the thread, but the text is gone
+    pub fn delete(&mut self, deleted_by: Principal, timestamp: u64) {
+        self.text = String::new();
+        self.history.clear();
+        self.deleted_by = Some(deleted_by);
+        self.updated_at = timestamp;
+    }
+
+    pub fn get_target(&self) -> &CommentTarget {
+        &self.target
+    }
+
+    pub fn get_reply_to(&self) -> Option<CommentId> {
+        self.reply_to
+    }
+
+    pub fn get_author(&self) -> Principal {
+        self.author
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_by.is_some()
+    }
+
+    fn process_text(text: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            text,
+            COMMENT_TEXT_MIN_LEN,
+            COMMENT_TEXT_MAX_LEN,
+            "Comment text",
+        )
+    }
+}
+
+impl Model<CommentId> for Comment {
+    fn get_id(&self) -> Option<CommentId> {
+        self.id
+    }
+
+    fn _init_id(&mut self, id: CommentId) {
+        assert!(self.is_transient());
+        self.id = Some(id);
+    }
+
+    fn is_transient(&self) -> bool {
+        self.id.is_none()
+    }
+}
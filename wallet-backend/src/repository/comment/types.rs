@@ -0,0 +1,27 @@
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::Id;
+use shared::types::wallet::{ChoiceId, VotingId};
+
+pub const COMMENT_TEXT_MIN_LEN: usize = 1;
+pub const COMMENT_TEXT_MAX_LEN: usize = 5000;
+
+pub type CommentId = Id;
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommentTarget {
+    Voting(VotingId),
+    Choice(ChoiceId),
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct CommentEdit {
+    pub text: String,
+    pub edited_at: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CommentFilter {
+    pub target: Option<CommentTarget>,
+    pub reply_to: Option<CommentId>,
+    pub author: Option<Principal>,
+}
@@ -6,10 +6,16 @@ use crate::repository::batch::types::BatchId;
 use crate::repository::batch::BatchRepository;
 use crate::repository::choice::model::Choice;
 use crate::repository::choice::types::ChoiceFilter;
-use crate::repository::choice::ChoiceRepository;
+use crate::repository::choice::{ChoiceRepository, LegacyChoiceRepository};
+use crate::repository::choice_amendment::model::ChoiceAmendment;
+use crate::repository::choice_amendment::types::{ChoiceAmendmentFilter, ChoiceAmendmentId};
+use crate::repository::choice_amendment::ChoiceAmendmentRepository;
 use crate::repository::chunk::model::Chunk;
 use crate::repository::chunk::types::{ChunkFilter, ChunkId};
 use crate::repository::chunk::ChunkRepository;
+use crate::repository::comment::model::Comment;
+use crate::repository::comment::types::{CommentFilter, CommentId};
+use crate::repository::comment::CommentRepository;
 use crate::repository::group::model::Group;
 use crate::repository::group::GroupRepository;
 use crate::repository::nested_voting::model::NestedVoting;
@@ -23,17 +29,26 @@ use crate::repository::nested_voting_config::NestedVotingConfigRepository;
 use crate::repository::permission::model::Permission;
 use crate::repository::permission::types::{PermissionFilter, PermissionId};
 use crate::repository::permission::PermissionRepository;
+use crate::repository::pipeline::model::Pipeline;
+use crate::repository::pipeline::types::{PipelineFilter, PipelineId};
+use crate::repository::pipeline::PipelineRepository;
 use crate::repository::profile::model::Profile;
 use crate::repository::profile::ProfileRepository;
 use crate::repository::token::model::Token;
 use crate::repository::token::types::{TokenFilter, TokenId};
-use crate::repository::token::TokenRepository;
+use crate::repository::token::{LegacyTokenRepository, TokenRepository};
+use crate::repository::vote_delegation::model::VoteDelegation;
+use crate::repository::vote_delegation::types::{VoteDelegationFilter, VoteDelegationId};
+use crate::repository::vote_delegation::VoteDelegationRepository;
 use crate::repository::voting::model::Voting;
-use crate::repository::voting::types::VotingSort;
-use crate::repository::voting::VotingRepository;
+use crate::repository::voting::types::{VotingFilter, VotingSort};
+use crate::repository::voting::{LegacyVotingRepository, VotingRepository};
 use crate::repository::voting_config::model::VotingConfig;
 use crate::repository::voting_config::types::VotingConfigFilter;
-use crate::repository::voting_config::VotingConfigRepository;
+use crate::repository::voting_config::{LegacyVotingConfigRepository, VotingConfigRepository};
+use crate::repository::voting_template::model::VotingTemplate;
+use crate::repository::voting_template::types::{VotingTemplateFilter, VotingTemplateId};
+use crate::repository::voting_template::VotingTemplateRepository;
 use candid::{CandidType, Deserialize};
 use shared::mvc::HasRepository;
 use shared::types::wallet::{
@@ -43,30 +58,80 @@ use shared::types::wallet::{
 pub mod access_config;
 pub mod batch;
 pub mod choice;
+pub mod choice_amendment;
 pub mod chunk;
+pub mod comment;
 pub mod group;
 pub mod nested_voting;
 pub mod nested_voting_config;
 pub mod permission;
+pub mod pipeline;
 pub mod profile;
 pub mod token;
+pub mod vote_delegation;
 pub mod voting;
 pub mod voting_config;
+pub mod voting_template;
 
 #[derive(Default, CandidType, Deserialize)]
 pub struct Repositories {
     access_config: AccessConfigRepository,
     batch: BatchRepository,
     choice: ChoiceRepository,
+    choice_amendment: ChoiceAmendmentRepository,
     chunk: ChunkRepository,
+    comment: CommentRepository,
     group: GroupRepository,
     nested_voting: NestedVotingRepository,
     nested_voting_config: NestedVotingConfigRepository,
     profile: ProfileRepository,
     permission: PermissionRepository,
+    pipeline: PipelineRepository,
     token: TokenRepository,
+    vote_delegation: VoteDelegationRepository,
     voting_config: VotingConfigRepository,
     voting: VotingRepository,
+    voting_template: VotingTemplateRepository,
+}
+
+// the state of unions from before comments, amendments, delegations, pipelines and templates
+#[derive(Default, CandidType, Deserialize)]
+pub struct LegacyRepositories {
+    pub access_config: AccessConfigRepository,
+    pub batch: BatchRepository,
+    pub choice: LegacyChoiceRepository,
+    pub chunk: ChunkRepository,
+    pub group: GroupRepository,
+    pub nested_voting: NestedVotingRepository,
+    pub nested_voting_config: NestedVotingConfigRepository,
+    pub profile: ProfileRepository,
+    pub permission: PermissionRepository,
+    pub token: LegacyTokenRepository,
+    pub voting_config: LegacyVotingConfigRepository,
+    pub voting: LegacyVotingRepository,
+}
+
+impl From<LegacyRepositories> for Repositories {
+    fn from(it: LegacyRepositories) -> Self {
+        let voting_config = VotingConfigRepository::from(it.voting_config);
+        let voting = VotingRepository::from_legacy(it.voting, &voting_config);
+
+        Self {
+            access_config: it.access_config,
+            batch: it.batch,
+            choice: ChoiceRepository::from(it.choice),
+            chunk: it.chunk,
+            group: it.group,
+            nested_voting: it.nested_voting,
+            nested_voting_config: it.nested_voting_config,
+            profile: it.profile,
+            permission: it.permission,
+            token: TokenRepository::from(it.token),
+            voting_config,
+            voting,
+            ..Default::default()
+        }
+    }
 }
 
 static mut REPOSITORIES: Option<Repositories> = None;
@@ -103,12 +168,32 @@ impl HasRepository<Choice, ChoiceId, ChoiceFilter, (), ChoiceRepository> for Cho
     }
 }
 
+impl
+    HasRepository<
+        ChoiceAmendment,
+        ChoiceAmendmentId,
+        ChoiceAmendmentFilter,
+        (),
+        ChoiceAmendmentRepository,
+    > for ChoiceAmendment
+{
+    fn repo() -> &'static mut ChoiceAmendmentRepository {
+        &mut get_repositories().choice_amendment
+    }
+}
+
 impl HasRepository<Chunk, ChunkId, ChunkFilter, (), ChunkRepository> for Chunk {
     fn repo() -> &'static mut ChunkRepository {
         &mut get_repositories().chunk
     }
 }
 
+impl HasRepository<Comment, CommentId, CommentFilter, (), CommentRepository> for Comment {
+    fn repo() -> &'static mut CommentRepository {
+        &mut get_repositories().comment
+    }
+}
+
 impl HasRepository<Group, GroupId, (), (), GroupRepository> for Group {
     fn repo() -> &'static mut GroupRepository {
         &mut get_repositories().group
@@ -143,6 +228,20 @@ impl HasRepository<Token, TokenId, TokenFilter, (), TokenRepository> for Token {
     }
 }
 
+impl
+    HasRepository<
+        VoteDelegation,
+        VoteDelegationId,
+        VoteDelegationFilter,
+        (),
+        VoteDelegationRepository,
+    > for VoteDelegation
+{
+    fn repo() -> &'static mut VoteDelegationRepository {
+        &mut get_repositories().vote_delegation
+    }
+}
+
 impl HasRepository<VotingConfig, VotingConfigId, VotingConfigFilter, (), VotingConfigRepository>
     for VotingConfig
 {
@@ -151,7 +250,7 @@ impl HasRepository<VotingConfig, VotingConfigId, VotingConfigFilter, (), VotingC
     }
 }
 
-impl HasRepository<Voting, VotingId, (), VotingSort, VotingRepository> for Voting {
+impl HasRepository<Voting, VotingId, VotingFilter, VotingSort, VotingRepository> for Voting {
     fn repo() -> &'static mut VotingRepository {
         &mut get_repositories().voting
     }
@@ -178,3 +277,153 @@ impl HasRepository<NestedVoting, NestedVotingId, NestedVotingFilter, (), NestedV
         &mut get_repositories().nested_voting
     }
 }
+
+impl HasRepository<Pipeline, PipelineId, PipelineFilter, (), PipelineRepository> for Pipeline {
+    fn repo() -> &'static mut PipelineRepository {
+        &mut get_repositories().pipeline
+    }
+}
+
+impl
+    HasRepository<
+        VotingTemplate,
+        VotingTemplateId,
+        VotingTemplateFilter,
+        (),
+        VotingTemplateRepository,
+    > for VotingTemplate
+{
+    fn repo() -> &'static mut VotingTemplateRepository {
+        &mut get_repositories().voting_template
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::choice::model::LegacyChoice;
+    use crate::repository::nested_voting::types::RemoteVotingId;
+    use crate::repository::voting::model::LegacyVoting;
+    use crate::repository::voting::types::{LegacyVotingStatus, VotingStatus};
+    use crate::repository::voting_config::model::LegacyVotingConfig;
+    use crate::repository::voting_config::types::{
+        Fraction, FractionOf, LegacyRoundSettings, TallyMode, Target, ThresholdValue,
+    };
+    use crate::repository::{LegacyRepositories, Repositories};
+    use candid::{Decode, Encode, Principal};
+    use shared::mvc::Repository;
+    use shared::remote_call::Program;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn threshold() -> ThresholdValue {
+        ThresholdValue::FractionOf(FractionOf {
+            fraction: Fraction::from(0.5),
+            target: Target::Group(1),
+        })
+    }
+
+    // the state, as it is saved by unions from before the repositories were extended
+    fn legacy_state() -> Vec<u8> {
+        let mut legacy = LegacyRepositories::default();
+
+        legacy.voting_config.voting_configs.insert(
+            1,
+            LegacyVotingConfig {
+                id: Some(1),
+                name: String::from("Legacy"),
+                description: String::new(),
+                choices_count: None,
+                winners_count: None,
+                round: LegacyRoundSettings {
+                    round_duration: 100,
+                    round_delay: 0,
+                },
+                permissions: BTreeSet::new(),
+                approval: threshold(),
+                rejection: threshold(),
+                quorum: threshold(),
+                win: threshold(),
+                next_round: threshold(),
+            },
+        );
+
+        legacy.voting.votings.insert(
+            1,
+            LegacyVoting {
+                id: Some(1),
+                voting_config_id: 1,
+                status: LegacyVotingStatus::Success,
+                created_at: 0,
+                updated_at: 0,
+                proposer: Principal::anonymous(),
+                task_id: None,
+                name: String::from("Legacy voting"),
+                description: String::new(),
+                winners_need: 1,
+                total_voting_power_by_group: BTreeMap::new(),
+                winners: Vec::new(),
+                losers: Vec::new(),
+                choices: vec![1].into_iter().collect(),
+                rejection_choice: None,
+                approval_choice: None,
+            },
+        );
+        legacy
+            .voting
+            .votings_by_voting_config_index
+            .insert(1, vec![1].into_iter().collect());
+        legacy
+            .voting
+            .votings_sorted_by_created_at_index
+            .push((0, 1));
+        legacy
+            .voting
+            .votings_sorted_by_updated_at_index
+            .push((0, 1));
+
+        legacy.choice.choices.insert(
+            1,
+            LegacyChoice {
+                id: Some(1),
+                voting_id: RemoteVotingId::Common(1),
+                name: String::from("Legacy choice"),
+                description: String::new(),
+                program: Program::Empty,
+                voting_power_by_group: BTreeMap::new(),
+            },
+        );
+
+        Encode!(&Some(legacy)).unwrap()
+    }
+
+    #[test]
+    fn legacy_state_doesnt_fit_current_repositories() {
+        let repos = Decode!(&legacy_state(), Option<Repositories>).unwrap();
+
+        assert!(repos.is_none());
+    }
+
+    #[test]
+    fn legacy_state_is_migrated() {
+        let legacy = Decode!(&legacy_state(), Option<LegacyRepositories>)
+            .unwrap()
+            .unwrap();
+        let repos = Repositories::from(legacy);
+
+        let voting = repos.voting.get(&1).unwrap();
+        assert!(matches!(voting.get_status(), VotingStatus::Executed));
+
+        // the voting gets a snapshot of its voting config, with the new settings off
+        let vc = voting.get_voting_config();
+        assert_eq!(vc.get_round_settings().round_duration, 100);
+        assert!(!vc.get_round_settings().early_finish);
+        assert!(matches!(vc.get_tally_mode(), TallyMode::Common));
+        assert!(vc.get_proposal_bond().is_none());
+
+        assert!(repos.voting_config.get(&1).is_some());
+        assert!(repos.choice.get(&1).unwrap().get_amendment_of().is_none());
+
+        // the migrated state is saved in the current shape on the next upgrade
+        let state = Encode!(&Some(repos)).unwrap();
+        assert!(Decode!(&state, Option<Repositories>).unwrap().is_some());
+    }
+}
@@ -13,6 +13,10 @@ pub type PermissionId = Id;
 pub enum PermissionTarget {
     SelfEmptyProgram,
     Endpoint(RemoteCallEndpoint),
+    // not related to programs - allows posting comments to votings and choices
+    Comment,
+    // allows deleting comments of anyone
+    ModerateComments,
 }
 
 #[derive(CandidType, Deserialize)]
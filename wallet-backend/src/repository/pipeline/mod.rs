@@ -0,0 +1,101 @@
+use crate::repository::pipeline::model::Pipeline;
+use crate::repository::pipeline::types::{PipelineFilter, PipelineId};
+use candid::{CandidType, Deserialize};
+use shared::mvc::{IdGenerator, Model, Repository};
+use shared::pageable::{Page, PageRequest, Pageable};
+use shared::types::wallet::VotingConfigId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub mod model;
+pub mod types;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct PipelineRepository {
+    pipelines: HashMap<PipelineId, Pipeline>,
+    id_gen: IdGenerator,
+
+    pipelines_by_voting_config_index: BTreeMap<VotingConfigId, BTreeSet<PipelineId>>,
+}
+
+impl Repository<Pipeline, PipelineId, PipelineFilter, ()> for PipelineRepository {
+    fn save(&mut self, mut it: Pipeline) -> PipelineId {
+        if it.is_transient() {
+            it._init_id(self.id_gen.generate());
+        } else {
+            let prev_it = self.get(&it.get_id().unwrap()).unwrap();
+            self.remove_from_index(&prev_it);
+        }
+
+        self.add_to_index(&it);
+        let id = it.get_id().unwrap();
+        self.pipelines.insert(id, it);
+
+        id
+    }
+
+    fn delete(&mut self, id: &PipelineId) -> Option<Pipeline> {
+        let it = self.pipelines.remove(id)?;
+        self.remove_from_index(&it);
+
+        Some(it)
+    }
+
+    fn get(&self, id: &PipelineId) -> Option<Pipeline> {
+        self.pipelines.get(id).cloned()
+    }
+
+    fn list(&self, page_req: &PageRequest<PipelineFilter, ()>) -> Page<Pipeline> {
+        if let Some(voting_config_id) = page_req.filter.voting_config {
+            let index = self
+                .pipelines_by_voting_config_index
+                .get(&voting_config_id)
+                .cloned()
+                .unwrap_or_default();
+
+            let (has_next, iter) = index.iter().get_page(page_req);
+            let data = iter.map(|id| self.get(id).unwrap()).collect();
+
+            Page::new(data, has_next)
+        } else {
+            // if no filter set - return all
+            let (has_next, iter) = self.pipelines.iter().get_page(page_req);
+            let data = iter.map(|(_, it)| it.clone()).collect();
+
+            Page::new(data, has_next)
+        }
+    }
+}
+
+impl PipelineRepository {
+    pub fn voting_config_has_related_pipelines(&self, voting_config_id: &VotingConfigId) -> bool {
+        if let Some(index) = self.pipelines_by_voting_config_index.get(voting_config_id) {
+            !index.is_empty()
+        } else {
+            false
+        }
+    }
+
+    fn add_to_index(&mut self, it: &Pipeline) {
+        let id = it.get_id().unwrap();
+
+        for voting_config_id in it.get_stages() {
+            self.pipelines_by_voting_config_index
+                .entry(*voting_config_id)
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    fn remove_from_index(&mut self, it: &Pipeline) {
+        let id = it.get_id().unwrap();
+
+        for voting_config_id in it.get_stages() {
+            if let Some(index) = self
+                .pipelines_by_voting_config_index
+                .get_mut(voting_config_id)
+            {
+                index.remove(&id);
+            }
+        }
+    }
+}
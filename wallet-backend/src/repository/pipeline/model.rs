@@ -0,0 +1,101 @@
+use crate::repository::pipeline::types::{
+    PipelineId, PIPELINE_DESCRIPTION_MAX_LEN, PIPELINE_DESCRIPTION_MIN_LEN, PIPELINE_NAME_MAX_LEN,
+    PIPELINE_NAME_MIN_LEN, PIPELINE_STAGES_MAX_LEN, PIPELINE_STAGES_MIN_LEN,
+};
+use candid::{CandidType, Deserialize};
+use shared::mvc::Model;
+use shared::types::wallet::VotingConfigId;
+use shared::validation::{validate_and_trim_str, ValidationError};
+
+// a chain of voting configs - winners of a voting under each stage are voted on again under the next one
+#[derive(Clone, CandidType, Deserialize)]
+pub struct Pipeline {
+    id: Option<PipelineId>,
+    name: String,
+    description: String,
+    stages: Vec<VotingConfigId>,
+}
+
+impl Pipeline {
+    pub fn new(
+        name: String,
+        description: String,
+        stages: Vec<VotingConfigId>,
+    ) -> Result<Self, ValidationError> {
+        Ok(Self {
+            id: None,
+            name: Self::process_name(name)?,
+            description: Self::process_description(description)?,
+            stages: Self::process_stages(stages)?,
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        name_opt: Option<String>,
+        description_opt: Option<String>,
+        stages_opt: Option<Vec<VotingConfigId>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(name) = name_opt {
+            self.name = Self::process_name(name)?;
+        }
+
+        if let Some(description) = description_opt {
+            self.description = Self::process_description(description)?;
+        }
+
+        if let Some(stages) = stages_opt {
+            self.stages = Self::process_stages(stages)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_stages(&self) -> &Vec<VotingConfigId> {
+        &self.stages
+    }
+
+    fn process_stages(stages: Vec<VotingConfigId>) -> Result<Vec<VotingConfigId>, ValidationError> {
+        if stages.len() < PIPELINE_STAGES_MIN_LEN || stages.len() > PIPELINE_STAGES_MAX_LEN {
+            return Err(ValidationError(format!(
+                "Pipeline should have from {} to {} stages",
+                PIPELINE_STAGES_MIN_LEN, PIPELINE_STAGES_MAX_LEN
+            )));
+        }
+
+        Ok(stages)
+    }
+
+    fn process_name(name: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            name,
+            PIPELINE_NAME_MIN_LEN,
+            PIPELINE_NAME_MAX_LEN,
+            "Pipeline name",
+        )
+    }
+
+    fn process_description(description: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            description,
+            PIPELINE_DESCRIPTION_MIN_LEN,
+            PIPELINE_DESCRIPTION_MAX_LEN,
+            "Pipeline description",
+        )
+    }
+}
+
+impl Model<PipelineId> for Pipeline {
+    fn get_id(&self) -> Option<PipelineId> {
+        self.id
+    }
+
+    fn _init_id(&mut self, id: PipelineId) {
+        assert!(self.is_transient());
+        self.id = Some(id);
+    }
+
+    fn is_transient(&self) -> bool {
+        self.id.is_none()
+    }
+}
@@ -0,0 +1,17 @@
+use candid::{CandidType, Deserialize};
+use shared::mvc::Id;
+use shared::types::wallet::VotingConfigId;
+
+pub const PIPELINE_NAME_MIN_LEN: usize = 1;
+pub const PIPELINE_NAME_MAX_LEN: usize = 200;
+pub const PIPELINE_DESCRIPTION_MIN_LEN: usize = 0;
+pub const PIPELINE_DESCRIPTION_MAX_LEN: usize = 2000;
+pub const PIPELINE_STAGES_MIN_LEN: usize = 2;
+pub const PIPELINE_STAGES_MAX_LEN: usize = 10;
+
+pub type PipelineId = Id;
+
+#[derive(CandidType, Deserialize)]
+pub struct PipelineFilter {
+    pub voting_config: Option<VotingConfigId>,
+}
@@ -1,4 +1,4 @@
-use crate::repository::token::model::Token;
+use crate::repository::token::model::{LegacyToken, Token};
 use crate::repository::token::types::{TokenFilter, TokenId};
 use crate::Principal;
 use candid::{CandidType, Deserialize};
@@ -75,3 +75,25 @@ impl TokenRepository {
             .unwrap_or_default()
     }
 }
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct LegacyTokenRepository {
+    pub tokens: HashMap<TokenId, LegacyToken>,
+    pub id_gen: IdGenerator,
+
+    pub tokens_by_principal_index: BTreeMap<Principal, BTreeSet<TokenId>>,
+}
+
+impl From<LegacyTokenRepository> for TokenRepository {
+    fn from(it: LegacyTokenRepository) -> Self {
+        Self {
+            tokens: it
+                .tokens
+                .into_iter()
+                .map(|(id, token)| (id, Token::from(token)))
+                .collect(),
+            id_gen: it.id_gen,
+            tokens_by_principal_index: it.tokens_by_principal_index,
+        }
+    }
+}
@@ -19,6 +19,9 @@ pub struct Token {
 
     unaccepted_total_supply: Shares,
     unaccepted_balances: HashMap<Principal, Shares>,
+
+    locked_total_supply: Shares,
+    locked_balances: HashMap<Principal, Shares>,
 }
 
 impl Token {
@@ -32,6 +35,8 @@ impl Token {
             balances: HashMap::default(),
             unaccepted_total_supply: Shares::default(),
             unaccepted_balances: HashMap::default(),
+            locked_total_supply: Shares::default(),
+            locked_balances: HashMap::default(),
         }
     }
 
@@ -40,6 +45,8 @@ impl Token {
         self.total_supply = Shares::default();
         self.unaccepted_balances = HashMap::new();
         self.unaccepted_total_supply = Shares::default();
+        self.locked_balances = HashMap::new();
+        self.locked_total_supply = Shares::default();
     }
 
     pub fn mint(&mut self, to: Principal, qty: Shares) {
@@ -83,6 +90,45 @@ impl Token {
         }
     }
 
+    // locked shares are kept in escrow - they are not a part of the total supply until unlocked
+    pub fn lock(&mut self, from: Principal, qty: Shares) -> Result<(), ValidationError> {
+        let balance = self.balance_of(&from);
+        if balance < qty {
+            Err(ValidationError(String::from("Insufficient balance")))
+        } else {
+            self.balances.insert(from, balance - qty.clone());
+            self.total_supply -= qty.clone();
+
+            self.locked_balances
+                .insert(from, self.locked_balance_of(&from) + qty.clone());
+            self.locked_total_supply += qty;
+            Ok(())
+        }
+    }
+
+    pub fn unlock(
+        &mut self,
+        from: Principal,
+        to: Principal,
+        qty: Shares,
+    ) -> Result<(), ValidationError> {
+        self.burn_locked(from, qty.clone())?;
+        self.mint(to, qty);
+
+        Ok(())
+    }
+
+    pub fn burn_locked(&mut self, from: Principal, qty: Shares) -> Result<(), ValidationError> {
+        let balance = self.locked_balance_of(&from);
+        if balance < qty {
+            Err(ValidationError(String::from("Insufficient locked balance")))
+        } else {
+            self.locked_balances.insert(from, balance - qty.clone());
+            self.locked_total_supply -= qty;
+            Ok(())
+        }
+    }
+
     pub fn transfer(
         &mut self,
         from: Principal,
@@ -157,6 +203,10 @@ impl Token {
             .unwrap_or_default()
     }
 
+    pub fn locked_balance_of(&self, of: &Principal) -> Shares {
+        self.locked_balances.get(of).cloned().unwrap_or_default()
+    }
+
     pub fn total_supply(&self) -> Shares {
         self.total_supply.clone()
     }
@@ -165,6 +215,10 @@ impl Token {
         self.unaccepted_total_supply.clone()
     }
 
+    pub fn locked_total_supply(&self) -> Shares {
+        self.locked_total_supply.clone()
+    }
+
     pub fn balances(&self, page_req: &PageRequest<(), ()>) -> Page<(Principal, Shares)> {
         let (has_next, iter) = self.balances.iter().get_page(page_req);
         let data = iter.map(|(id, it)| (*id, it.clone())).collect();
@@ -194,3 +248,36 @@ impl Model<TokenId> for Token {
         self.id.is_none()
     }
 }
+
+// the shape of a token, as it is stored by unions from before shares could be locked
+#[derive(CandidType, Deserialize)]
+pub struct LegacyToken {
+    pub id: Option<TokenId>,
+    pub cog: ChoiceOrGroup,
+
+    pub acceptable: bool,
+    pub transferable: bool,
+
+    pub total_supply: Shares,
+    pub balances: HashMap<Principal, Shares>,
+
+    pub unaccepted_total_supply: Shares,
+    pub unaccepted_balances: HashMap<Principal, Shares>,
+}
+
+impl From<LegacyToken> for Token {
+    fn from(it: LegacyToken) -> Self {
+        Self {
+            id: it.id,
+            cog: it.cog,
+            acceptable: it.acceptable,
+            transferable: it.transferable,
+            total_supply: it.total_supply,
+            balances: it.balances,
+            unaccepted_total_supply: it.unaccepted_total_supply,
+            unaccepted_balances: it.unaccepted_balances,
+            locked_total_supply: Shares::default(),
+            locked_balances: HashMap::default(),
+        }
+    }
+}
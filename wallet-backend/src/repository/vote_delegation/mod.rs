@@ -0,0 +1,153 @@
+use crate::repository::vote_delegation::model::VoteDelegation;
+use crate::repository::vote_delegation::types::{VoteDelegationFilter, VoteDelegationId};
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::{IdGenerator, Model, Repository};
+use shared::pageable::{Page, PageRequest, Pageable};
+use shared::types::wallet::GroupId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub mod model;
+pub mod types;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct VoteDelegationRepository {
+    vote_delegations: HashMap<VoteDelegationId, VoteDelegation>,
+    id_gen: IdGenerator,
+
+    vote_delegations_by_delegator_index: BTreeMap<Principal, BTreeSet<VoteDelegationId>>,
+    vote_delegations_by_delegate_index: BTreeMap<Principal, BTreeSet<VoteDelegationId>>,
+    vote_delegations_by_group_index: BTreeMap<GroupId, BTreeSet<VoteDelegationId>>,
+}
+
+impl Repository<VoteDelegation, VoteDelegationId, VoteDelegationFilter, ()>
+    for VoteDelegationRepository
+{
+    fn save(&mut self, mut it: VoteDelegation) -> VoteDelegationId {
+        if it.is_transient() {
+            it._init_id(self.id_gen.generate());
+        } else {
+            let prev_it = self.get(&it.get_id().unwrap()).unwrap();
+            self.remove_from_indexes(&prev_it);
+        }
+
+        self.add_to_indexes(&it);
+        let id = it.get_id().unwrap();
+        self.vote_delegations.insert(id, it);
+
+        id
+    }
+
+    fn delete(&mut self, id: &VoteDelegationId) -> Option<VoteDelegation> {
+        let it = self.vote_delegations.remove(id)?;
+        self.remove_from_indexes(&it);
+
+        Some(it)
+    }
+
+    fn get(&self, id: &VoteDelegationId) -> Option<VoteDelegation> {
+        self.vote_delegations.get(id).cloned()
+    }
+
+    fn list(&self, page_req: &PageRequest<VoteDelegationFilter, ()>) -> Page<VoteDelegation> {
+        let mut indexes = Vec::new();
+
+        if let Some(delegator) = &page_req.filter.delegator {
+            indexes.push(self.get_vote_delegations_by_delegator(delegator));
+        }
+
+        if let Some(delegate) = &page_req.filter.delegate {
+            indexes.push(self.get_vote_delegations_by_delegate(delegate));
+        }
+
+        if let Some(group_id) = &page_req.filter.group {
+            indexes.push(
+                self.vote_delegations_by_group_index
+                    .get(group_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+        }
+
+        let index = match indexes.pop() {
+            Some(mut index) => {
+                for index1 in indexes {
+                    index = index.intersection(&index1).cloned().collect();
+                }
+
+                index
+            }
+            None => {
+                // if no filter was set - return all
+                let (has_next, iter) = self.vote_delegations.iter().get_page(page_req);
+                let data = iter.map(|(_, it)| it.clone()).collect();
+
+                return Page::new(data, has_next);
+            }
+        };
+
+        let (has_next, iter) = index.iter().get_page(page_req);
+        let data = iter.map(|id| self.get(id).unwrap()).collect();
+
+        Page::new(data, has_next)
+    }
+}
+
+impl VoteDelegationRepository {
+    pub fn get_vote_delegations_by_delegator(
+        &self,
+        delegator: &Principal,
+    ) -> BTreeSet<VoteDelegationId> {
+        self.vote_delegations_by_delegator_index
+            .get(delegator)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn get_vote_delegations_by_delegate(
+        &self,
+        delegate: &Principal,
+    ) -> BTreeSet<VoteDelegationId> {
+        self.vote_delegations_by_delegate_index
+            .get(delegate)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn add_to_indexes(&mut self, delegation: &VoteDelegation) {
+        let id = delegation.get_id().unwrap();
+
+        self.vote_delegations_by_delegator_index
+            .entry(delegation.get_delegator())
+            .or_default()
+            .insert(id);
+
+        self.vote_delegations_by_delegate_index
+            .entry(delegation.get_delegate())
+            .or_default()
+            .insert(id);
+
+        self.vote_delegations_by_group_index
+            .entry(delegation.get_group_id())
+            .or_default()
+            .insert(id);
+    }
+
+    fn remove_from_indexes(&mut self, delegation: &VoteDelegation) {
+        let id = delegation.get_id().unwrap();
+
+        self.vote_delegations_by_delegator_index
+            .get_mut(&delegation.get_delegator())
+            .unwrap()
+            .remove(&id);
+
+        self.vote_delegations_by_delegate_index
+            .get_mut(&delegation.get_delegate())
+            .unwrap()
+            .remove(&id);
+
+        self.vote_delegations_by_group_index
+            .get_mut(&delegation.get_group_id())
+            .unwrap()
+            .remove(&id);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::repository::vote_delegation::types::VoteDelegationId;
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::Model;
+use shared::types::wallet::{GroupId, VotingConfigId};
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct VoteDelegation {
+    id: Option<VoteDelegationId>,
+    delegator: Principal,
+    delegate: Principal,
+    group_id: GroupId,
+
+    // if set, the delegation only works for votings of this voting config
+    voting_config_id: Option<VotingConfigId>,
+
+    created_at: u64,
+}
+
+impl VoteDelegation {
+    pub fn new(
+        delegator: Principal,
+        delegate: Principal,
+        group_id: GroupId,
+        voting_config_id: Option<VotingConfigId>,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            id: None,
+            delegator,
+            delegate,
+            group_id,
+            voting_config_id,
+            created_at: timestamp,
+        }
+    }
+
+    pub fn get_delegator(&self) -> Principal {
+        self.delegator
+    }
+
+    pub fn get_delegate(&self) -> Principal {
+        self.delegate
+    }
+
+    pub fn get_group_id(&self) -> GroupId {
+        self.group_id
+    }
+
+    pub fn get_voting_config_id(&self) -> Option<VotingConfigId> {
+        self.voting_config_id
+    }
+
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+}
+
+impl Model<VoteDelegationId> for VoteDelegation {
+    fn get_id(&self) -> Option<VoteDelegationId> {
+        self.id
+    }
+
+    fn _init_id(&mut self, id: VoteDelegationId) {
+        assert!(self.is_transient());
+        self.id = Some(id);
+    }
+
+    fn is_transient(&self) -> bool {
+        self.id.is_none()
+    }
+}
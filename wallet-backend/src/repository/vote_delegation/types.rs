@@ -0,0 +1,12 @@
+use candid::{CandidType, Deserialize, Principal};
+use shared::mvc::Id;
+use shared::types::wallet::GroupId;
+
+pub type VoteDelegationId = Id;
+
+#[derive(CandidType, Deserialize)]
+pub struct VoteDelegationFilter {
+    pub delegator: Option<Principal>,
+    pub delegate: Option<Principal>,
+    pub group: Option<GroupId>,
+}
@@ -1,6 +1,9 @@
-use crate::repository::voting::model::Voting;
-use crate::repository::voting::types::VotingSort;
-use candid::{CandidType, Deserialize};
+use crate::repository::voting::model::{LegacyVoting, Voting};
+use crate::repository::voting::types::{
+    VotingFilter, VotingSort, VotingStatus, VotingStatusFilter,
+};
+use crate::repository::voting_config::VotingConfigRepository;
+use candid::{CandidType, Deserialize, Principal};
 use shared::mvc::{IdGenerator, Model, Repository};
 use shared::pageable::{Page, PageRequest, Pageable};
 use shared::types::wallet::{VotingConfigId, VotingId};
@@ -15,18 +18,27 @@ pub struct VotingRepository {
     id_gen: IdGenerator,
 
     votings_by_voting_config_index: BTreeMap<VotingConfigId, BTreeSet<VotingId>>,
+    votings_by_proposer_index: BTreeMap<Principal, BTreeSet<VotingId>>,
+    votings_by_status_index: BTreeMap<VotingStatusFilter, BTreeSet<VotingId>>,
+    votings_by_voter_index: BTreeMap<Principal, BTreeSet<VotingId>>,
+    // lowercase words of names and descriptions
+    votings_by_word_index: BTreeMap<String, BTreeSet<VotingId>>,
 
     votings_sorted_by_created_at_index: Vec<(u64, VotingId)>,
     votings_sorted_by_updated_at_index: Vec<(u64, VotingId)>,
 }
 
-impl Repository<Voting, VotingId, (), VotingSort> for VotingRepository {
+impl Repository<Voting, VotingId, VotingFilter, VotingSort> for VotingRepository {
     fn save(&mut self, mut it: Voting) -> VotingId {
         if it.is_transient() {
             it._init_id(self.id_gen.generate());
             self.add_to_index(&it);
+            self.add_to_mutable_indexes(&it);
         } else {
             self.remove_from_updated_at_index(&it);
+
+            let prev_it = self.votings.remove(&it.get_id().unwrap()).unwrap();
+            self.update_mutable_indexes(&prev_it, &it);
         }
 
         self.add_to_updated_at_index(&it);
@@ -41,6 +53,8 @@ impl Repository<Voting, VotingId, (), VotingSort> for VotingRepository {
         let it = self.votings.remove(id)?;
         self.remove_from_index(&it);
         self.remove_from_updated_at_index(&it);
+        self.remove_from_mutable_indexes(&it);
+        self.remove_from_voter_index(id);
 
         Some(it)
     }
@@ -49,58 +63,53 @@ impl Repository<Voting, VotingId, (), VotingSort> for VotingRepository {
         self.votings.get(id).cloned()
     }
 
-    fn list(&self, page_req: &PageRequest<(), VotingSort>) -> Page<Voting> {
+    fn list(&self, page_req: &PageRequest<VotingFilter, VotingSort>) -> Page<Voting> {
+        let index_opt = self.filter_ids(&page_req.filter);
+
+        // votings someone has voted at are only skipped while paging, instead of being
+        // subtracted from the whole set of votings upfront
+        let voted_opt = page_req
+            .filter
+            .not_voted_by
+            .as_ref()
+            .and_then(|voter| self.votings_by_voter_index.get(voter));
+        let is_not_voted = |id: &VotingId| match voted_opt {
+            Some(voted) => !voted.contains(id),
+            None => true,
+        };
+
+        let is_included = |id: &VotingId| match &index_opt {
+            Some(index) => index.contains(id) && is_not_voted(id),
+            None => is_not_voted(id),
+        };
+
         match page_req.sort {
             VotingSort::None => {
-                let (has_next, iter) = self.votings.iter().get_page(page_req);
-                let data = iter.map(|(_, it)| it.clone()).collect();
+                if let Some(index) = &index_opt {
+                    return self.get_page_of(index.iter().cloned().filter(is_not_voted), page_req);
+                }
 
-                Page::new(data, has_next)
-            }
-            VotingSort::CreatedAt(descending) => {
-                let (has_next, data) = if !descending {
-                    let (has_next, iter) = self
-                        .votings_sorted_by_created_at_index
-                        .iter()
-                        .rev()
-                        .get_page(page_req);
-                    let data = iter.map(|(_, id)| self.get(id).unwrap()).collect();
-
-                    (has_next, data)
-                } else {
-                    let (has_next, iter) = self
-                        .votings_sorted_by_created_at_index
-                        .iter()
-                        .get_page(page_req);
-                    let data = iter.map(|(_, id)| self.get(id).unwrap()).collect();
-
-                    (has_next, data)
-                };
-
-                Page::new(data, has_next)
-            }
-            VotingSort::UpdatedAt(descending) => {
-                let (has_next, data) = if !descending {
-                    let (has_next, iter) = self
-                        .votings_sorted_by_updated_at_index
-                        .iter()
-                        .rev()
-                        .get_page(page_req);
-                    let data = iter.map(|(_, id)| self.get(id).unwrap()).collect();
-
-                    (has_next, data)
-                } else {
-                    let (has_next, iter) = self
-                        .votings_sorted_by_updated_at_index
-                        .iter()
-                        .get_page(page_req);
-                    let data = iter.map(|(_, id)| self.get(id).unwrap()).collect();
-
-                    (has_next, data)
-                };
-
-                Page::new(data, has_next)
+                if voted_opt.is_none() {
+                    let (has_next, iter) = self.votings.iter().get_page(page_req);
+                    let data = iter.map(|(_, it)| it.clone()).collect();
+
+                    return Page::new(data, has_next);
+                }
+
+                self.get_page_of(self.votings.keys().cloned().filter(is_not_voted), page_req)
             }
+            VotingSort::CreatedAt(descending) => self.list_sorted(
+                &self.votings_sorted_by_created_at_index,
+                descending,
+                is_included,
+                page_req,
+            ),
+            VotingSort::UpdatedAt(descending) => self.list_sorted(
+                &self.votings_sorted_by_updated_at_index,
+                descending,
+                is_included,
+                page_req,
+            ),
         }
     }
 }
@@ -114,12 +123,271 @@ impl VotingRepository {
         }
     }
 
+    // votings of this voting config, which are still in progress
+    pub fn count_open_votings_of(
+        &self,
+        proposer: &Principal,
+        voting_config_id: &VotingConfigId,
+    ) -> u32 {
+        match self.votings_by_proposer_index.get(proposer) {
+            Some(ids) => ids
+                .iter()
+                .map(|id| self.votings.get(id).unwrap())
+                .filter(|it| it.get_voting_config_id() == voting_config_id)
+                .filter(|it| {
+                    matches!(
+                        it.get_status(),
+                        VotingStatus::PreRound(_)
+                            | VotingStatus::Round(_)
+                            | VotingStatus::Reveal(_)
+                    )
+                })
+                .count() as u32,
+            None => 0,
+        }
+    }
+
+    // everyone who voted by themselves (or committed a vote) at any round of the voting
+    pub fn add_voter(&mut self, id: &VotingId, voter: Principal) {
+        self.votings_by_voter_index
+            .entry(voter)
+            .or_default()
+            .insert(*id);
+    }
+
+    // returns None, if no filter is set (not_voted_by is applied while paging)
+    fn filter_ids(&self, filter: &VotingFilter) -> Option<BTreeSet<VotingId>> {
+        let mut index_opt: Option<BTreeSet<VotingId>> = None;
+
+        if let Some(status) = &filter.status {
+            let index = self
+                .votings_by_status_index
+                .iter()
+                .filter(|(it, _)| status.matches(it))
+                .flat_map(|(_, ids)| ids.iter().cloned())
+                .collect();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if let Some(voting_config_id) = &filter.voting_config {
+            let index = self
+                .votings_by_voting_config_index
+                .get(voting_config_id)
+                .cloned()
+                .unwrap_or_default();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if let Some(proposer) = &filter.proposer {
+            let index = self
+                .votings_by_proposer_index
+                .get(proposer)
+                .cloned()
+                .unwrap_or_default();
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if filter.created_from.is_some() || filter.created_to.is_some() {
+            let from = filter.created_from.unwrap_or(u64::MIN);
+            let to = filter.created_to.unwrap_or(u64::MAX);
+
+            let start = self
+                .votings_sorted_by_created_at_index
+                .partition_point(|(created_at, _)| *created_at < from);
+            let end = self
+                .votings_sorted_by_created_at_index
+                .partition_point(|(created_at, _)| *created_at <= to);
+
+            let index = if start < end {
+                self.votings_sorted_by_created_at_index[start..end]
+                    .iter()
+                    .map(|(_, id)| *id)
+                    .collect()
+            } else {
+                BTreeSet::new()
+            };
+
+            index_opt = Some(Self::intersect(index_opt, index));
+        }
+
+        if let Some(search) = &filter.search {
+            // each of the words should be a prefix of some word of the name or the description
+            for word in Self::split_words(search) {
+                let index = self
+                    .votings_by_word_index
+                    .range(word.clone()..)
+                    .take_while(|(it, _)| it.starts_with(&word))
+                    .flat_map(|(_, ids)| ids.iter().cloned())
+                    .collect();
+
+                index_opt = Some(Self::intersect(index_opt, index));
+            }
+        }
+
+        index_opt
+    }
+
+    fn list_sorted(
+        &self,
+        sorted_index: &[(u64, VotingId)],
+        descending: bool,
+        is_included: impl Fn(&VotingId) -> bool,
+        page_req: &PageRequest<VotingFilter, VotingSort>,
+    ) -> Page<Voting> {
+        if !descending {
+            let ids = sorted_index.iter().rev().map(|(_, id)| *id);
+
+            self.get_page_of(ids.filter(is_included), page_req)
+        } else {
+            let ids = sorted_index.iter().map(|(_, id)| *id);
+
+            self.get_page_of(ids.filter(is_included), page_req)
+        }
+    }
+
+    // only walks the ids up to the end of the requested page
+    fn get_page_of(
+        &self,
+        ids: impl Iterator<Item = VotingId>,
+        page_req: &PageRequest<VotingFilter, VotingSort>,
+    ) -> Page<Voting> {
+        let mut iter = ids.skip((page_req.page_size * page_req.page_index) as usize);
+
+        let data = iter
+            .by_ref()
+            .take(page_req.page_size as usize)
+            .map(|id| self.get(&id).unwrap())
+            .collect();
+        let has_next = iter.next().is_some();
+
+        Page::new(data, has_next)
+    }
+
+    fn intersect(
+        index_opt: Option<BTreeSet<VotingId>>,
+        index: BTreeSet<VotingId>,
+    ) -> BTreeSet<VotingId> {
+        match index_opt {
+            Some(prev_index) => prev_index.intersection(&index).cloned().collect(),
+            None => index,
+        }
+    }
+
+    fn split_words(text: &str) -> BTreeSet<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|it| !it.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    fn list_words(voting: &Voting) -> BTreeSet<String> {
+        let mut words = Self::split_words(voting.get_name());
+        words.extend(Self::split_words(voting.get_description()));
+
+        words
+    }
+
+    // status, name and description could change with each save
+    fn add_to_mutable_indexes(&mut self, voting: &Voting) {
+        let id = voting.get_id().unwrap();
+
+        self.votings_by_status_index
+            .entry(VotingStatusFilter::from(voting.get_status()))
+            .or_default()
+            .insert(id);
+
+        for word in Self::list_words(voting) {
+            self.votings_by_word_index
+                .entry(word)
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    // only the entries which differ between the two versions are touched
+    fn update_mutable_indexes(&mut self, prev_voting: &Voting, voting: &Voting) {
+        let id = voting.get_id().unwrap();
+
+        let prev_status = VotingStatusFilter::from(prev_voting.get_status());
+        let status = VotingStatusFilter::from(voting.get_status());
+
+        if prev_status != status {
+            Self::remove_from(&mut self.votings_by_status_index, &prev_status, &id);
+            self.votings_by_status_index
+                .entry(status)
+                .or_default()
+                .insert(id);
+        }
+
+        if prev_voting.get_name() == voting.get_name()
+            && prev_voting.get_description() == voting.get_description()
+        {
+            return;
+        }
+
+        let prev_words = Self::list_words(prev_voting);
+        let words = Self::list_words(voting);
+
+        for word in prev_words.difference(&words) {
+            Self::remove_from(&mut self.votings_by_word_index, word, &id);
+        }
+
+        for word in words.difference(&prev_words) {
+            self.votings_by_word_index
+                .entry(word.clone())
+                .or_default()
+                .insert(id);
+        }
+    }
+
+    fn remove_from_mutable_indexes(&mut self, voting: &Voting) {
+        let id = voting.get_id().unwrap();
+
+        Self::remove_from(
+            &mut self.votings_by_status_index,
+            &VotingStatusFilter::from(voting.get_status()),
+            &id,
+        );
+
+        for word in Self::list_words(voting) {
+            Self::remove_from(&mut self.votings_by_word_index, &word, &id);
+        }
+    }
+
+    // voters of previous rounds are no longer known to the voting itself
+    fn remove_from_voter_index(&mut self, id: &VotingId) {
+        self.votings_by_voter_index.retain(|_, ids| {
+            ids.remove(id);
+
+            !ids.is_empty()
+        });
+    }
+
+    fn remove_from<K: Ord>(index: &mut BTreeMap<K, BTreeSet<VotingId>>, key: &K, id: &VotingId) {
+        if let Some(ids) = index.get_mut(key) {
+            ids.remove(id);
+
+            if ids.is_empty() {
+                index.remove(key);
+            }
+        }
+    }
+
     fn add_to_index(&mut self, voting: &Voting) {
         self.votings_by_voting_config_index
             .entry(*voting.get_voting_config_id())
             .or_default()
             .insert(voting.get_id().unwrap());
 
+        self.votings_by_proposer_index
+            .entry(voting.get_proposer())
+            .or_default()
+            .insert(voting.get_id().unwrap());
+
         let idx = match self
             .votings_sorted_by_created_at_index
             .binary_search_by(|(created_at, _)| created_at.cmp(&voting.get_created_at()))
@@ -138,6 +406,12 @@ impl VotingRepository {
             .unwrap()
             .remove(&voting.get_id().unwrap());
 
+        Self::remove_from(
+            &mut self.votings_by_proposer_index,
+            &voting.get_proposer(),
+            &voting.get_id().unwrap(),
+        );
+
         let (idx, _) = self
             .votings_sorted_by_created_at_index
             .iter()
@@ -172,3 +446,47 @@ impl VotingRepository {
         self.votings_sorted_by_updated_at_index.remove(idx);
     }
 }
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct LegacyVotingRepository {
+    pub votings: HashMap<VotingId, LegacyVoting>,
+    pub id_gen: IdGenerator,
+
+    pub votings_by_voting_config_index: BTreeMap<VotingConfigId, BTreeSet<VotingId>>,
+
+    pub votings_sorted_by_created_at_index: Vec<(u64, VotingId)>,
+    pub votings_sorted_by_updated_at_index: Vec<(u64, VotingId)>,
+}
+
+impl VotingRepository {
+    // voters of legacy votings are only known to choice tokens, so they are not indexed
+    pub fn from_legacy(
+        it: LegacyVotingRepository,
+        voting_configs: &VotingConfigRepository,
+    ) -> Self {
+        let mut repo = Self {
+            id_gen: it.id_gen,
+            votings_by_voting_config_index: it.votings_by_voting_config_index,
+            votings_sorted_by_created_at_index: it.votings_sorted_by_created_at_index,
+            votings_sorted_by_updated_at_index: it.votings_sorted_by_updated_at_index,
+            ..Default::default()
+        };
+
+        for (id, legacy_voting) in it.votings {
+            // voting configs with related votings can't be deleted
+            let voting_config = voting_configs
+                .get(&legacy_voting.voting_config_id)
+                .expect("Voting config of a legacy voting not found");
+            let voting = Voting::from_legacy(legacy_voting, voting_config);
+
+            repo.votings_by_proposer_index
+                .entry(voting.get_proposer())
+                .or_default()
+                .insert(id);
+            repo.add_to_mutable_indexes(&voting);
+            repo.votings.insert(id, voting);
+        }
+
+        repo
+    }
+}
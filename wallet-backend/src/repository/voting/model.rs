@@ -1,10 +1,14 @@
 use crate::repository::voting::types::{
-    RoundResult, VotingStatus, VOTING_DESCRIPTION_MAX_LEN, VOTING_DESCRIPTION_MIN_LEN,
-    VOTING_NAME_MAX_LEN, VOTING_NAME_MIN_LEN,
+    DelegatedVote, LegacyVotingStatus, LockedBond, LockedBondStatus, PipelineStage, RankedBallot,
+    RoundExtension, RoundId, RoundResult, VoteCommitment, VotingStatus, VOTING_DESCRIPTION_MAX_LEN,
+    VOTING_DESCRIPTION_MIN_LEN, VOTING_NAME_MAX_LEN, VOTING_NAME_MIN_LEN,
 };
+use crate::repository::voting_config::model::VotingConfig;
 use candid::{CandidType, Deserialize, Principal};
 use ic_cron::types::TaskId;
 use shared::mvc::Model;
+use shared::remote_call::ProgramExecutionResult;
+use shared::types::history_ledger::SharesInfo;
 use shared::types::wallet::{ChoiceId, GroupId, Shares, VotingConfigId, VotingId};
 use shared::validation::{validate_and_trim_str, ValidationError};
 use std::collections::{BTreeMap, BTreeSet};
@@ -13,13 +17,18 @@ use std::collections::{BTreeMap, BTreeSet};
 pub struct Voting {
     id: Option<VotingId>,
     voting_config_id: VotingConfigId,
+    voting_config: VotingConfig,
 
     status: VotingStatus,
     created_at: u64,
     updated_at: u64,
     proposer: Principal,
+    locked_bond: Option<LockedBond>,
+    pipeline_stage: Option<PipelineStage>,
 
     task_id: Option<TaskId>,
+    round_ends_at: Option<u64>,
+    round_extensions: Vec<RoundExtension>,
 
     name: String,
     description: String,
@@ -32,13 +41,28 @@ pub struct Voting {
 
     choices: BTreeSet<ChoiceId>,
 
+    ranked_ballots: BTreeMap<GroupId, BTreeMap<Principal, RankedBallot>>,
+
+    direct_votes: BTreeMap<GroupId, BTreeMap<Principal, Shares>>,
+    delegated_votes: BTreeMap<GroupId, BTreeMap<Principal, DelegatedVote>>,
+
+    commitments: BTreeMap<GroupId, BTreeMap<Principal, VoteCommitment>>,
+    unrevealed_shares_by_round: BTreeMap<RoundId, BTreeMap<GroupId, Shares>>,
+
+    shares_info_cache: BTreeMap<GroupId, BTreeMap<Principal, SharesInfo>>,
+
     rejection_choice: Option<ChoiceId>,
     approval_choice: Option<ChoiceId>,
+    abstain_choice: Option<ChoiceId>,
+    veto_choice: Option<ChoiceId>,
+
+    execution_results: BTreeMap<ChoiceId, ProgramExecutionResult>,
+    retrying_executions: BTreeSet<ChoiceId>,
 }
 
 impl Voting {
     pub fn new(
-        voting_config_id: VotingConfigId,
+        voting_config: VotingConfig,
         name: String,
         description: String,
         winners_need: u32,
@@ -53,13 +77,18 @@ impl Voting {
 
         let voting = Self {
             id: None,
-            voting_config_id,
+            voting_config_id: voting_config.get_id().unwrap(),
+            voting_config,
 
             created_at: timestamp,
             updated_at: timestamp,
             status: VotingStatus::Round(0),
             proposer,
+            locked_bond: None,
+            pipeline_stage: None,
             task_id: None,
+            round_ends_at: None,
+            round_extensions: Vec::new(),
 
             name: Self::process_name(name)?,
             description: Self::process_description(description)?,
@@ -71,8 +100,23 @@ impl Voting {
             losers: Vec::new(),
             choices: BTreeSet::new(),
 
+            ranked_ballots: BTreeMap::new(),
+
+            direct_votes: BTreeMap::new(),
+            delegated_votes: BTreeMap::new(),
+
+            commitments: BTreeMap::new(),
+            unrevealed_shares_by_round: BTreeMap::new(),
+
+            shares_info_cache: BTreeMap::new(),
+
             rejection_choice: None,
             approval_choice: None,
+            abstain_choice: None,
+            veto_choice: None,
+
+            execution_results: BTreeMap::new(),
+            retrying_executions: BTreeSet::new(),
         };
 
         Ok(voting)
@@ -85,6 +129,34 @@ impl Voting {
         self.approval_choice = Some(approval);
     }
 
+    pub fn init_abstain_choice(&mut self, abstain: ChoiceId) {
+        assert!(self.abstain_choice.is_none());
+
+        self.abstain_choice = Some(abstain);
+    }
+
+    pub fn init_locked_bond(&mut self, group_id: GroupId, quantity: Shares) {
+        assert!(self.locked_bond.is_none());
+
+        self.locked_bond = Some(LockedBond {
+            group_id,
+            quantity,
+            status: LockedBondStatus::Locked,
+        });
+    }
+
+    pub fn init_pipeline_stage(&mut self, pipeline_stage: PipelineStage) {
+        assert!(self.pipeline_stage.is_none());
+
+        self.pipeline_stage = Some(pipeline_stage);
+    }
+
+    pub fn init_veto_choice(&mut self, veto: ChoiceId) {
+        assert!(self.veto_choice.is_none());
+
+        self.veto_choice = Some(veto);
+    }
+
     pub fn update(
         &mut self,
         new_name: Option<String>,
@@ -156,16 +228,72 @@ impl Voting {
         match self.status {
             VotingStatus::Round(round) => {
                 self.status = VotingStatus::PreRound(round + 1);
+                self.clear_round_votes();
                 self.updated_at = timestamp;
             }
             _ => unreachable!(),
         }
     }
 
+    pub fn start_reveal(&mut self, timestamp: u64) {
+        match self.status {
+            VotingStatus::Round(round) => {
+                self.status = VotingStatus::Reveal(round);
+                self.updated_at = timestamp;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // returns all commitments of the round, so the unrevealed ones could be processed
+    pub fn end_reveal(
+        &mut self,
+        timestamp: u64,
+    ) -> BTreeMap<GroupId, BTreeMap<Principal, VoteCommitment>> {
+        match self.status {
+            VotingStatus::Reveal(round) => {
+                self.status = VotingStatus::Round(round);
+                self.updated_at = timestamp;
+
+                std::mem::take(&mut self.commitments)
+            }
+            _ => unreachable!(),
+        }
+    }
+
     pub fn finish_success(&mut self, timestamp: u64) {
         assert!(matches!(self.status, VotingStatus::Round(_)));
 
-        self.status = VotingStatus::Success;
+        self.status = VotingStatus::AwaitingExecution;
+        self.clear_round_votes();
+        self.updated_at = timestamp;
+    }
+
+    pub fn veto(&mut self, timestamp: u64) {
+        assert!(matches!(self.status, VotingStatus::AwaitingExecution));
+
+        self.status = VotingStatus::Vetoed;
+        self.updated_at = timestamp;
+    }
+
+    pub fn start_execution(&mut self, timestamp: u64) {
+        assert!(matches!(self.status, VotingStatus::AwaitingExecution));
+
+        self.status = VotingStatus::Executing;
+        self.updated_at = timestamp;
+    }
+
+    pub fn finish_execution(&mut self, timestamp: u64) {
+        assert!(matches!(self.status, VotingStatus::Executing));
+
+        self.status = VotingStatus::Executed;
+        self.updated_at = timestamp;
+    }
+
+    pub fn fail_execution(&mut self, reason_msg: String, timestamp: u64) {
+        assert!(matches!(self.status, VotingStatus::Executing));
+
+        self.status = VotingStatus::Fail(reason_msg);
         self.updated_at = timestamp;
     }
 
@@ -181,6 +309,27 @@ impl Voting {
         self.updated_at = timestamp;
     }
 
+    pub fn set_locked_bond_status(&mut self, status: LockedBondStatus, timestamp: u64) {
+        self.locked_bond.as_mut().unwrap().status = status;
+        self.updated_at = timestamp;
+    }
+
+    pub fn set_next_stage_voting(&mut self, voting_id: VotingId, timestamp: u64) {
+        self.pipeline_stage.as_mut().unwrap().next_voting = Some(voting_id);
+        self.updated_at = timestamp;
+    }
+
+    pub fn set_round_ends_at(&mut self, round_ends_at: u64, timestamp: u64) {
+        self.round_ends_at = Some(round_ends_at);
+        self.updated_at = timestamp;
+    }
+
+    pub fn add_round_extension(&mut self, extension: RoundExtension, timestamp: u64) {
+        self.round_ends_at = Some(extension.round_ends_at);
+        self.round_extensions.push(extension);
+        self.updated_at = timestamp;
+    }
+
     pub fn add_winner(&mut self, round_result: RoundResult, timestamp: u64) {
         self.winners.push(round_result);
         self.updated_at = timestamp;
@@ -201,14 +350,222 @@ impl Voting {
         self.updated_at = timestamp;
     }
 
+    pub fn set_ranked_ballot(
+        &mut self,
+        group_id: GroupId,
+        principal: Principal,
+        ballot: RankedBallot,
+        timestamp: u64,
+    ) {
+        self.ranked_ballots
+            .entry(group_id)
+            .or_default()
+            .insert(principal, ballot);
+        self.updated_at = timestamp;
+    }
+
+    pub fn remove_ranked_ballot(
+        &mut self,
+        group_id: &GroupId,
+        principal: &Principal,
+        timestamp: u64,
+    ) {
+        if let Some(ballots) = self.ranked_ballots.get_mut(group_id) {
+            ballots.remove(principal);
+        }
+
+        self.updated_at = timestamp;
+    }
+
+    pub fn set_commitment(
+        &mut self,
+        group_id: GroupId,
+        principal: Principal,
+        commitment: VoteCommitment,
+        timestamp: u64,
+    ) {
+        self.commitments
+            .entry(group_id)
+            .or_default()
+            .insert(principal, commitment);
+        self.updated_at = timestamp;
+    }
+
+    pub fn mark_commitment_revealed(
+        &mut self,
+        group_id: &GroupId,
+        principal: &Principal,
+        timestamp: u64,
+    ) {
+        let commitment = self
+            .commitments
+            .get_mut(group_id)
+            .and_then(|it| it.get_mut(principal))
+            .unwrap();
+
+        commitment.revealed = true;
+        self.updated_at = timestamp;
+    }
+
+    pub fn get_commitment(
+        &self,
+        group_id: &GroupId,
+        principal: &Principal,
+    ) -> Option<&VoteCommitment> {
+        self.commitments.get(group_id)?.get(principal)
+    }
+
+    // shares info fetched from the history ledger never changes, since it is taken at the voting's creation time
+    pub fn cache_shares_info(&mut self, shares_info: SharesInfo) {
+        self.shares_info_cache
+            .entry(shares_info.group_id)
+            .or_default()
+            .insert(shares_info.principal_id, shares_info);
+    }
+
+    pub fn get_cached_shares_info(
+        &self,
+        group_id: &GroupId,
+        principal: &Principal,
+    ) -> Option<&SharesInfo> {
+        self.shares_info_cache.get(group_id)?.get(principal)
+    }
+
+    pub fn add_unrevealed_shares(
+        &mut self,
+        round: RoundId,
+        group_id: GroupId,
+        shares: Shares,
+        timestamp: u64,
+    ) {
+        let by_group = self.unrevealed_shares_by_round.entry(round).or_default();
+        let prev_shares = by_group.get(&group_id).cloned().unwrap_or_default();
+        by_group.insert(group_id, prev_shares + shares);
+
+        self.updated_at = timestamp;
+    }
+
+    pub fn get_unrevealed_shares_by_group(&self, round: RoundId) -> BTreeMap<GroupId, Shares> {
+        self.unrevealed_shares_by_round
+            .get(&round)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // a direct vote always replaces the one made by a delegate
+    pub fn set_direct_vote(
+        &mut self,
+        group_id: GroupId,
+        principal: Principal,
+        shares: Shares,
+        timestamp: u64,
+    ) {
+        if let Some(delegated) = self.delegated_votes.get_mut(&group_id) {
+            delegated.remove(&principal);
+        }
+
+        self.direct_votes
+            .entry(group_id)
+            .or_default()
+            .insert(principal, shares);
+        self.updated_at = timestamp;
+    }
+
+    pub fn set_delegated_vote(
+        &mut self,
+        group_id: GroupId,
+        delegator: Principal,
+        vote: DelegatedVote,
+        timestamp: u64,
+    ) {
+        assert!(!self.has_direct_vote(&group_id, &delegator));
+
+        self.delegated_votes
+            .entry(group_id)
+            .or_default()
+            .insert(delegator, vote);
+        self.updated_at = timestamp;
+    }
+
+    pub fn has_direct_vote(&self, group_id: &GroupId, principal: &Principal) -> bool {
+        self.direct_votes
+            .get(group_id)
+            .map(|it| it.contains_key(principal))
+            .unwrap_or_default()
+    }
+
+    pub fn has_delegated_vote(&self, group_id: &GroupId, principal: &Principal) -> bool {
+        self.delegated_votes
+            .get(group_id)
+            .map(|it| it.contains_key(principal))
+            .unwrap_or_default()
+    }
+
+    pub fn get_direct_vote(&self, group_id: &GroupId, principal: &Principal) -> Option<&Shares> {
+        self.direct_votes.get(group_id)?.get(principal)
+    }
+
+    pub fn list_delegated_votes_of(
+        &self,
+        group_id: &GroupId,
+        delegate: &Principal,
+    ) -> BTreeMap<Principal, Shares> {
+        self.delegated_votes
+            .get(group_id)
+            .map(|votes| {
+                votes
+                    .iter()
+                    .filter(|(_, vote)| vote.delegate == *delegate)
+                    .map(|(delegator, vote)| (*delegator, vote.shares.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // returns (group, voter, new choice, shares) for each ballot that was moved to another choice
+    pub fn transfer_ranked_ballots(
+        &mut self,
+        eliminated: &BTreeSet<ChoiceId>,
+        timestamp: u64,
+    ) -> Vec<(GroupId, Principal, ChoiceId, Shares)> {
+        let mut transfers = Vec::new();
+
+        for (group_id, ballots) in self.ranked_ballots.iter_mut() {
+            for (principal, ballot) in ballots.iter_mut() {
+                match ballot.get_current_choice() {
+                    Some(choice_id) if eliminated.contains(&choice_id) => {
+                        if let Some(new_choice_id) = ballot.transfer(&self.choices) {
+                            transfers.push((
+                                *group_id,
+                                *principal,
+                                new_choice_id,
+                                ballot.get_shares().clone(),
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.updated_at = timestamp;
+
+        transfers
+    }
+
     pub fn get_voting_config_id(&self) -> &VotingConfigId {
         &self.voting_config_id
     }
 
+    // a copy of the voting config made at the creation of this voting
+    pub fn get_voting_config(&self) -> &VotingConfig {
+        &self.voting_config
+    }
+
     pub fn get_created_at(&self) -> u64 {
         self.created_at
     }
-    
+
     pub fn get_updated_at(&self) -> u64 {
         self.updated_at
     }
@@ -225,6 +582,10 @@ impl Voting {
         &self.choices
     }
 
+    pub fn get_ranked_ballots(&self) -> &BTreeMap<GroupId, BTreeMap<Principal, RankedBallot>> {
+        &self.ranked_ballots
+    }
+
     pub fn get_approval_choice(&self) -> ChoiceId {
         self.approval_choice.unwrap()
     }
@@ -233,6 +594,49 @@ impl Voting {
         self.rejection_choice.unwrap()
     }
 
+    // votings created before abstentions were introduced don't have this choice
+    pub fn get_abstain_choice(&self) -> Option<ChoiceId> {
+        self.abstain_choice
+    }
+
+    pub fn get_veto_choice(&self) -> Option<ChoiceId> {
+        self.veto_choice
+    }
+
+    pub fn set_execution_result(
+        &mut self,
+        choice_id: ChoiceId,
+        result: ProgramExecutionResult,
+        timestamp: u64,
+    ) {
+        self.execution_results.insert(choice_id, result);
+        self.updated_at = timestamp;
+    }
+
+    // the previous result is kept until the retry is over, so a failed retry loses nothing
+    pub fn start_execution_retry(&mut self, choice_id: ChoiceId, timestamp: u64) -> bool {
+        if !self.retrying_executions.insert(choice_id) {
+            return false;
+        }
+
+        self.updated_at = timestamp;
+        true
+    }
+
+    pub fn finish_execution_retry(
+        &mut self,
+        choice_id: ChoiceId,
+        result: ProgramExecutionResult,
+        timestamp: u64,
+    ) {
+        self.retrying_executions.remove(&choice_id);
+        self.set_execution_result(choice_id, result, timestamp);
+    }
+
+    pub fn get_execution_results(&self) -> &BTreeMap<ChoiceId, ProgramExecutionResult> {
+        &self.execution_results
+    }
+
     pub fn get_total_voting_power_by_group(&self) -> &BTreeMap<GroupId, Shares> {
         &self.total_voting_power_by_group
     }
@@ -253,6 +657,44 @@ impl Voting {
         self.proposer
     }
 
+    pub fn get_locked_bond(&self) -> &Option<LockedBond> {
+        &self.locked_bond
+    }
+
+    pub fn get_pipeline_stage(&self) -> &Option<PipelineStage> {
+        &self.pipeline_stage
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn get_round_ends_at(&self) -> Option<u64> {
+        self.round_ends_at
+    }
+
+    pub fn get_round_extensions(&self) -> &Vec<RoundExtension> {
+        &self.round_extensions
+    }
+
+    pub fn count_round_extensions(&self, round: RoundId) -> u32 {
+        self.round_extensions
+            .iter()
+            .filter(|it| it.round == round)
+            .count() as u32
+    }
+
+    // who voted by themselves and on whose behalf is only tracked within a round (or the veto phase),
+    // so a vote from an earlier round doesn't block the delegation in the next one
+    fn clear_round_votes(&mut self) {
+        self.direct_votes.clear();
+        self.delegated_votes.clear();
+    }
+
     fn process_name(name: String) -> Result<String, ValidationError> {
         validate_and_trim_str(
             name,
@@ -286,3 +728,82 @@ impl Model<VotingId> for Voting {
         self.id.is_none()
     }
 }
+
+// the shape of a voting, as it is stored by unions from before votings had their own config snapshot
+#[derive(CandidType, Deserialize)]
+pub struct LegacyVoting {
+    pub id: Option<VotingId>,
+    pub voting_config_id: VotingConfigId,
+
+    pub status: LegacyVotingStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub proposer: Principal,
+
+    pub task_id: Option<TaskId>,
+
+    pub name: String,
+    pub description: String,
+    pub winners_need: u32,
+
+    pub total_voting_power_by_group: BTreeMap<GroupId, Shares>,
+
+    pub winners: Vec<RoundResult>,
+    pub losers: Vec<RoundResult>,
+
+    pub choices: BTreeSet<ChoiceId>,
+
+    pub rejection_choice: Option<ChoiceId>,
+    pub approval_choice: Option<ChoiceId>,
+}
+
+impl Voting {
+    // the voting config is already migrated as well, it is the one the voting was created with
+    pub fn from_legacy(it: LegacyVoting, voting_config: VotingConfig) -> Self {
+        Self {
+            id: it.id,
+            voting_config_id: it.voting_config_id,
+            voting_config,
+
+            status: VotingStatus::from(it.status),
+            created_at: it.created_at,
+            updated_at: it.updated_at,
+            proposer: it.proposer,
+            locked_bond: None,
+            pipeline_stage: None,
+
+            task_id: it.task_id,
+            round_ends_at: None,
+            round_extensions: Vec::new(),
+
+            name: it.name,
+            description: it.description,
+            winners_need: it.winners_need,
+
+            total_voting_power_by_group: it.total_voting_power_by_group,
+
+            winners: it.winners,
+            losers: it.losers,
+
+            choices: it.choices,
+
+            ranked_ballots: BTreeMap::new(),
+
+            direct_votes: BTreeMap::new(),
+            delegated_votes: BTreeMap::new(),
+
+            commitments: BTreeMap::new(),
+            unrevealed_shares_by_round: BTreeMap::new(),
+
+            shares_info_cache: BTreeMap::new(),
+
+            rejection_choice: it.rejection_choice,
+            approval_choice: it.approval_choice,
+            abstain_choice: None,
+            veto_choice: None,
+
+            execution_results: BTreeMap::new(),
+            retrying_executions: BTreeSet::new(),
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use candid::{CandidType, Deserialize};
-use shared::types::wallet::ChoiceId;
+use crate::repository::pipeline::types::PipelineId;
+use crate::repository::voting_config::types::{Fraction, OverSubscriptionPolicy};
+use candid::{CandidType, Deserialize, Principal};
+use shared::types::history_ledger::SharesInfo;
+use shared::types::wallet::{ChoiceId, GroupId, Shares, VotingConfigId, VotingId};
 use std::collections::BTreeSet;
 
 pub const VOTING_NAME_MIN_LEN: usize = 1;
@@ -13,15 +16,67 @@ pub type RoundId = u16;
 pub enum VotingStatus {
     PreRound(RoundId),
     Round(RoundId),
+    Reveal(RoundId),
     Rejected,
-    Success,
+    AwaitingExecution,
+    Vetoed,
+    Executing,
+    Executed,
     Fail(String),
 }
 
+// the proposal bond, locked from the proposer when the voting is created
+#[derive(Clone, CandidType, Deserialize)]
+pub struct LockedBond {
+    pub group_id: GroupId,
+    pub quantity: Shares,
+    pub status: LockedBondStatus,
+}
+
+#[derive(Debug, Clone, Copy, CandidType, Deserialize)]
+pub enum LockedBondStatus {
+    Locked,
+    Returned,
+    Burned,
+    Kept,
+}
+
+// the place of a voting within a pipeline; the stages are copied, so later pipeline updates
+// don't affect votings which are already started
+#[derive(Clone, CandidType, Deserialize)]
+pub struct PipelineStage {
+    pub pipeline_id: PipelineId,
+    pub stages: Vec<VotingConfigId>,
+    pub stage: u32,
+    pub prev_voting: Option<VotingId>,
+    pub next_voting: Option<VotingId>,
+}
+
+impl PipelineStage {
+    pub fn get_next_voting_config(&self) -> Option<VotingConfigId> {
+        self.stages.get(self.stage as usize + 1).cloned()
+    }
+
+    // only the first stage is created by the proposer, the rest follow once the previous one is approved
+    pub fn is_automatic(&self) -> bool {
+        self.stage > 0
+    }
+}
+
+// a round, prolonged because of a late vote that changed which choices pass
+#[derive(Clone, CandidType, Deserialize)]
+pub struct RoundExtension {
+    pub round: RoundId,
+    pub caused_by: Principal,
+    pub timestamp: u64,
+    pub round_ends_at: u64,
+}
+
 #[derive(Clone, CandidType, Deserialize)]
 pub struct RoundResult {
     round: RoundId,
     choices: BTreeSet<ChoiceId>,
+    over_subscription: Option<OverSubscription>,
 }
 
 impl RoundResult {
@@ -29,6 +84,7 @@ impl RoundResult {
         Self {
             round,
             choices: BTreeSet::new(),
+            over_subscription: None,
         }
     }
 
@@ -36,12 +92,22 @@ impl RoundResult {
         self.choices.insert(choice_id);
     }
 
-    pub fn get_round(&self) -> RoundId { self.round }
-    
+    pub fn get_round(&self) -> RoundId {
+        self.round
+    }
+
     pub fn get_choices(&self) -> &BTreeSet<ChoiceId> {
         &self.choices
     }
 
+    pub fn set_over_subscription(&mut self, over_subscription: OverSubscription) {
+        self.over_subscription = Some(over_subscription);
+    }
+
+    pub fn get_over_subscription(&self) -> &Option<OverSubscription> {
+        &self.over_subscription
+    }
+
     pub fn len(&self) -> usize {
         self.choices.len()
     }
@@ -51,9 +117,151 @@ impl RoundResult {
     }
 }
 
+// how the round was resolved, when more choices won than there were winner slots left
+#[derive(Clone, CandidType, Deserialize)]
+pub struct OverSubscription {
+    // the policy which was actually applied
+    pub policy: OverSubscriptionPolicy,
+    pub slots_left: u32,
+
+    // all winning choices with their scores, from the best to the worst (ties are broken by id)
+    pub scores: Vec<(ChoiceId, Fraction)>,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct RankedBallot {
+    ranking: Vec<ChoiceId>,
+    shares: Shares,
+    current_choice: Option<ChoiceId>,
+}
+
+impl RankedBallot {
+    pub fn new(ranking: Vec<ChoiceId>, shares: Shares) -> Self {
+        let current_choice = ranking.first().cloned();
+
+        Self {
+            ranking,
+            shares,
+            current_choice,
+        }
+    }
+
+    // moves the ballot to the most preferred choice which is still in the voting
+    pub fn transfer(&mut self, continuing: &BTreeSet<ChoiceId>) -> Option<ChoiceId> {
+        self.current_choice = self
+            .ranking
+            .iter()
+            .find(|id| continuing.contains(id))
+            .cloned();
+
+        self.current_choice
+    }
+
+    pub fn get_ranking(&self) -> &Vec<ChoiceId> {
+        &self.ranking
+    }
+
+    pub fn get_shares(&self) -> &Shares {
+        &self.shares
+    }
+
+    pub fn get_current_choice(&self) -> Option<ChoiceId> {
+        self.current_choice
+    }
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct VoteCommitment {
+    pub hash: Vec<u8>,
+    pub shares_info: SharesInfo,
+    pub delegated_shares_info: Vec<SharesInfo>,
+    pub revealed: bool,
+}
+
+#[derive(Clone, CandidType, Deserialize)]
+pub struct DelegatedVote {
+    pub delegate: Principal,
+    pub shares: Shares,
+}
+
 #[derive(CandidType, Deserialize)]
 pub enum VotingSort {
     None,
     CreatedAt(bool),
     UpdatedAt(bool),
-}
\ No newline at end of file
+}
+
+// the same as the voting status, but a round could be omitted to match any round
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub enum VotingStatusFilter {
+    PreRound(Option<RoundId>),
+    Round(Option<RoundId>),
+    Reveal(Option<RoundId>),
+    Rejected,
+    AwaitingExecution,
+    Vetoed,
+    Executing,
+    Executed,
+    Fail,
+}
+
+impl VotingStatusFilter {
+    pub fn matches(&self, other: &VotingStatusFilter) -> bool {
+        match (self, other) {
+            (VotingStatusFilter::PreRound(None), VotingStatusFilter::PreRound(_))
+            | (VotingStatusFilter::Round(None), VotingStatusFilter::Round(_))
+            | (VotingStatusFilter::Reveal(None), VotingStatusFilter::Reveal(_)) => true,
+            _ => self == other,
+        }
+    }
+}
+
+impl From<&VotingStatus> for VotingStatusFilter {
+    fn from(status: &VotingStatus) -> Self {
+        match status {
+            VotingStatus::PreRound(r) => VotingStatusFilter::PreRound(Some(*r)),
+            VotingStatus::Round(r) => VotingStatusFilter::Round(Some(*r)),
+            VotingStatus::Reveal(r) => VotingStatusFilter::Reveal(Some(*r)),
+            VotingStatus::Rejected => VotingStatusFilter::Rejected,
+            VotingStatus::AwaitingExecution => VotingStatusFilter::AwaitingExecution,
+            VotingStatus::Vetoed => VotingStatusFilter::Vetoed,
+            VotingStatus::Executing => VotingStatusFilter::Executing,
+            VotingStatus::Executed => VotingStatusFilter::Executed,
+            VotingStatus::Fail(_) => VotingStatusFilter::Fail,
+        }
+    }
+}
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct VotingFilter {
+    pub status: Option<VotingStatusFilter>,
+    pub voting_config: Option<VotingConfigId>,
+    pub proposer: Option<Principal>,
+    pub created_from: Option<u64>,
+    pub created_to: Option<u64>,
+    pub not_voted_by: Option<Principal>,
+    pub search: Option<String>,
+}
+
+// the voting status, as it is stored by unions from before delayed executions and vetoes
+#[derive(CandidType, Deserialize)]
+pub enum LegacyVotingStatus {
+    PreRound(RoundId),
+    Round(RoundId),
+    Rejected,
+    Success,
+    Fail(String),
+}
+
+impl From<LegacyVotingStatus> for VotingStatus {
+    fn from(it: LegacyVotingStatus) -> Self {
+        match it {
+            LegacyVotingStatus::PreRound(r) => VotingStatus::PreRound(r),
+            LegacyVotingStatus::Round(r) => VotingStatus::Round(r),
+            LegacyVotingStatus::Rejected => VotingStatus::Rejected,
+            // successful votings used to be executed right away
+            LegacyVotingStatus::Success => VotingStatus::Executed,
+            LegacyVotingStatus::Fail(reason) => VotingStatus::Fail(reason),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::repository::permission::types::PermissionId;
-use crate::repository::voting_config::model::VotingConfig;
+use crate::repository::voting_config::model::{LegacyVotingConfig, VotingConfig};
 use crate::repository::voting_config::types::VotingConfigFilter;
 use candid::{CandidType, Deserialize};
 use shared::mvc::{IdGenerator, Model, Repository};
@@ -124,6 +124,10 @@ impl VotingConfigRepository {
         for gop in voting_config.get_next_round_threshold().list_groups() {
             self.add_to_group_index(id, gop);
         }
+
+        if let Some(bond) = voting_config.get_proposal_bond() {
+            self.add_to_group_index(id, bond.group_id);
+        }
     }
 
     fn remove_from_indexes(&mut self, voting_config: &VotingConfig) {
@@ -152,6 +156,10 @@ impl VotingConfigRepository {
         for gop in voting_config.get_next_round_threshold().list_groups() {
             self.remove_from_group_index(&id, &gop);
         }
+
+        if let Some(bond) = voting_config.get_proposal_bond() {
+            self.remove_from_group_index(&id, &bond.group_id);
+        }
     }
 
     fn add_to_permissions_index(
@@ -190,3 +198,27 @@ impl VotingConfigRepository {
             .remove(voting_config_id);
     }
 }
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct LegacyVotingConfigRepository {
+    pub voting_configs: HashMap<VotingConfigId, LegacyVotingConfig>,
+    pub id_gen: IdGenerator,
+
+    pub voting_configs_by_group_index: BTreeMap<GroupId, BTreeSet<VotingConfigId>>,
+    pub voting_configs_by_permission_index: BTreeMap<PermissionId, BTreeSet<VotingConfigId>>,
+}
+
+impl From<LegacyVotingConfigRepository> for VotingConfigRepository {
+    fn from(it: LegacyVotingConfigRepository) -> Self {
+        Self {
+            voting_configs: it
+                .voting_configs
+                .into_iter()
+                .map(|(id, voting_config)| (id, VotingConfig::from(voting_config)))
+                .collect(),
+            id_gen: it.id_gen,
+            voting_configs_by_group_index: it.voting_configs_by_group_index,
+            voting_configs_by_permission_index: it.voting_configs_by_permission_index,
+        }
+    }
+}
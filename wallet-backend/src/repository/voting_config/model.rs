@@ -1,23 +1,34 @@
 use crate::repository::permission::types::PermissionId;
 use crate::repository::voting_config::types::{
-    LenInterval, RoundSettings, ThresholdValue, VOTING_CONFIG_DESCRIPTION_MAX_LEN,
-    VOTING_CONFIG_DESCRIPTION_MIN_LEN, VOTING_CONFIG_NAME_MAX_LEN, VOTING_CONFIG_NAME_MIN_LEN,
+    CommitRevealSettings, Fraction, LegacyRoundSettings, LenInterval, OverSubscriptionPolicy,
+    ProposalBond, RoundSettings, TallyMode, ThresholdValue, VotingConfigParams, VotingConfigUpdate,
+    VotingPowerCap, VOTING_CONFIG_DESCRIPTION_MAX_LEN, VOTING_CONFIG_DESCRIPTION_MIN_LEN,
+    VOTING_CONFIG_NAME_MAX_LEN, VOTING_CONFIG_NAME_MIN_LEN,
 };
 use candid::{CandidType, Deserialize};
 use shared::mvc::Model;
-use shared::types::wallet::VotingConfigId;
+use shared::types::wallet::{GroupId, VotingConfigId};
 use shared::validation::{validate_and_trim_str, ValidationError};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, CandidType, Deserialize)]
 pub struct VotingConfig {
     id: Option<VotingConfigId>,
+    version: u32,
     name: String,
     description: String,
 
     choices_count: Option<LenInterval>,
     winners_count: Option<LenInterval>,
     round: RoundSettings,
+    tally_mode: TallyMode,
+    commit_reveal: Option<CommitRevealSettings>,
+    over_subscription: OverSubscriptionPolicy,
+    voting_power_cap: Option<VotingPowerCap>,
+    group_weights: BTreeMap<GroupId, Fraction>,
+    proposal_bond: Option<ProposalBond>,
+    max_open_votings_per_proposer: Option<u32>,
+    veto: Option<ThresholdValue>,
 
     permissions: BTreeSet<PermissionId>,
 
@@ -29,19 +40,29 @@ pub struct VotingConfig {
 }
 
 impl VotingConfig {
-    pub fn new(
-        name: String,
-        description: String,
-        choices_count: Option<LenInterval>,
-        winners_count: Option<LenInterval>,
-        permissions: BTreeSet<PermissionId>,
-        round: RoundSettings,
-        approval: ThresholdValue,
-        quorum: ThresholdValue,
-        rejection: ThresholdValue,
-        win: ThresholdValue,
-        next_round: ThresholdValue,
-    ) -> Result<VotingConfig, ValidationError> {
+    pub fn new(params: VotingConfigParams) -> Result<VotingConfig, ValidationError> {
+        let VotingConfigParams {
+            name,
+            description,
+            choices_count,
+            winners_count,
+            permissions,
+            round,
+            tally_mode,
+            commit_reveal,
+            over_subscription,
+            voting_power_cap,
+            group_weights,
+            proposal_bond,
+            max_open_votings_per_proposer,
+            veto,
+            approval,
+            quorum,
+            rejection,
+            win,
+            next_round,
+        } = params;
+
         if let Some(cc) = &choices_count {
             if !cc.is_valid() {
                 return Err(ValidationError(
@@ -58,38 +79,65 @@ impl VotingConfig {
             }
         }
 
+        Self::assert_commit_reveal_supported(tally_mode, &commit_reveal)?;
+        Self::assert_early_finish_supported(&round, tally_mode, &commit_reveal)?;
+
         let voting_config = VotingConfig {
             id: None,
+            version: 1,
             name: Self::process_name(name)?,
             description: Self::process_description(description)?,
             choices_count,
             winners_count,
             permissions,
-            round,
-            approval,
-            quorum,
-            rejection,
-            win,
-            next_round,
+            round: Self::process_round(round)?,
+            tally_mode,
+            commit_reveal,
+            over_subscription,
+            voting_power_cap: voting_power_cap
+                .map(Self::process_voting_power_cap)
+                .transpose()?,
+            group_weights: Self::process_group_weights(group_weights)?,
+            proposal_bond: proposal_bond.map(Self::process_proposal_bond).transpose()?,
+            max_open_votings_per_proposer: Self::process_max_open_votings_per_proposer(
+                max_open_votings_per_proposer,
+            )?,
+            veto: veto
+                .map(|it| Self::process_threshold(it, "Veto"))
+                .transpose()?,
+            approval: Self::process_threshold(approval, "Approval")?,
+            quorum: Self::process_threshold(quorum, "Quorum")?,
+            rejection: Self::process_threshold(rejection, "Rejection")?,
+            win: Self::process_threshold(win, "Win")?,
+            next_round: Self::process_threshold(next_round, "Next round")?,
         };
 
         Ok(voting_config)
     }
 
-    pub fn update(
-        &mut self,
-        name_opt: Option<String>,
-        description_opt: Option<String>,
-        choices_count_opt: Option<Option<LenInterval>>,
-        winners_count_opt: Option<Option<LenInterval>>,
-        permissions_opt: Option<BTreeSet<PermissionId>>,
-        round_opt: Option<RoundSettings>,
-        approval_opt: Option<ThresholdValue>,
-        quorum_opt: Option<ThresholdValue>,
-        rejection_opt: Option<ThresholdValue>,
-        win_opt: Option<ThresholdValue>,
-        next_round_opt: Option<ThresholdValue>,
-    ) -> Result<(), ValidationError> {
+    pub fn update(&mut self, update: VotingConfigUpdate) -> Result<(), ValidationError> {
+        let VotingConfigUpdate {
+            name_opt,
+            description_opt,
+            choices_count_opt,
+            winners_count_opt,
+            permissions_opt,
+            round_opt,
+            tally_mode_opt,
+            commit_reveal_opt,
+            over_subscription_opt,
+            voting_power_cap_opt,
+            group_weights_opt,
+            proposal_bond_opt,
+            max_open_votings_per_proposer_opt,
+            veto_opt,
+            approval_opt,
+            quorum_opt,
+            rejection_opt,
+            win_opt,
+            next_round_opt,
+        } = update;
+
         if let Some(name) = name_opt {
             self.name = Self::process_name(name)?;
         }
@@ -127,36 +175,114 @@ impl VotingConfig {
         }
 
         if let Some(round) = round_opt {
-            self.round = round;
+            self.round = Self::process_round(round)?;
+        }
+
+        if let Some(tally_mode) = tally_mode_opt {
+            self.tally_mode = tally_mode;
+        }
+
+        if let Some(commit_reveal) = commit_reveal_opt {
+            self.commit_reveal = commit_reveal;
+        }
+
+        Self::assert_commit_reveal_supported(self.tally_mode, &self.commit_reveal)?;
+        Self::assert_early_finish_supported(&self.round, self.tally_mode, &self.commit_reveal)?;
+
+        if let Some(over_subscription) = over_subscription_opt {
+            self.over_subscription = over_subscription;
+        }
+
+        if let Some(voting_power_cap) = voting_power_cap_opt {
+            self.voting_power_cap = voting_power_cap
+                .map(Self::process_voting_power_cap)
+                .transpose()?;
+        }
+
+        if let Some(group_weights) = group_weights_opt {
+            self.group_weights = Self::process_group_weights(group_weights)?;
+        }
+
+        if let Some(proposal_bond) = proposal_bond_opt {
+            self.proposal_bond = proposal_bond.map(Self::process_proposal_bond).transpose()?;
+        }
+
+        if let Some(max_open_votings_per_proposer) = max_open_votings_per_proposer_opt {
+            self.max_open_votings_per_proposer =
+                Self::process_max_open_votings_per_proposer(max_open_votings_per_proposer)?;
+        }
+
+        if let Some(veto) = veto_opt {
+            self.veto = veto
+                .map(|it| Self::process_threshold(it, "Veto"))
+                .transpose()?;
         }
 
         if let Some(approval) = approval_opt {
-            self.approval = approval;
+            self.approval = Self::process_threshold(approval, "Approval")?;
         }
 
         if let Some(quorum) = quorum_opt {
-            self.quorum = quorum;
+            self.quorum = Self::process_threshold(quorum, "Quorum")?;
         }
 
         if let Some(rejection) = rejection_opt {
-            self.rejection = rejection;
+            self.rejection = Self::process_threshold(rejection, "Rejection")?;
         }
 
         if let Some(win) = win_opt {
-            self.win = win;
+            self.win = Self::process_threshold(win, "Win")?;
         }
 
         if let Some(next_round) = next_round_opt {
-            self.next_round = next_round;
+            self.next_round = Self::process_threshold(next_round, "Next round")?;
         }
 
+        self.version += 1;
+
         Ok(())
     }
 
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
     pub fn get_round_settings(&self) -> &RoundSettings {
         &self.round
     }
 
+    pub fn get_tally_mode(&self) -> TallyMode {
+        self.tally_mode
+    }
+
+    pub fn get_commit_reveal_settings(&self) -> &Option<CommitRevealSettings> {
+        &self.commit_reveal
+    }
+
+    pub fn get_over_subscription_policy(&self) -> OverSubscriptionPolicy {
+        self.over_subscription
+    }
+
+    pub fn get_voting_power_cap(&self) -> &Option<VotingPowerCap> {
+        &self.voting_power_cap
+    }
+
+    pub fn get_group_weights(&self) -> &BTreeMap<GroupId, Fraction> {
+        &self.group_weights
+    }
+
+    pub fn get_proposal_bond(&self) -> &Option<ProposalBond> {
+        &self.proposal_bond
+    }
+
+    pub fn get_max_open_votings_per_proposer(&self) -> Option<u32> {
+        self.max_open_votings_per_proposer
+    }
+
+    pub fn get_veto_threshold(&self) -> &Option<ThresholdValue> {
+        &self.veto
+    }
+
     pub fn get_winners_count(&self) -> &Option<LenInterval> {
         &self.winners_count
     }
@@ -189,6 +315,104 @@ impl VotingConfig {
         &self.next_round
     }
 
+    // ranked ballots can't be committed, since only multi-choice votes are hashed
+    fn assert_commit_reveal_supported(
+        tally_mode: TallyMode,
+        commit_reveal: &Option<CommitRevealSettings>,
+    ) -> Result<(), ValidationError> {
+        if commit_reveal.is_some() && matches!(tally_mode, TallyMode::InstantRunoff) {
+            return Err(ValidationError(
+                "Commit-reveal is not supported by instant-runoff tally mode".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // the outcome can't be predicted, when votes are hidden or not comparable with the voting power
+    fn assert_early_finish_supported(
+        round: &RoundSettings,
+        tally_mode: TallyMode,
+        commit_reveal: &Option<CommitRevealSettings>,
+    ) -> Result<(), ValidationError> {
+        if !round.early_finish {
+            return Ok(());
+        }
+
+        if !matches!(tally_mode, TallyMode::Common) {
+            return Err(ValidationError(
+                "Early finish is only supported by common tally mode".to_string(),
+            ));
+        }
+
+        if commit_reveal.is_some() {
+            return Err(ValidationError(
+                "Early finish is not supported by commit-reveal".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn process_threshold(
+        threshold: ThresholdValue,
+        name: &str,
+    ) -> Result<ThresholdValue, ValidationError> {
+        if !threshold.is_valid() {
+            return Err(ValidationError(format!("{} threshold is invalid", name)));
+        }
+
+        Ok(threshold)
+    }
+
+    fn process_voting_power_cap(cap: VotingPowerCap) -> Result<VotingPowerCap, ValidationError> {
+        if !cap.is_valid() {
+            return Err(ValidationError("Invalid voting power cap".to_string()));
+        }
+
+        Ok(cap)
+    }
+
+    fn process_group_weights(
+        group_weights: BTreeMap<GroupId, Fraction>,
+    ) -> Result<BTreeMap<GroupId, Fraction>, ValidationError> {
+        if group_weights.values().any(|w| *w <= Fraction::default()) {
+            return Err(ValidationError(
+                "Group weights should be positive".to_string(),
+            ));
+        }
+
+        Ok(group_weights)
+    }
+
+    fn process_proposal_bond(bond: ProposalBond) -> Result<ProposalBond, ValidationError> {
+        if !bond.is_valid() {
+            return Err(ValidationError("Invalid proposal bond".to_string()));
+        }
+
+        Ok(bond)
+    }
+
+    fn process_max_open_votings_per_proposer(
+        max_opt: Option<u32>,
+    ) -> Result<Option<u32>, ValidationError> {
+        if max_opt == Some(0) {
+            return Err(ValidationError(
+                "Max open votings per proposer should be positive".to_string(),
+            ));
+        }
+
+        Ok(max_opt)
+    }
+
+    fn process_round(round: RoundSettings) -> Result<RoundSettings, ValidationError> {
+        if !round.is_valid() {
+            return Err(ValidationError("Invalid anti-sniping settings".to_string()));
+        }
+
+        Ok(round)
+    }
+
     fn process_name(name: String) -> Result<String, ValidationError> {
         validate_and_trim_str(
             name,
@@ -222,3 +446,52 @@ impl Model<VotingConfigId> for VotingConfig {
         self.id.is_none()
     }
 }
+
+// the shape of a voting config, as it is stored by unions from before tally modes, bonds and vetoes
+#[derive(CandidType, Deserialize)]
+pub struct LegacyVotingConfig {
+    pub id: Option<VotingConfigId>,
+    pub name: String,
+    pub description: String,
+
+    pub choices_count: Option<LenInterval>,
+    pub winners_count: Option<LenInterval>,
+    pub round: LegacyRoundSettings,
+
+    pub permissions: BTreeSet<PermissionId>,
+
+    pub approval: ThresholdValue,
+    pub rejection: ThresholdValue,
+    pub quorum: ThresholdValue,
+    pub win: ThresholdValue,
+    pub next_round: ThresholdValue,
+}
+
+// the new settings are set the way older unions used to behave
+impl From<LegacyVotingConfig> for VotingConfig {
+    fn from(it: LegacyVotingConfig) -> Self {
+        Self {
+            id: it.id,
+            version: 1,
+            name: it.name,
+            description: it.description,
+            choices_count: it.choices_count,
+            winners_count: it.winners_count,
+            round: RoundSettings::from(it.round),
+            tally_mode: TallyMode::Common,
+            commit_reveal: None,
+            over_subscription: OverSubscriptionPolicy::HighestShares,
+            voting_power_cap: None,
+            group_weights: BTreeMap::new(),
+            proposal_bond: None,
+            max_open_votings_per_proposer: None,
+            veto: None,
+            permissions: it.permissions,
+            approval: it.approval,
+            rejection: it.rejection,
+            quorum: it.quorum,
+            win: it.win,
+            next_round: it.next_round,
+        }
+    }
+}
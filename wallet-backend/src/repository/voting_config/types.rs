@@ -5,6 +5,7 @@ use candid::types::{Serializer, Type};
 use candid::{CandidType, Deserialize, Nat};
 use serde::Deserializer;
 use shared::types::wallet::{GroupId, Shares};
+use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{AddAssign, Div, Mul};
 use std::str::FromStr;
@@ -97,6 +98,10 @@ impl<'de> Deserialize<'de> for Fraction {
 pub enum ThresholdValue {
     QuantityOf(QuantityOf),
     FractionOf(FractionOf),
+    AllOf(Vec<ThresholdValue>),
+    AnyOf(Vec<ThresholdValue>),
+    Not(Box<ThresholdValue>),
+    AtLeastN(AtLeastN),
 }
 
 impl ThresholdValue {
@@ -112,62 +117,163 @@ impl ThresholdValue {
         &self,
         total: &BTreeMap<GroupId, Shares>,
         voted: &BTreeMap<GroupId, Shares>,
+        weights: &BTreeMap<GroupId, Fraction>,
     ) -> bool {
-        let (voted_shares, total_shares) = match self.get_target() {
+        match &self {
+            ThresholdValue::FractionOf(f) => {
+                let (voted_shares, total_shares) =
+                    Self::count_target(&f.target, total, voted, weights);
+
+                if total_shares == Fraction::default() {
+                    return false;
+                }
+
+                let voted_fraction = voted_shares / total_shares;
+
+                voted_fraction >= f.fraction
+            }
+            ThresholdValue::QuantityOf(q) => {
+                let (voted_shares, _) = Self::count_target(&q.target, total, voted, weights);
+
+                voted_shares >= Fraction::from(q.quantity.clone())
+            }
+            ThresholdValue::AllOf(thresholds) => thresholds
+                .iter()
+                .all(|th| th.is_reached(total, voted, weights)),
+            ThresholdValue::AnyOf(thresholds) => thresholds
+                .iter()
+                .any(|th| th.is_reached(total, voted, weights)),
+            ThresholdValue::Not(threshold) => !threshold.is_reached(total, voted, weights),
+            ThresholdValue::AtLeastN(a) => {
+                let reached = a
+                    .thresholds
+                    .iter()
+                    .filter(|th| th.is_reached(total, voted, weights))
+                    .count();
+
+                reached >= a.n as usize
+            }
+        }
+    }
+
+    // more votes never make a monotonic threshold unreached - only negations break it
+    pub fn is_monotonic(&self) -> bool {
+        match &self {
+            ThresholdValue::QuantityOf(QuantityOf { target, .. })
+            | ThresholdValue::FractionOf(FractionOf { target, .. }) => match target {
+                Target::Group(_) | Target::WeightedGroups(_) => true,
+                Target::Thresholds(t) => t.iter().all(|th| th.is_monotonic()),
+            },
+            ThresholdValue::AllOf(thresholds) | ThresholdValue::AnyOf(thresholds) => {
+                thresholds.iter().all(|th| th.is_monotonic())
+            }
+            ThresholdValue::Not(_) => false,
+            ThresholdValue::AtLeastN(a) => a.thresholds.iter().all(|th| th.is_monotonic()),
+        }
+    }
+
+    // combinators should never be empty, otherwise they are reached (or not) regardless of votes
+    pub fn is_valid(&self) -> bool {
+        match &self {
+            ThresholdValue::QuantityOf(q) => Self::is_target_valid(&q.target),
+            ThresholdValue::FractionOf(f) => Self::is_target_valid(&f.target),
+            ThresholdValue::AllOf(thresholds) | ThresholdValue::AnyOf(thresholds) => {
+                !thresholds.is_empty() && thresholds.iter().all(|th| th.is_valid())
+            }
+            ThresholdValue::Not(threshold) => threshold.is_valid(),
+            ThresholdValue::AtLeastN(a) => {
+                a.n >= 1
+                    && a.n as usize <= a.thresholds.len()
+                    && a.thresholds.iter().all(|th| th.is_valid())
+            }
+        }
+    }
+
+    fn count_target(
+        target: &Target,
+        total: &BTreeMap<GroupId, Shares>,
+        voted: &BTreeMap<GroupId, Shares>,
+        weights: &BTreeMap<GroupId, Fraction>,
+    ) -> (Fraction, Fraction) {
+        match target {
             Target::Group(group_id) => {
                 let voted_shares = voted.get(group_id).cloned().unwrap_or_default();
                 let total_shares = total.get(group_id).cloned().unwrap_or_default();
 
+                (Fraction::from(voted_shares), Fraction::from(total_shares))
+            }
+            Target::WeightedGroups(group_ids) => {
+                let mut voted_shares = Fraction::default();
+                let mut total_shares = Fraction::default();
+
+                for group_id in group_ids {
+                    // groups without an explicit weight count as is
+                    let weight = weights
+                        .get(group_id)
+                        .cloned()
+                        .unwrap_or_else(|| Fraction::from(1usize));
+
+                    voted_shares += weight.clone()
+                        * Fraction::from(voted.get(group_id).cloned().unwrap_or_default());
+                    total_shares +=
+                        weight * Fraction::from(total.get(group_id).cloned().unwrap_or_default());
+                }
+
                 (voted_shares, total_shares)
             }
             Target::Thresholds(thresholds) => {
                 let mut voted_shares_usize = 0usize;
-                let total_shares = Shares::from(thresholds.len());
+                let total_shares = Fraction::from(thresholds.len());
 
                 for th in thresholds {
-                    if th.is_reached(total, voted) {
+                    if th.is_reached(total, voted, weights) {
                         voted_shares_usize += 1;
                     }
                 }
-                let votes_shares = Shares::from(voted_shares_usize);
+                let votes_shares = Fraction::from(voted_shares_usize);
 
                 (votes_shares, total_shares)
             }
-        };
-
-        match &self {
-            ThresholdValue::FractionOf(f) => {
-                if total_shares == Shares::default() {
-                    return false;
-                }
-
-                let voted_fraction = Fraction::from(voted_shares) / Fraction::from(total_shares);
+        }
+    }
 
-                voted_fraction >= f.fraction
-            }
-            ThresholdValue::QuantityOf(q) => voted_shares >= q.quantity,
+    fn is_target_valid(target: &Target) -> bool {
+        match target {
+            Target::Group(_) => true,
+            Target::WeightedGroups(group_ids) => !group_ids.is_empty(),
+            Target::Thresholds(thresholds) => thresholds.iter().all(|th| th.is_valid()),
         }
     }
 
     fn _list_groups(&self, list: &mut BTreeSet<GroupId>) {
-        match self.get_target() {
-            Target::Group(r) => {
-                list.insert(*r);
+        match &self {
+            ThresholdValue::QuantityOf(QuantityOf { target, .. })
+            | ThresholdValue::FractionOf(FractionOf { target, .. }) => match target {
+                Target::Group(r) => {
+                    list.insert(*r);
+                }
+                Target::WeightedGroups(r) => {
+                    list.extend(r.iter().copied());
+                }
+                Target::Thresholds(t) => {
+                    for it in t {
+                        it._list_groups(list);
+                    }
+                }
+            },
+            ThresholdValue::AllOf(thresholds) | ThresholdValue::AnyOf(thresholds) => {
+                for it in thresholds {
+                    it._list_groups(list);
+                }
             }
-            Target::Thresholds(t) => {
-                for it in t {
+            ThresholdValue::Not(threshold) => threshold._list_groups(list),
+            ThresholdValue::AtLeastN(a) => {
+                for it in &a.thresholds {
                     it._list_groups(list);
                 }
             }
         }
     }
-
-    pub fn get_target(&self) -> &Target {
-        match &self {
-            ThresholdValue::QuantityOf(q) => &q.target,
-            ThresholdValue::FractionOf(f) => &f.target,
-        }
-    }
 }
 
 #[derive(Clone, CandidType, Deserialize)]
@@ -182,16 +288,123 @@ pub struct FractionOf {
     pub target: Target,
 }
 
+#[derive(Clone, CandidType, Deserialize)]
+pub struct AtLeastN {
+    pub n: u32,
+    pub thresholds: Vec<ThresholdValue>,
+}
+
 #[derive(Clone, CandidType, Deserialize)]
 pub enum Target {
     Thresholds(Vec<ThresholdValue>),
     Group(GroupId),
+    // shares of all these groups combined, each multiplied by its weight from the voting config
+    WeightedGroups(Vec<GroupId>),
 }
 
 #[derive(Clone, CandidType, Deserialize)]
 pub struct RoundSettings {
     pub round_duration: u64,
     pub round_delay: u64,
+    pub execution_delay: u64,
+    pub anti_sniping: Option<AntiSnipingSettings>,
+    pub early_finish: bool,
+}
+
+impl RoundSettings {
+    pub fn is_valid(&self) -> bool {
+        match &self.anti_sniping {
+            Some(settings) => settings.is_valid(self.round_duration),
+            None => true,
+        }
+    }
+}
+
+// extends the round, if a vote cast within the final window changes which choices pass
+#[derive(Clone, CandidType, Deserialize)]
+pub struct AntiSnipingSettings {
+    pub window: u64,
+    pub extension: u64,
+    pub max_extensions: u32,
+}
+
+impl AntiSnipingSettings {
+    pub fn is_valid(&self, round_duration: u64) -> bool {
+        self.window > 0
+            && self.window <= round_duration
+            && self.extension > 0
+            && self.max_extensions > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, CandidType, Deserialize)]
+pub enum TallyMode {
+    Common,
+    InstantRunoff,
+    Quadratic,
+}
+
+#[derive(Debug, Clone, Copy, CandidType, Deserialize)]
+pub struct CommitRevealSettings {
+    pub reveal_duration: u64,
+}
+
+// the most shares a single principal could contribute in each group, the rest is ignored
+#[derive(Clone, CandidType, Deserialize)]
+pub enum VotingPowerCap {
+    Quantity(Shares),
+    FractionOfTotalSupply(Fraction),
+}
+
+impl VotingPowerCap {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            VotingPowerCap::Quantity(q) => *q > Shares::default(),
+            VotingPowerCap::FractionOfTotalSupply(f) => {
+                *f > Fraction::default() && *f <= Fraction::from(1usize)
+            }
+        }
+    }
+
+    pub fn apply(&self, balance: Shares, total_supply: &Shares) -> Shares {
+        let cap = match self {
+            VotingPowerCap::Quantity(q) => q.clone(),
+            VotingPowerCap::FractionOfTotalSupply(f) => {
+                (f.clone() * Fraction::from(total_supply.clone())).into()
+            }
+        };
+
+        min(balance, cap)
+    }
+}
+
+// shares of a group, which are locked from the proposer until the voting is over
+#[derive(Clone, CandidType, Deserialize)]
+pub struct ProposalBond {
+    pub group_id: GroupId,
+    pub quantity: Shares,
+    pub on_rejection: RejectedBondPolicy,
+}
+
+impl ProposalBond {
+    pub fn is_valid(&self) -> bool {
+        self.quantity > Shares::default()
+    }
+}
+
+// the bond is returned to the proposer in any other case
+#[derive(Debug, Clone, Copy, CandidType, Deserialize)]
+pub enum RejectedBondPolicy {
+    Burn,
+    Keep,
+}
+
+// what to do when more choices reach the win threshold than there are winner slots left
+#[derive(Debug, Clone, Copy, CandidType, Deserialize)]
+pub enum OverSubscriptionPolicy {
+    HighestShares,
+    RunOff,
+    Fail,
 }
 
 #[derive(Debug, Clone, Copy, CandidType, Deserialize)]
@@ -214,3 +427,69 @@ pub struct VotingConfigFilter {
     pub group: Option<GroupId>,
     pub permission: Option<PermissionId>,
 }
+
+// everything a new voting config is made of
+pub struct VotingConfigParams {
+    pub name: String,
+    pub description: String,
+    pub choices_count: Option<LenInterval>,
+    pub winners_count: Option<LenInterval>,
+    pub permissions: BTreeSet<PermissionId>,
+    pub round: RoundSettings,
+    pub tally_mode: TallyMode,
+    pub commit_reveal: Option<CommitRevealSettings>,
+    pub over_subscription: OverSubscriptionPolicy,
+    pub voting_power_cap: Option<VotingPowerCap>,
+    pub group_weights: BTreeMap<GroupId, Fraction>,
+    pub proposal_bond: Option<ProposalBond>,
+    pub max_open_votings_per_proposer: Option<u32>,
+    pub veto: Option<ThresholdValue>,
+    pub approval: ThresholdValue,
+    pub quorum: ThresholdValue,
+    pub rejection: ThresholdValue,
+    pub win: ThresholdValue,
+    pub next_round: ThresholdValue,
+}
+
+// only the fields which are set are changed
+#[derive(Default)]
+pub struct VotingConfigUpdate {
+    pub name_opt: Option<String>,
+    pub description_opt: Option<String>,
+    pub choices_count_opt: Option<Option<LenInterval>>,
+    pub winners_count_opt: Option<Option<LenInterval>>,
+    pub permissions_opt: Option<BTreeSet<PermissionId>>,
+    pub round_opt: Option<RoundSettings>,
+    pub tally_mode_opt: Option<TallyMode>,
+    pub commit_reveal_opt: Option<Option<CommitRevealSettings>>,
+    pub over_subscription_opt: Option<OverSubscriptionPolicy>,
+    pub voting_power_cap_opt: Option<Option<VotingPowerCap>>,
+    pub group_weights_opt: Option<BTreeMap<GroupId, Fraction>>,
+    pub proposal_bond_opt: Option<Option<ProposalBond>>,
+    pub max_open_votings_per_proposer_opt: Option<Option<u32>>,
+    pub veto_opt: Option<Option<ThresholdValue>>,
+    pub approval_opt: Option<ThresholdValue>,
+    pub quorum_opt: Option<ThresholdValue>,
+    pub rejection_opt: Option<ThresholdValue>,
+    pub win_opt: Option<ThresholdValue>,
+    pub next_round_opt: Option<ThresholdValue>,
+}
+
+// the shape of round settings, as they are stored by unions from before execution delays
+#[derive(CandidType, Deserialize)]
+pub struct LegacyRoundSettings {
+    pub round_duration: u64,
+    pub round_delay: u64,
+}
+
+impl From<LegacyRoundSettings> for RoundSettings {
+    fn from(it: LegacyRoundSettings) -> Self {
+        Self {
+            round_duration: it.round_duration,
+            round_delay: it.round_delay,
+            execution_delay: 0,
+            anti_sniping: None,
+            early_finish: false,
+        }
+    }
+}
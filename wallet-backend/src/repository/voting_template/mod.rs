@@ -0,0 +1,93 @@
+use crate::repository::voting_template::model::VotingTemplate;
+use crate::repository::voting_template::types::{VotingTemplateFilter, VotingTemplateId};
+use candid::{CandidType, Deserialize};
+use shared::mvc::{IdGenerator, Model, Repository};
+use shared::pageable::{Page, PageRequest, Pageable};
+use shared::types::wallet::VotingConfigId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub mod model;
+pub mod types;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct VotingTemplateRepository {
+    voting_templates: HashMap<VotingTemplateId, VotingTemplate>,
+    id_gen: IdGenerator,
+
+    voting_templates_by_voting_config_index: BTreeMap<VotingConfigId, BTreeSet<VotingTemplateId>>,
+}
+
+impl Repository<VotingTemplate, VotingTemplateId, VotingTemplateFilter, ()>
+    for VotingTemplateRepository
+{
+    fn save(&mut self, mut it: VotingTemplate) -> VotingTemplateId {
+        if it.is_transient() {
+            it._init_id(self.id_gen.generate());
+        }
+
+        let id = it.get_id().unwrap();
+
+        self.voting_templates_by_voting_config_index
+            .entry(*it.get_voting_config_id())
+            .or_default()
+            .insert(id);
+
+        self.voting_templates.insert(id, it);
+
+        id
+    }
+
+    fn delete(&mut self, id: &VotingTemplateId) -> Option<VotingTemplate> {
+        let it = self.voting_templates.remove(id)?;
+
+        if let Some(index) = self
+            .voting_templates_by_voting_config_index
+            .get_mut(it.get_voting_config_id())
+        {
+            index.remove(id);
+        }
+
+        Some(it)
+    }
+
+    fn get(&self, id: &VotingTemplateId) -> Option<VotingTemplate> {
+        self.voting_templates.get(id).cloned()
+    }
+
+    fn list(&self, page_req: &PageRequest<VotingTemplateFilter, ()>) -> Page<VotingTemplate> {
+        if let Some(voting_config_id) = page_req.filter.voting_config {
+            let index = self
+                .voting_templates_by_voting_config_index
+                .get(&voting_config_id)
+                .cloned()
+                .unwrap_or_default();
+
+            let (has_next, iter) = index.iter().get_page(page_req);
+            let data = iter.map(|id| self.get(id).unwrap()).collect();
+
+            Page::new(data, has_next)
+        } else {
+            // if no filter set - return all
+            let (has_next, iter) = self.voting_templates.iter().get_page(page_req);
+            let data = iter.map(|(_, it)| it.clone()).collect();
+
+            Page::new(data, has_next)
+        }
+    }
+}
+
+impl VotingTemplateRepository {
+    pub fn voting_config_has_related_voting_templates(
+        &self,
+        voting_config_id: &VotingConfigId,
+    ) -> bool {
+        if let Some(index) = self
+            .voting_templates_by_voting_config_index
+            .get(voting_config_id)
+        {
+            !index.is_empty()
+        } else {
+            false
+        }
+    }
+}
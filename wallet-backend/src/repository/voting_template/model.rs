@@ -0,0 +1,221 @@
+use crate::repository::voting_template::types::{
+    replace_placeholders, TemplateChoice, TemplateParamKind, VotingTemplateId,
+    VOTING_TEMPLATE_CHOICES_MAX_LEN, VOTING_TEMPLATE_CHOICES_MIN_LEN,
+    VOTING_TEMPLATE_DESCRIPTION_MAX_LEN, VOTING_TEMPLATE_DESCRIPTION_MIN_LEN,
+    VOTING_TEMPLATE_NAME_MAX_LEN, VOTING_TEMPLATE_NAME_MIN_LEN, VOTING_TEMPLATE_PARAMS_MAX_LEN,
+    VOTING_TEMPLATE_PARAM_NAME_MAX_LEN, VOTING_TEMPLATE_PARAM_NAME_MIN_LEN,
+};
+use candid::{CandidType, Deserialize};
+use shared::mvc::Model;
+use shared::remote_call::{Program, RemoteCallArgs};
+use shared::types::wallet::VotingConfigId;
+use shared::validation::{validate_and_trim_str, ValidationError};
+use std::collections::BTreeMap;
+
+// a voting with its choices, which names, descriptions and candid string program arguments
+// may contain "{{param}}" placeholders - they are filled in, when a voting is created from the template
+#[derive(Clone, CandidType, Deserialize)]
+pub struct VotingTemplate {
+    id: Option<VotingTemplateId>,
+    voting_config_id: VotingConfigId,
+    name: String,
+    description: String,
+    winners_need: u32,
+    params: BTreeMap<String, TemplateParamKind>,
+    choices: Vec<TemplateChoice>,
+}
+
+impl VotingTemplate {
+    pub fn new(
+        voting_config_id: VotingConfigId,
+        name: String,
+        description: String,
+        winners_need: u32,
+        params: BTreeMap<String, TemplateParamKind>,
+        choices: Vec<TemplateChoice>,
+    ) -> Result<Self, ValidationError> {
+        let it = Self {
+            id: None,
+            voting_config_id,
+            name: Self::process_name(name)?,
+            description: Self::process_description(description)?,
+            winners_need: Self::process_winners_need(winners_need)?,
+            params: Self::process_params(params)?,
+            choices: Self::process_choices(choices)?,
+        };
+
+        it.assert_placeholders_are_declared()?;
+
+        Ok(it)
+    }
+
+    pub fn update(
+        &mut self,
+        name_opt: Option<String>,
+        description_opt: Option<String>,
+        winners_need_opt: Option<u32>,
+        params_opt: Option<BTreeMap<String, TemplateParamKind>>,
+        choices_opt: Option<Vec<TemplateChoice>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(name) = name_opt {
+            self.name = Self::process_name(name)?;
+        }
+
+        if let Some(description) = description_opt {
+            self.description = Self::process_description(description)?;
+        }
+
+        if let Some(winners_need) = winners_need_opt {
+            self.winners_need = Self::process_winners_need(winners_need)?;
+        }
+
+        if let Some(params) = params_opt {
+            self.params = Self::process_params(params)?;
+        }
+
+        if let Some(choices) = choices_opt {
+            self.choices = Self::process_choices(choices)?;
+        }
+
+        self.assert_placeholders_are_declared()
+    }
+
+    pub fn get_voting_config_id(&self) -> &VotingConfigId {
+        &self.voting_config_id
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+
+    pub fn get_winners_need(&self) -> u32 {
+        self.winners_need
+    }
+
+    pub fn get_params(&self) -> &BTreeMap<String, TemplateParamKind> {
+        &self.params
+    }
+
+    pub fn get_choices(&self) -> &Vec<TemplateChoice> {
+        &self.choices
+    }
+
+    fn assert_placeholders_are_declared(&self) -> Result<(), ValidationError> {
+        let mut strings = vec![&self.name, &self.description];
+
+        for choice in &self.choices {
+            strings.push(&choice.name);
+            strings.push(&choice.description);
+
+            if let Program::RemoteCallSequence(seq) = &choice.program {
+                for call in seq {
+                    if let RemoteCallArgs::CandidString(str_args) = &call.args {
+                        strings.extend(str_args.iter());
+                    }
+                }
+            }
+        }
+
+        for string in strings {
+            replace_placeholders(string, |param| {
+                if self.params.contains_key(param) {
+                    Ok(String::new())
+                } else {
+                    Err(ValidationError(format!(
+                        "Placeholder {} is not a declared template param",
+                        param
+                    )))
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn process_params(
+        params: BTreeMap<String, TemplateParamKind>,
+    ) -> Result<BTreeMap<String, TemplateParamKind>, ValidationError> {
+        if params.len() > VOTING_TEMPLATE_PARAMS_MAX_LEN {
+            return Err(ValidationError(format!(
+                "Voting template can't have more than {} params",
+                VOTING_TEMPLATE_PARAMS_MAX_LEN
+            )));
+        }
+
+        for param in params.keys() {
+            if param.len() < VOTING_TEMPLATE_PARAM_NAME_MIN_LEN
+                || param.len() > VOTING_TEMPLATE_PARAM_NAME_MAX_LEN
+                || !param.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(ValidationError(format!(
+                    "Invalid voting template param name {:?}",
+                    param
+                )));
+            }
+        }
+
+        Ok(params)
+    }
+
+    fn process_choices(
+        choices: Vec<TemplateChoice>,
+    ) -> Result<Vec<TemplateChoice>, ValidationError> {
+        if choices.len() < VOTING_TEMPLATE_CHOICES_MIN_LEN
+            || choices.len() > VOTING_TEMPLATE_CHOICES_MAX_LEN
+        {
+            return Err(ValidationError(format!(
+                "Voting template should have from {} to {} choices",
+                VOTING_TEMPLATE_CHOICES_MIN_LEN, VOTING_TEMPLATE_CHOICES_MAX_LEN
+            )));
+        }
+
+        Ok(choices)
+    }
+
+    fn process_winners_need(winners_need: u32) -> Result<u32, ValidationError> {
+        if winners_need < 1 {
+            return Err(ValidationError(
+                "There should be at least 1 winner in any voting".to_string(),
+            ));
+        }
+
+        Ok(winners_need)
+    }
+
+    fn process_name(name: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            name,
+            VOTING_TEMPLATE_NAME_MIN_LEN,
+            VOTING_TEMPLATE_NAME_MAX_LEN,
+            "Voting template name",
+        )
+    }
+
+    fn process_description(description: String) -> Result<String, ValidationError> {
+        validate_and_trim_str(
+            description,
+            VOTING_TEMPLATE_DESCRIPTION_MIN_LEN,
+            VOTING_TEMPLATE_DESCRIPTION_MAX_LEN,
+            "Voting template description",
+        )
+    }
+}
+
+impl Model<VotingTemplateId> for VotingTemplate {
+    fn get_id(&self) -> Option<VotingTemplateId> {
+        self.id
+    }
+
+    fn _init_id(&mut self, id: VotingTemplateId) {
+        assert!(self.is_transient());
+        self.id = Some(id);
+    }
+
+    fn is_transient(&self) -> bool {
+        self.id.is_none()
+    }
+}
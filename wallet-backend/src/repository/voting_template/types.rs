@@ -0,0 +1,112 @@
+use candid::parser::value::IDLValue;
+use candid::{CandidType, Deserialize, Int, Nat, Principal};
+use shared::mvc::Id;
+use shared::remote_call::Program;
+use shared::types::wallet::VotingConfigId;
+
+pub const VOTING_TEMPLATE_NAME_MIN_LEN: usize = 1;
+pub const VOTING_TEMPLATE_NAME_MAX_LEN: usize = 200;
+pub const VOTING_TEMPLATE_DESCRIPTION_MIN_LEN: usize = 0;
+pub const VOTING_TEMPLATE_DESCRIPTION_MAX_LEN: usize = 2000;
+pub const VOTING_TEMPLATE_PARAM_NAME_MIN_LEN: usize = 1;
+pub const VOTING_TEMPLATE_PARAM_NAME_MAX_LEN: usize = 50;
+pub const VOTING_TEMPLATE_PARAMS_MAX_LEN: usize = 20;
+pub const VOTING_TEMPLATE_CHOICES_MIN_LEN: usize = 1;
+pub const VOTING_TEMPLATE_CHOICES_MAX_LEN: usize = 20;
+
+const PLACEHOLDER_START: &str = "{{";
+const PLACEHOLDER_END: &str = "}}";
+
+pub type VotingTemplateId = Id;
+
+#[derive(CandidType, Deserialize)]
+pub struct VotingTemplateFilter {
+    pub voting_config: Option<VotingConfigId>,
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TemplateChoice {
+    pub name: String,
+    pub description: String,
+    pub program: Program,
+}
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateParamKind {
+    Principal,
+    Nat,
+    Int,
+    Text,
+    Bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum TemplateParamValue {
+    Principal(Principal),
+    Nat(Nat),
+    Int(Int),
+    Text(String),
+    Bool(bool),
+}
+
+impl TemplateParamValue {
+    pub fn get_kind(&self) -> TemplateParamKind {
+        match self {
+            TemplateParamValue::Principal(_) => TemplateParamKind::Principal,
+            TemplateParamValue::Nat(_) => TemplateParamKind::Nat,
+            TemplateParamValue::Int(_) => TemplateParamKind::Int,
+            TemplateParamValue::Text(_) => TemplateParamKind::Text,
+            TemplateParamValue::Bool(_) => TemplateParamKind::Bool,
+        }
+    }
+
+    // for names and descriptions
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            TemplateParamValue::Principal(it) => it.to_text(),
+            TemplateParamValue::Nat(it) => it.to_string(),
+            TemplateParamValue::Int(it) => it.to_string(),
+            TemplateParamValue::Text(it) => it.clone(),
+            TemplateParamValue::Bool(it) => it.to_string(),
+        }
+    }
+
+    // for candid string arguments of programs - escaped and annotated with its type
+    pub fn to_candid_string(&self) -> String {
+        let value = match self {
+            TemplateParamValue::Principal(it) => IDLValue::Principal(*it),
+            TemplateParamValue::Nat(it) => IDLValue::Nat(it.clone()),
+            TemplateParamValue::Int(it) => IDLValue::Int(it.clone()),
+            TemplateParamValue::Text(it) => IDLValue::Text(it.clone()),
+            TemplateParamValue::Bool(it) => IDLValue::Bool(*it),
+        };
+
+        format!("({:?})", value)
+    }
+}
+
+// replaces every "{{name}}" placeholder of the string with the result of "f(name)"
+pub fn replace_placeholders<E>(
+    string: &str,
+    mut f: impl FnMut(&str) -> Result<String, E>,
+) -> Result<String, E> {
+    let mut result = String::new();
+    let mut rest = string;
+
+    while let Some(start) = rest.find(PLACEHOLDER_START) {
+        let after_start = &rest[start + PLACEHOLDER_START.len()..];
+
+        if let Some(end) = after_start.find(PLACEHOLDER_END) {
+            result.push_str(&rest[..start]);
+            result.push_str(&f(after_start[..end].trim())?);
+
+            rest = &after_start[end + PLACEHOLDER_END.len()..];
+        } else {
+            break;
+        }
+    }
+
+    result.push_str(rest);
+
+    Ok(result)
+}
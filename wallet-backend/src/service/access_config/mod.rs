@@ -1,3 +1,4 @@
+use crate::certification::Certification;
 use crate::repository::access_config::model::AccessConfig;
 use crate::repository::access_config::types::{AlloweeConstraint, GroupCondition};
 use crate::repository::access_config::AccessConfigRepository;
@@ -10,8 +11,8 @@ use crate::service::access_config::types::{
 };
 use crate::service::group::types::{GroupService, HAS_PROFILE_GROUP_ID};
 use crate::service::permission::types::{
-    ALLOW_READ_PERMISSION_ID, ALLOW_SEND_FEEDBACK_PERMISSION_ID, ALLOW_VOTE_PERMISSION_ID,
-    ALLOW_WRITE_PERMISSION_ID,
+    ALLOW_COMMENT_PERMISSION_ID, ALLOW_MODERATE_COMMENTS_PERMISSION_ID, ALLOW_READ_PERMISSION_ID,
+    ALLOW_SEND_FEEDBACK_PERMISSION_ID, ALLOW_VOTE_PERMISSION_ID, ALLOW_WRITE_PERMISSION_ID,
 };
 use crate::{EventsService, PermissionService};
 use candid::Principal;
@@ -28,8 +29,8 @@ impl AccessConfigService {
     pub fn init_default_access_configs(wallet_creator_profile_id: Principal) {
         let allow_vote_access_config_id = AccessConfigService::create_access_config(
             String::from("Voting access"),
-            String::from("Non-deletable default access config. Allows 'Has profile' group members to call to any voting related method of this digital organization."),
-            vec![ALLOW_VOTE_PERMISSION_ID].into_iter().collect(),
+            String::from("Non-deletable default access config. Allows 'Has profile' group members to call to any voting related method of this digital organization and to comment votings."),
+            vec![ALLOW_VOTE_PERMISSION_ID, ALLOW_COMMENT_PERMISSION_ID].into_iter().collect(),
             vec![AlloweeConstraint::Group(GroupCondition { id: HAS_PROFILE_GROUP_ID, min_shares: Shares::from(1) })].into_iter().collect(),
         ).unwrap();
 
@@ -37,8 +38,8 @@ impl AccessConfigService {
 
         AccessConfigService::create_access_config(
             String::from("Unlimited access"),
-            String::from("Allows calling to any method (including query methods) of this digital organization. Be very careful editing or deleting this access config."),
-            vec![ALLOW_WRITE_PERMISSION_ID, ALLOW_READ_PERMISSION_ID].into_iter().collect(),
+            String::from("Allows calling to any method (including query methods) of this digital organization and moderating comments. Be very careful editing or deleting this access config."),
+            vec![ALLOW_WRITE_PERMISSION_ID, ALLOW_READ_PERMISSION_ID, ALLOW_MODERATE_COMMENTS_PERMISSION_ID].into_iter().collect(),
             vec![AlloweeConstraint::Profile(wallet_creator_profile_id)].into_iter().collect(),
         ).unwrap();
 
@@ -50,6 +51,35 @@ impl AccessConfigService {
         ).unwrap();
     }
 
+    // gives comment permissions, created on upgrade, to the same access configs as init does
+    pub fn grant_comment_permissions(
+        comment_permission_id: PermissionId,
+        moderate_comments_permission_id: PermissionId,
+    ) {
+        let mut allow_vote_ac = AccessConfig::repo()
+            .get(&ALLOW_VOTE_ACCESS_CONFIG_ID)
+            .unwrap();
+        let mut permissions = allow_vote_ac.get_permissions().clone();
+        permissions.insert(comment_permission_id);
+
+        allow_vote_ac
+            .update(None, None, Some(permissions), None)
+            .unwrap();
+        AccessConfig::repo().save(allow_vote_ac);
+
+        let unlimited_ac_ids =
+            AccessConfig::repo().get_access_configs_by_permission(&ALLOW_WRITE_PERMISSION_ID);
+
+        for id in unlimited_ac_ids {
+            let mut ac = AccessConfig::repo().get(&id).unwrap();
+            let mut permissions = ac.get_permissions().clone();
+            permissions.insert(moderate_comments_permission_id);
+
+            ac.update(None, None, Some(permissions), None).unwrap();
+            AccessConfig::repo().save(ac);
+        }
+    }
+
     pub async fn execute(
         id: &AccessConfigId,
         program: Program,
@@ -105,7 +135,7 @@ impl AccessConfigService {
         false
     }
 
-    fn caller_has_access_to_target(target: &PermissionTarget, caller: Principal) -> bool {
+    pub fn caller_has_access_to_target(target: &PermissionTarget, caller: Principal) -> bool {
         let mut permission_ids = Permission::repo().get_permissions_by_target(target);
 
         for permission_id in permission_ids {
@@ -122,12 +152,12 @@ impl AccessConfigService {
         false
     }
 
-    pub fn get_query_delegation_proof(
+    pub fn issue_query_delegation_proof(
         this_union_id: Principal,
         delegate_id: Principal,
         requested_targets: BTreeSet<PermissionTarget>,
         timestamp: u64,
-    ) -> QueryDelegationProof {
+    ) {
         let groups_of_delegate = GroupService::get_groups_of(&delegate_id);
         let mut allowed_query_targets = Vec::new();
 
@@ -137,15 +167,24 @@ impl AccessConfigService {
             }
         }
 
-        // TODO: implement subnet signature
-        QueryDelegationProof {
+        let proof = QueryDelegationProof {
             union_id: this_union_id,
             delegate_id,
             allowed_query_targets,
             // TODO: make expiration timeout configurable
             expires_at: timestamp + days(1),
-            signature: (),
-        }
+            signature: None,
+        };
+
+        Certification::with(|it| it.certify_query_delegation_proof(proof));
+    }
+
+    // should be called with a query call, otherwise the proof won't be signed
+    pub fn get_query_delegation_proof(
+        delegate_id: &Principal,
+    ) -> Result<QueryDelegationProof, AccessConfigError> {
+        Certification::with(|it| it.get_query_delegation_proof(delegate_id))
+            .ok_or(AccessConfigError::QueryDelegationProofNotIssued)
     }
 
     fn assert_caller_allowed(
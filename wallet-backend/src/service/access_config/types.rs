@@ -1,7 +1,9 @@
 use crate::repository::permission::types::{PermissionId, PermissionTarget};
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Encode, Principal};
 use ic_cdk::api::time;
 use ic_cdk::caller;
+use sha2::{Digest, Sha256};
+use shared::certification::{CertifiedSignature, Hash};
 use shared::types::wallet::{AccessConfigId, GroupId, ProfileId};
 use shared::validation::ValidationError;
 use std::collections::BTreeSet;
@@ -20,26 +22,39 @@ pub enum AccessConfigError {
     UnableToEditDefaultAccessConfig,
     CallerNotAllowed,
     ProgramNotAllowedByAccessConfig,
+    QueryDelegationProofNotIssued,
 }
 
-#[derive(CandidType, Deserialize)]
+#[derive(CandidType, Deserialize, Clone)]
 pub struct QueryDelegationProof {
     pub union_id: Principal,
     pub delegate_id: Principal,
     pub allowed_query_targets: Vec<PermissionTarget>,
     pub expires_at: u64,
-    // TODO: implement subnet signature
-    pub signature: (),
+    // is only present, when the proof is fetched from the issuing union with a query call
+    pub signature: Option<CertifiedSignature>,
 }
 
 impl QueryDelegationProof {
+    // the signature itself is not a part of the hash
+    pub fn hash(&self) -> Hash {
+        let encoded = Encode!(
+            &self.union_id,
+            &self.delegate_id,
+            &self.allowed_query_targets,
+            &self.expires_at
+        )
+        .expect("Unable to encode query delegation proof");
+
+        Sha256::digest(encoded).into()
+    }
+
     pub fn validate(
         &self,
         caller: Principal,
         timestamp: u64,
         targets: &Vec<PermissionTarget>,
     ) -> Result<(), ValidationError> {
-        // TODO: implement signature validation
         if self.expires_at < timestamp {
             return Err(ValidationError(format!(
                 "Query delegation proof is expired"
@@ -1,9 +1,12 @@
 use crate::repository::choice::model::Choice;
 use crate::repository::choice::types::ChoiceFilter;
+use crate::repository::comment::types::CommentTarget;
 use crate::repository::nested_voting::types::RemoteVotingId;
 use crate::repository::token::model::Token;
 use crate::repository::voting::model::Voting;
 use crate::service::choice::types::{ChoiceError, ChoiceService};
+use crate::service::choice_amendment::types::ChoiceAmendmentService;
+use crate::service::comment::types::CommentService;
 use crate::service::voting::types::VotingService;
 use crate::service::voting_config::types::VotingConfigService;
 use shared::mvc::{HasRepository, Repository};
@@ -26,7 +29,7 @@ impl ChoiceService {
         };
 
         let mut voting = VotingService::get_voting(&voting_id).map_err(ChoiceError::VotingError)?;
-        let vc = VotingConfigService::get_voting_config(voting.get_voting_config_id()).unwrap();
+        let vc = voting.get_voting_config().clone();
 
         if !VotingService::is_editable(&voting) {
             return Err(ChoiceError::UnableToEditVoting(voting_id));
@@ -64,6 +67,14 @@ impl ChoiceService {
         (rejection_choice_id, approval_choice_id)
     }
 
+    pub fn create_abstain_choice(voting_id: VotingId) -> ChoiceId {
+        Choice::repo().save(Choice::new_abstain(voting_id))
+    }
+
+    pub fn create_veto_choice(voting_id: VotingId) -> ChoiceId {
+        Choice::repo().save(Choice::new_veto(voting_id))
+    }
+
     pub fn update_choice(
         choice_id: &ChoiceId,
         new_name: Option<String>,
@@ -77,7 +88,7 @@ impl ChoiceService {
         };
 
         let voting = VotingService::get_voting(&voting_id).unwrap();
-        let vc = VotingConfigService::get_voting_config(voting.get_voting_config_id()).unwrap();
+        let vc = voting.get_voting_config().clone();
 
         if !VotingService::is_editable(&voting) {
             return Err(ChoiceError::UnableToEditVoting(voting_id));
@@ -129,11 +140,21 @@ impl ChoiceService {
             }
         };
 
-        for (_, token_id) in choice.list_tokens_by_group() {
+        for token_id in choice.list_tokens_by_group().values() {
+            Token::repo().delete(token_id).unwrap();
+        }
+
+        for token_id in choice.list_spent_tokens_by_group().values() {
+            Token::repo().delete(token_id).unwrap();
+        }
+
+        for token_id in choice.list_raw_tokens_by_group().values() {
             Token::repo().delete(token_id).unwrap();
         }
 
         Choice::repo().delete(choice_id).unwrap();
+        CommentService::delete_comments_of(&CommentTarget::Choice(*choice_id));
+        ChoiceAmendmentService::delete_amendments_of(choice_id);
 
         Ok(())
     }
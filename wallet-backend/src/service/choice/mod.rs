@@ -43,6 +43,57 @@ impl ChoiceService {
         Token::repo().get(&token_id).unwrap()
     }
 
+    // only created for quadratic votings, where voted shares differ from the spent ones
+    pub fn get_spent_token_for_group(choice: &mut Choice, group_id: GroupId) -> Token {
+        let token_id = if let Some(token_id) = choice.get_spent_shares_by_group_token(&group_id) {
+            *token_id
+        } else {
+            let token_id = TokenService::create_token(
+                ChoiceOrGroup::Choice(choice.get_id().unwrap(), group_id),
+                false,
+                false,
+            );
+            choice.set_spent_shares_by_group_token(group_id, token_id);
+
+            token_id
+        };
+
+        Token::repo().get(&token_id).unwrap()
+    }
+
+    // only created for votings with a voting power cap, where voted shares may be less than the raw ones
+    pub fn get_raw_token_for_group(choice: &mut Choice, group_id: GroupId) -> Token {
+        let token_id = if let Some(token_id) = choice.get_raw_shares_by_group_token(&group_id) {
+            *token_id
+        } else {
+            let token_id = TokenService::create_token(
+                ChoiceOrGroup::Choice(choice.get_id().unwrap(), group_id),
+                false,
+                false,
+            );
+            choice.set_raw_shares_by_group_token(group_id, token_id);
+
+            token_id
+        };
+
+        Token::repo().get(&token_id).unwrap()
+    }
+
+    pub fn list_total_spent_shares_by_group(choice: &Choice) -> BTreeMap<GroupId, Shares> {
+        choice
+            .list_tokens_by_group()
+            .iter()
+            .map(|(gop, token_id)| {
+                let token_id = choice
+                    .get_spent_shares_by_group_token(gop)
+                    .unwrap_or(token_id);
+                let token = Token::repo().get(token_id).unwrap();
+
+                (*gop, token.total_supply())
+            })
+            .collect()
+    }
+
     pub fn list_total_voted_shares_by_group(choice: &Choice) -> BTreeMap<GroupId, Shares> {
         choice
             .list_tokens_by_group()
@@ -60,6 +111,22 @@ impl ChoiceService {
         Token::repo().add_to_principal_index(voter, token.get_id().unwrap());
     }
 
+    pub fn revert_spent_shares(choice: &Choice, group_id: &GroupId, voter: Principal) {
+        if let Some(token_id) = choice.get_spent_shares_by_group_token(group_id) {
+            let mut token = Token::repo().get(token_id).unwrap();
+            ChoiceService::revert_vote(&mut token, voter);
+            Token::repo().save(token);
+        }
+    }
+
+    pub fn revert_raw_shares(choice: &Choice, group_id: &GroupId, voter: Principal) {
+        if let Some(token_id) = choice.get_raw_shares_by_group_token(group_id) {
+            let mut token = Token::repo().get(token_id).unwrap();
+            ChoiceService::revert_vote(&mut token, voter);
+            Token::repo().save(token);
+        }
+    }
+
     pub fn revert_vote(token: &mut Token, voter: Principal) {
         let shares = token.balance_of(&voter);
         token.burn(voter, shares).unwrap();
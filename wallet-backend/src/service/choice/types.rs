@@ -18,7 +18,19 @@ pub struct ChoiceService;
 
 impl ChoiceService {
     pub fn reset(choice: &Choice) {
-        for (_, token_id) in choice.list_tokens_by_group() {
+        for token_id in choice.list_tokens_by_group().values() {
+            let mut token = Token::repo().get(token_id).unwrap();
+            token.reset();
+            Token::repo().save(token);
+        }
+
+        for token_id in choice.list_spent_tokens_by_group().values() {
+            let mut token = Token::repo().get(token_id).unwrap();
+            token.reset();
+            Token::repo().save(token);
+        }
+
+        for token_id in choice.list_raw_tokens_by_group().values() {
             let mut token = Token::repo().get(token_id).unwrap();
             token.reset();
             Token::repo().save(token);
@@ -0,0 +1,174 @@
+use crate::repository::choice::model::Choice;
+use crate::repository::choice_amendment::model::ChoiceAmendment;
+use crate::repository::choice_amendment::types::{
+    ChoiceAmendmentFilter, ChoiceAmendmentId, ChoiceAmendmentStatus,
+};
+use crate::repository::nested_voting::types::RemoteVotingId;
+use crate::repository::voting::model::Voting;
+use crate::service::choice::types::ChoiceService;
+use crate::service::choice_amendment::types::{ChoiceAmendmentError, ChoiceAmendmentService};
+use crate::service::voting::types::VotingService;
+use crate::service::voting_config::types::VotingConfigService;
+use candid::Principal;
+use ic_cdk::id;
+use shared::mvc::{HasRepository, Repository};
+use shared::pageable::{Page, PageRequest};
+use shared::remote_call::Program;
+use shared::types::wallet::ChoiceId;
+
+pub mod types;
+
+impl ChoiceAmendmentService {
+    pub fn propose_amendment(
+        choice_id: &ChoiceId,
+        name: String,
+        description: Option<String>,
+        program: Option<Program>,
+        author: Principal,
+        timestamp: u64,
+    ) -> Result<ChoiceAmendmentId, ChoiceAmendmentError> {
+        let choice = Choice::repo()
+            .get(choice_id)
+            .ok_or(ChoiceAmendmentError::ChoiceNotFound(*choice_id))?;
+
+        let voting_id = match choice.get_voting_id() {
+            RemoteVotingId::Common(id) => id,
+            _ => return Err(ChoiceAmendmentError::UnableToAmendChoice(*choice_id)),
+        };
+
+        let voting =
+            VotingService::get_voting(&voting_id).map_err(ChoiceAmendmentError::VotingError)?;
+
+        // rejection, approval and other service choices can't be amended
+        if !voting.get_choices().contains(choice_id) {
+            return Err(ChoiceAmendmentError::UnableToAmendChoice(*choice_id));
+        }
+
+        if !VotingService::is_editable(&voting) {
+            return Err(ChoiceAmendmentError::UnableToEditVoting(voting_id));
+        }
+
+        if let Some(program) = &program {
+            program
+                .validate()
+                .map_err(ChoiceAmendmentError::ValidationError)?;
+
+            if !VotingConfigService::does_program_fit(voting.get_voting_config(), program) {
+                return Err(ChoiceAmendmentError::ProgramNotAllowedByVotingConfig);
+            }
+        }
+
+        let it = ChoiceAmendment::new(
+            voting_id,
+            *choice_id,
+            author,
+            name,
+            description,
+            program,
+            timestamp,
+        )
+        .map_err(ChoiceAmendmentError::ValidationError)?;
+
+        Ok(ChoiceAmendment::repo().save(it))
+    }
+
+    // the accepted amendment becomes a new choice, the original choice stays as is
+    pub fn accept_amendment(
+        id: &ChoiceAmendmentId,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<ChoiceId, ChoiceAmendmentError> {
+        let mut it = ChoiceAmendmentService::get_proposed_amendment(id)?;
+        let voting = VotingService::get_voting(&it.get_voting_id())
+            .map_err(ChoiceAmendmentError::VotingError)?;
+
+        ChoiceAmendmentService::assert_caller_can_decide(&voting, caller)?;
+
+        let original = Choice::repo()
+            .get(&it.get_choice_id())
+            .ok_or_else(|| ChoiceAmendmentError::ChoiceNotFound(it.get_choice_id()))?;
+
+        // also resets the approval votes, since the voting has changed
+        let new_choice_id = ChoiceService::create_choice(
+            it.get_name().clone(),
+            it.get_description()
+                .clone()
+                .unwrap_or_else(|| original.get_description().clone()),
+            it.get_program()
+                .clone()
+                .unwrap_or_else(|| original.get_program().clone()),
+            RemoteVotingId::Common(it.get_voting_id()),
+            timestamp,
+        )
+        .map_err(ChoiceAmendmentError::ChoiceError)?;
+
+        let mut new_choice = Choice::repo().get(&new_choice_id).unwrap();
+        new_choice.set_amendment_of(it.get_choice_id());
+        Choice::repo().save(new_choice);
+
+        it.accept(new_choice_id, timestamp);
+        ChoiceAmendment::repo().save(it);
+
+        Ok(new_choice_id)
+    }
+
+    pub fn reject_amendment(
+        id: &ChoiceAmendmentId,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), ChoiceAmendmentError> {
+        let mut it = ChoiceAmendmentService::get_proposed_amendment(id)?;
+        let voting = VotingService::get_voting(&it.get_voting_id())
+            .map_err(ChoiceAmendmentError::VotingError)?;
+
+        ChoiceAmendmentService::assert_caller_can_decide(&voting, caller)?;
+
+        it.reject(timestamp);
+        ChoiceAmendment::repo().save(it);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn get_amendment(id: &ChoiceAmendmentId) -> Result<ChoiceAmendment, ChoiceAmendmentError> {
+        ChoiceAmendment::repo()
+            .get(id)
+            .ok_or(ChoiceAmendmentError::ChoiceAmendmentNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn list_amendments(
+        page_req: &PageRequest<ChoiceAmendmentFilter, ()>,
+    ) -> Page<ChoiceAmendment> {
+        ChoiceAmendment::repo().list(page_req)
+    }
+
+    pub fn delete_amendments_of(choice_id: &ChoiceId) {
+        for id in ChoiceAmendment::repo().list_ids_of_choice(choice_id) {
+            ChoiceAmendment::repo().delete(&id);
+        }
+    }
+
+    fn get_proposed_amendment(
+        id: &ChoiceAmendmentId,
+    ) -> Result<ChoiceAmendment, ChoiceAmendmentError> {
+        let it = ChoiceAmendmentService::get_amendment(id)?;
+
+        match it.get_status() {
+            ChoiceAmendmentStatus::Proposed => Ok(it),
+            _ => Err(ChoiceAmendmentError::ChoiceAmendmentIsNotProposed(*id)),
+        }
+    }
+
+    // either the proposer, or the union itself (e.g. as a result of an approval voting)
+    fn assert_caller_can_decide(
+        voting: &Voting,
+        caller: Principal,
+    ) -> Result<(), ChoiceAmendmentError> {
+        if caller == voting.get_proposer() || caller == id() {
+            Ok(())
+        } else {
+            Err(ChoiceAmendmentError::CallerIsNotProposer)
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::repository::choice_amendment::types::ChoiceAmendmentId;
+use crate::service::choice::types::ChoiceError;
+use crate::service::voting::types::VotingError;
+use shared::types::wallet::{ChoiceId, VotingId};
+use shared::validation::ValidationError;
+
+pub struct ChoiceAmendmentService;
+
+#[derive(Debug)]
+pub enum ChoiceAmendmentError {
+    ValidationError(ValidationError),
+    ChoiceAmendmentNotFound(ChoiceAmendmentId),
+    ChoiceNotFound(ChoiceId),
+    UnableToAmendChoice(ChoiceId),
+    UnableToEditVoting(VotingId),
+    ProgramNotAllowedByVotingConfig,
+    ChoiceAmendmentIsNotProposed(ChoiceAmendmentId),
+    CallerIsNotProposer,
+    VotingError(VotingError),
+    ChoiceError(ChoiceError),
+}
@@ -0,0 +1,99 @@
+use crate::repository::comment::model::Comment;
+use crate::repository::comment::types::{CommentFilter, CommentId, CommentTarget};
+use crate::repository::permission::types::PermissionTarget;
+use crate::service::comment::types::{CommentError, CommentService};
+use candid::Principal;
+use shared::mvc::{HasRepository, Repository};
+use shared::pageable::{Page, PageRequest};
+
+impl CommentService {
+    pub fn create_comment(
+        target: CommentTarget,
+        reply_to: Option<CommentId>,
+        text: String,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<CommentId, CommentError> {
+        CommentService::assert_caller_has_access(&PermissionTarget::Comment, caller)?;
+        CommentService::assert_target_exists(&target)?;
+
+        if let Some(parent_id) = reply_to {
+            let parent = CommentService::get_comment(&parent_id)?;
+
+            if *parent.get_target() != target {
+                return Err(CommentError::ReplyTargetMismatch(parent_id));
+            }
+
+            if parent.is_deleted() {
+                return Err(CommentError::CommentIsDeleted(parent_id));
+            }
+        }
+
+        let it = Comment::new(target, reply_to, caller, text, timestamp)
+            .map_err(CommentError::ValidationError)?;
+
+        Ok(Comment::repo().save(it))
+    }
+
+    pub fn update_comment(
+        id: &CommentId,
+        new_text: String,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), CommentError> {
+        CommentService::assert_caller_has_access(&PermissionTarget::Comment, caller)?;
+
+        let mut it = CommentService::get_comment(id)?;
+
+        if it.get_author() != caller {
+            return Err(CommentError::CallerIsNotAuthor);
+        }
+
+        if it.is_deleted() {
+            return Err(CommentError::CommentIsDeleted(*id));
+        }
+
+        it.update(new_text, timestamp)
+            .map_err(CommentError::ValidationError)?;
+
+        Comment::repo().save(it);
+
+        Ok(())
+    }
+
+    // authors can delete their own comments, moderators - any comment
+    pub fn delete_comment(
+        id: &CommentId,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), CommentError> {
+        let mut it = CommentService::get_comment(id)?;
+
+        if it.get_author() == caller {
+            CommentService::assert_caller_has_access(&PermissionTarget::Comment, caller)?;
+        } else {
+            CommentService::assert_caller_has_access(&PermissionTarget::ModerateComments, caller)?;
+        }
+
+        if it.is_deleted() {
+            return Err(CommentError::CommentIsDeleted(*id));
+        }
+
+        it.delete(caller, timestamp);
+        Comment::repo().save(it);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn get_comment(id: &CommentId) -> Result<Comment, CommentError> {
+        Comment::repo()
+            .get(id)
+            .ok_or(CommentError::CommentNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn list_comments(page_req: &PageRequest<CommentFilter, ()>) -> Page<Comment> {
+        Comment::repo().list(page_req)
+    }
+}
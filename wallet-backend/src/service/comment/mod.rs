@@ -0,0 +1,58 @@
+use crate::repository::choice::model::Choice;
+use crate::repository::comment::model::Comment;
+use crate::repository::comment::types::CommentTarget;
+use crate::repository::permission::types::PermissionTarget;
+use crate::repository::profile::model::Profile;
+use crate::repository::voting::model::Voting;
+use crate::service::access_config::types::AccessConfigService;
+use crate::service::comment::types::{CommentError, CommentService};
+use candid::Principal;
+use ic_cdk::id;
+use shared::mvc::{HasRepository, Repository};
+use std::collections::BTreeSet;
+
+pub mod crud;
+pub mod types;
+
+impl CommentService {
+    // profiles of the authors, for those of them who have one
+    pub fn get_author_profiles(comments: &[Comment]) -> Vec<Profile> {
+        comments
+            .iter()
+            .map(|it| it.get_author())
+            .collect::<BTreeSet<_>>()
+            .iter()
+            .filter_map(|it| Profile::repo().get(it))
+            .collect()
+    }
+
+    pub fn delete_comments_of(target: &CommentTarget) {
+        for id in Comment::repo().list_ids_of_target(target) {
+            Comment::repo().delete(&id);
+        }
+    }
+
+    fn assert_caller_has_access(
+        target: &PermissionTarget,
+        caller: Principal,
+    ) -> Result<(), CommentError> {
+        if caller == id() || AccessConfigService::caller_has_access_to_target(target, caller) {
+            Ok(())
+        } else {
+            Err(CommentError::CallerNotAllowed)
+        }
+    }
+
+    fn assert_target_exists(target: &CommentTarget) -> Result<(), CommentError> {
+        let exists = match target {
+            CommentTarget::Voting(voting_id) => Voting::repo().get(voting_id).is_some(),
+            CommentTarget::Choice(choice_id) => Choice::repo().get(choice_id).is_some(),
+        };
+
+        if exists {
+            Ok(())
+        } else {
+            Err(CommentError::TargetNotFound(*target))
+        }
+    }
+}
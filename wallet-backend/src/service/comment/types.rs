@@ -0,0 +1,15 @@
+use crate::repository::comment::types::{CommentId, CommentTarget};
+use shared::validation::ValidationError;
+
+pub struct CommentService;
+
+#[derive(Debug)]
+pub enum CommentError {
+    ValidationError(ValidationError),
+    CommentNotFound(CommentId),
+    TargetNotFound(CommentTarget),
+    ReplyTargetMismatch(CommentId),
+    CommentIsDeleted(CommentId),
+    CallerNotAllowed,
+    CallerIsNotAuthor,
+}
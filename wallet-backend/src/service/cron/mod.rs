@@ -1,9 +1,10 @@
 use crate::repository::choice::model::Choice;
 use crate::repository::voting::model::Voting;
+use crate::repository::voting::types::VotingStatus;
 use crate::repository::voting_config::model::VotingConfig;
 use crate::service::events::EventsService;
 use crate::service::voting::types::VotingService;
-use crate::{cron_enqueue, cron_ready_tasks};
+use crate::{cron_dequeue, cron_enqueue, cron_ready_tasks};
 use candid::{CandidType, Deserialize};
 use ic_cdk::api::time;
 use ic_cdk::spawn;
@@ -15,6 +16,7 @@ use shared::types::wallet::{ProgramExecutedWith, VotingId};
 pub enum CronTaskKind {
     RoundStart(VotingId),
     RoundEnd(VotingId),
+    RevealEnd(VotingId),
     VotingExecution(VotingId),
 }
 
@@ -36,10 +38,12 @@ impl CronService {
     }
 
     pub fn schedule_round_end(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let round_duration = vc.get_round_settings().round_duration;
+
         let task_id = cron_enqueue(
             CronTaskKind::RoundEnd(voting.get_id().unwrap()),
             SchedulingOptions {
-                delay_nano: vc.get_round_settings().round_duration,
+                delay_nano: round_duration,
                 interval_nano: 0,
                 iterations: Iterations::Exact(1),
             },
@@ -47,13 +51,47 @@ impl CronService {
         .expect("Unable to schedule a task");
 
         voting.set_cron_task(task_id, timestamp);
+        voting.set_round_ends_at(timestamp + round_duration, timestamp);
     }
 
-    pub fn schedule_voting_execution(voting: &mut Voting, timestamp: u64) {
+    // moves the end of the current round to the new moment
+    pub fn reschedule_round_end(voting: &mut Voting, round_ends_at: u64, timestamp: u64) {
+        if let Some(task_id) = voting.get_cron_task() {
+            cron_dequeue(task_id);
+        }
+
+        let task_id = cron_enqueue(
+            CronTaskKind::RoundEnd(voting.get_id().unwrap()),
+            SchedulingOptions {
+                delay_nano: round_ends_at.saturating_sub(timestamp),
+                interval_nano: 0,
+                iterations: Iterations::Exact(1),
+            },
+        )
+        .expect("Unable to schedule a task");
+
+        voting.set_cron_task(task_id, timestamp);
+    }
+
+    pub fn schedule_reveal_end(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let task_id = cron_enqueue(
+            CronTaskKind::RevealEnd(voting.get_id().unwrap()),
+            SchedulingOptions {
+                delay_nano: vc.get_commit_reveal_settings().unwrap().reveal_duration,
+                interval_nano: 0,
+                iterations: Iterations::Exact(1),
+            },
+        )
+        .expect("Unable to schedule a task");
+
+        voting.set_cron_task(task_id, timestamp);
+    }
+
+    pub fn schedule_voting_execution(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
         let task_id = cron_enqueue(
             CronTaskKind::VotingExecution(voting.get_id().unwrap()),
             SchedulingOptions {
-                delay_nano: 0,
+                delay_nano: vc.get_round_settings().execution_delay,
                 interval_nano: 0,
                 iterations: Iterations::Exact(1),
             },
@@ -72,27 +110,66 @@ impl CronService {
             match kind {
                 CronTaskKind::RoundStart(voting_id) => {
                     let mut voting = Voting::repo().get(&voting_id).unwrap();
-                    let vc = VotingConfig::repo()
-                        .get(voting.get_voting_config_id())
-                        .unwrap();
+                    let vc = voting.get_voting_config().clone();
 
                     voting.start_round(timestamp);
                     CronService::schedule_round_end(&mut voting, &vc, timestamp);
 
+                    if let VotingStatus::Round(round) = voting.get_status() {
+                        EventsService::emit_voting_round_started_event(&voting, *round, timestamp);
+                    }
+
                     Voting::repo().save(voting);
                 }
                 CronTaskKind::RoundEnd(voting_id) => {
                     let mut voting = Voting::repo().get(&voting_id).unwrap();
-                    let vc = VotingConfig::repo()
-                        .get(voting.get_voting_config_id())
-                        .unwrap();
+                    let vc = voting.get_voting_config().clone();
+
+                    if VotingService::should_reveal(&voting, &vc) {
+                        voting.start_reveal(timestamp);
+                        CronService::schedule_reveal_end(&mut voting, &vc, timestamp);
+                    } else {
+                        VotingService::try_finish_voting(&mut voting, &vc, timestamp);
+                    }
+
+                    Voting::repo().save(voting);
+                }
+                CronTaskKind::RevealEnd(voting_id) => {
+                    let mut voting = Voting::repo().get(&voting_id).unwrap();
+                    let vc = voting.get_voting_config().clone();
 
+                    VotingService::finish_reveal(&mut voting, timestamp);
                     VotingService::try_finish_voting(&mut voting, &vc, timestamp);
 
                     Voting::repo().save(voting);
                 }
                 CronTaskKind::VotingExecution(voting_id) => spawn(async move {
-                    let voting = Voting::repo().get(&voting_id).unwrap();
+                    let mut voting = Voting::repo().get(&voting_id).unwrap();
+
+                    // the voting could have been vetoed during the execution delay
+                    if !matches!(voting.get_status(), VotingStatus::AwaitingExecution) {
+                        return;
+                    }
+
+                    // vetoes are no longer accepted, once the execution is started
+                    voting.start_execution(time());
+
+                    if VotingService::has_next_stage(&voting) {
+                        // the stage only succeeds, if the next voting was actually started
+                        match VotingService::start_next_stage(&mut voting, time()) {
+                            Ok(_) => voting.finish_execution(time()),
+                            Err(e) => voting.fail_execution(
+                                format!("Unable to start the next pipeline stage: {:?}", e),
+                                time(),
+                            ),
+                        }
+
+                        EventsService::emit_voting_finished_event(&voting, time());
+                        Voting::repo().save(voting);
+                        return;
+                    }
+
+                    Voting::repo().save(voting.clone());
 
                     for result in voting.get_winners() {
                         for choice in result
@@ -104,6 +181,16 @@ impl CronService {
                             let program = choice.get_program().clone();
                             let result = program.execute().await;
 
+                            // the voting could have been changed while the program was executing
+                            if let Some(mut voting) = Voting::repo().get(&voting_id) {
+                                voting.set_execution_result(
+                                    choice.get_id().unwrap(),
+                                    result.clone(),
+                                    time(),
+                                );
+                                Voting::repo().save(voting);
+                            }
+
                             EventsService::emit_program_executed_event(
                                 voting.get_proposer(),
                                 ProgramExecutedWith::WithVotingConfig(
@@ -115,6 +202,13 @@ impl CronService {
                             );
                         }
                     }
+
+                    // only now results of all the winning programs are stored
+                    if let Some(mut voting) = Voting::repo().get(&voting_id) {
+                        voting.finish_execution(time());
+                        EventsService::emit_voting_finished_event(&voting, time());
+                        Voting::repo().save(voting);
+                    }
                 }),
             };
         }
@@ -1,6 +1,8 @@
 use crate::repository::nested_voting::types::RemoteVotingId;
-use crate::repository::voting::types::RoundResult;
+use crate::repository::voting::types::{RoundId, RoundResult, VotingStatus};
+use candid::Principal;
 use ic_event_hub_macros::Event;
+use shared::types::wallet::{GroupId, VotingConfigId, VotingId};
 
 #[derive(Event)]
 pub struct VotingRoundStartEvent {
@@ -19,3 +21,67 @@ pub struct VotingRoundEndEvent {
 // TODO: add nested voting status
 // TODO: improve voting updated events with this status
 // TODO: refactor everything (make nested votings into normal ones, move shared/wallet to wallet client
+
+// events of local votings, so anyone could follow them without polling
+
+#[derive(Event)]
+pub struct VotingCreatedEvent {
+    #[topic]
+    pub voting_id: VotingId,
+    #[topic]
+    pub voting_config_id: VotingConfigId,
+    #[topic]
+    pub proposer: Principal,
+    pub timestamp: u64,
+}
+
+#[derive(Event)]
+pub struct VoteCastEvent {
+    #[topic]
+    pub voting_id: VotingId,
+    #[topic]
+    pub voting_config_id: VotingConfigId,
+    #[topic]
+    pub proposer: Principal,
+    pub voter: Principal,
+    pub group_id: GroupId,
+    pub timestamp: u64,
+}
+
+#[derive(Event)]
+pub struct VotingRoundStartedEvent {
+    #[topic]
+    pub voting_id: VotingId,
+    #[topic]
+    pub voting_config_id: VotingConfigId,
+    #[topic]
+    pub proposer: Principal,
+    pub round: RoundId,
+    pub timestamp: u64,
+}
+
+#[derive(Event)]
+pub struct VotingRoundEndedEvent {
+    #[topic]
+    pub voting_id: VotingId,
+    #[topic]
+    pub voting_config_id: VotingConfigId,
+    #[topic]
+    pub proposer: Principal,
+    pub round: RoundId,
+    pub status: VotingStatus,
+    pub timestamp: u64,
+}
+
+#[derive(Event)]
+pub struct VotingFinishedEvent {
+    #[topic]
+    pub voting_id: VotingId,
+    #[topic]
+    pub voting_config_id: VotingConfigId,
+    #[topic]
+    pub proposer: Principal,
+    pub status: VotingStatus,
+    pub winners: Vec<RoundResult>,
+    pub timestamp: u64,
+}
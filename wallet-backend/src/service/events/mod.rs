@@ -1,16 +1,20 @@
 use crate::emit;
 use crate::repository::nested_voting::model::NestedVoting;
 use crate::repository::nested_voting::types::RemoteVotingId;
-use crate::repository::voting::types::RoundResult;
+use crate::repository::voting::model::Voting;
+use crate::repository::voting::types::{RoundId, RoundResult, VotingStatus};
 use crate::service::events::events::{
-    VotingRoundEndEvent, VotingRoundEndEventFilter, VotingRoundStartEvent,
-    VotingRoundStartEventFilter,
+    VoteCastEvent, VotingCreatedEvent, VotingFinishedEvent, VotingRoundEndEvent,
+    VotingRoundEndEventFilter, VotingRoundEndedEvent, VotingRoundStartEvent,
+    VotingRoundStartEventFilter, VotingRoundStartedEvent,
 };
 use candid::Principal;
 use ic_cdk::print;
 use ic_cdk_macros::update;
 use ic_event_hub::api::IEventHubClient;
-use ic_event_hub::types::{CallbackInfo, Event, IEvent, IEventFilter, SubscribeRequest};
+use ic_event_hub::types::{
+    CallbackInfo, Event, EventHubError, IEvent, IEventFilter, SubscribeRequest,
+};
 use shared::candid::{CandidCallResult, CandidRejectionCode};
 use shared::mvc::{HasRepository, Model, Repository};
 use shared::remote_call::{Program, ProgramExecutionResult};
@@ -161,6 +165,80 @@ impl EventsService {
             .map_err(|(_, m)| m)
     }
 
+    pub fn emit_voting_created_event(voting: &Voting, timestamp: u64) {
+        EventsService::emit_if_listened(VotingCreatedEvent {
+            voting_id: voting.get_id().unwrap(),
+            voting_config_id: *voting.get_voting_config_id(),
+            proposer: voting.get_proposer(),
+            timestamp,
+        });
+    }
+
+    pub fn emit_vote_cast_event(
+        voting: &Voting,
+        voter: Principal,
+        group_id: GroupId,
+        timestamp: u64,
+    ) {
+        EventsService::emit_if_listened(VoteCastEvent {
+            voting_id: voting.get_id().unwrap(),
+            voting_config_id: *voting.get_voting_config_id(),
+            proposer: voting.get_proposer(),
+            voter,
+            group_id,
+            timestamp,
+        });
+    }
+
+    pub fn emit_voting_round_started_event(voting: &Voting, round: RoundId, timestamp: u64) {
+        EventsService::emit_if_listened(VotingRoundStartedEvent {
+            voting_id: voting.get_id().unwrap(),
+            voting_config_id: *voting.get_voting_config_id(),
+            proposer: voting.get_proposer(),
+            round,
+            timestamp,
+        });
+    }
+
+    // the voting is finished, once it is not possible to vote for its choices anymore
+    pub fn emit_voting_round_ended_event(voting: &Voting, round: RoundId, timestamp: u64) {
+        EventsService::emit_if_listened(VotingRoundEndedEvent {
+            voting_id: voting.get_id().unwrap(),
+            voting_config_id: *voting.get_voting_config_id(),
+            proposer: voting.get_proposer(),
+            round,
+            status: voting.get_status().clone(),
+            timestamp,
+        });
+
+        if matches!(
+            voting.get_status(),
+            VotingStatus::Rejected | VotingStatus::AwaitingExecution | VotingStatus::Fail(_)
+        ) {
+            EventsService::emit_voting_finished_event(voting, timestamp);
+        }
+    }
+
+    // emitted each time the voting reaches a final status - once it is decided, vetoed or executed
+    pub fn emit_voting_finished_event(voting: &Voting, timestamp: u64) {
+        EventsService::emit_if_listened(VotingFinishedEvent {
+            voting_id: voting.get_id().unwrap(),
+            voting_config_id: *voting.get_voting_config_id(),
+            proposer: voting.get_proposer(),
+            status: voting.get_status().clone(),
+            winners: voting.get_winners().clone(),
+            timestamp,
+        });
+    }
+
+    // voting events are optional to listen to - failing to emit one should not revert the voting
+    fn emit_if_listened(event: impl IEvent) {
+        match emit(event) {
+            Ok(_) | Err(EventHubError::EventHasNoActiveListeners) => {}
+            Err(e) => print(format!("Unable to emit an event: {:?}", e)),
+        }
+    }
+
     pub fn emit_profile_created_event(owner: Principal) {
         /*emit(ProfileCreatedEvent {
             profile_owner: owner,
@@ -227,6 +227,101 @@ impl GroupService {
         Ok(())
     }
 
+    // for the history ledger locked shares are burned, until they are unlocked
+    pub fn lock_shares(
+        group_id: GroupId,
+        owner: Principal,
+        qty: Shares,
+        timestamp: u64,
+    ) -> Result<(), GroupError> {
+        GroupService::assert_not_has_profile_group(group_id)?;
+
+        let group = GroupService::get_group(group_id)?.it;
+        let mut token = GroupService::get_token(&group);
+
+        token
+            .lock(owner, qty.clone())
+            .map_err(GroupError::ValidationError)?;
+
+        let new_balance = token.balance_of(&owner);
+        let total_supply = token.total_supply();
+
+        Token::repo().save(token);
+
+        EventsService::emit_shares_burn_event(
+            group_id,
+            owner,
+            qty,
+            new_balance,
+            total_supply,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    pub fn unlock_shares(
+        group_id: GroupId,
+        owner: Principal,
+        to: Principal,
+        qty: Shares,
+        timestamp: u64,
+    ) -> Result<(), GroupError> {
+        let group = GroupService::get_group(group_id)?.it;
+        let mut token = GroupService::get_token(&group);
+
+        let prev_to_balance = token.balance_of(&to);
+
+        token
+            .unlock(owner, to, qty.clone())
+            .map_err(GroupError::ValidationError)?;
+
+        let to_balance = token.balance_of(&to);
+        let total_supply = token.total_supply();
+
+        let zero = Shares::default();
+        if GroupService::owns_nothing(&token, &owner) {
+            Token::repo().remove_from_principal_index(&owner, &token.get_id().unwrap());
+        }
+        if prev_to_balance == zero {
+            Token::repo().add_to_principal_index(to, token.get_id().unwrap());
+        }
+
+        Token::repo().save(token);
+
+        EventsService::emit_shares_mint_event(
+            group_id,
+            to,
+            qty,
+            to_balance,
+            total_supply,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    pub fn burn_locked_shares(
+        group_id: GroupId,
+        owner: Principal,
+        qty: Shares,
+    ) -> Result<(), GroupError> {
+        let group = GroupService::get_group(group_id)?.it;
+        let mut token = GroupService::get_token(&group);
+
+        token
+            .burn_locked(owner, qty)
+            .map_err(GroupError::ValidationError)?;
+
+        if GroupService::owns_nothing(&token, &owner) {
+            Token::repo().remove_from_principal_index(&owner, &token.get_id().unwrap());
+        }
+
+        Token::repo().save(token);
+
+        Ok(())
+    }
+
     pub fn get_groups_of(caller: &Principal) -> Vec<GroupExt> {
         Token::repo()
             .get_tokens_by_principal(caller)
@@ -338,6 +433,13 @@ impl GroupService {
         Token::repo().get(&group.get_token()).unwrap()
     }
 
+    fn owns_nothing(token: &Token, owner: &Principal) -> bool {
+        token.balance_of(owner)
+            + token.unaccepted_balance_of(owner)
+            + token.locked_balance_of(owner)
+            == Shares::default()
+    }
+
     fn assert_private(group: &Group) -> Result<(), GroupError> {
         if !group.is_private() {
             Err(GroupError::GroupIsPublic(group.get_id().unwrap()))
@@ -1,4 +1,4 @@
-use candid::Principal;
+use candid::{CandidType, Deserialize, Principal};
 use shared::mvc::ZERO_ID;
 use shared::types::wallet::GroupId;
 use shared::validation::ValidationError;
@@ -8,7 +8,7 @@ pub const DEFAULT_GROUP_SHARES: u64 = 100;
 
 pub struct GroupService;
 
-#[derive(Debug)]
+#[derive(CandidType, Deserialize, Debug)]
 pub enum GroupError {
     ValidationError(ValidationError),
     GroupNotFound(GroupId),
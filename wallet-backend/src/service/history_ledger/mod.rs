@@ -1,5 +1,6 @@
+use crate::certification::Certification;
 use crate::settings::Settings;
-use candid::Principal;
+use candid::{CandidType, Deserialize, Principal};
 use history_ledger_client::api::{
     GetSharesInfoOfAtRequest, ListProgramExecutionEntryIdsRequest, ProgramExecutionFilter,
 };
@@ -9,7 +10,7 @@ use shared::pageable::{Page, PageRequest};
 use shared::types::history_ledger::SharesInfo;
 use shared::types::wallet::GroupId;
 
-#[derive(Debug)]
+#[derive(CandidType, Deserialize, Debug)]
 pub enum HistoryLedgerError {
     NetworkError(CandidRejectionCode, String),
 }
@@ -32,6 +33,30 @@ impl HistoryLedgerService {
         Ok(resp.info_opt)
     }
 
+    // certifies the fetched shares info, so it could be verified by others later
+    pub async fn issue_shares_info_of_at(
+        group_id: GroupId,
+        of: Principal,
+        at: u64,
+    ) -> Result<Option<SharesInfo>, HistoryLedgerError> {
+        let shares_info_opt = HistoryLedgerService::get_shares_info_of_at(group_id, of, at).await?;
+
+        if let Some(shares_info) = &shares_info_opt {
+            Certification::with(|it| it.certify_shares_info(shares_info.clone()));
+        }
+
+        Ok(shares_info_opt)
+    }
+
+    // should be called with a query call, otherwise the shares info won't be signed
+    pub fn get_certified_shares_info_of_at(
+        group_id: GroupId,
+        of: Principal,
+        at: u64,
+    ) -> Option<SharesInfo> {
+        Certification::with(|it| it.get_shares_info(group_id, of, at))
+    }
+
     pub async fn list_program_execution_entry_ids(
         page_req: PageRequest<ProgramExecutionFilter, ()>,
     ) -> Result<(Page<u64>, Principal), HistoryLedgerError> {
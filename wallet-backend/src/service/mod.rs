@@ -1,5 +1,7 @@
 pub mod access_config;
 pub mod choice;
+pub mod choice_amendment;
+pub mod comment;
 pub mod cron;
 pub mod events;
 pub mod group;
@@ -7,8 +9,11 @@ pub mod history_ledger;
 pub mod nested_voting;
 pub mod nested_voting_config;
 pub mod permission;
+pub mod pipeline;
 pub mod profile;
 pub mod streaming;
 pub mod token;
+pub mod vote_delegation;
 pub mod voting;
 pub mod voting_config;
+pub mod voting_template;
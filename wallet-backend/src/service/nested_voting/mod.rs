@@ -1,3 +1,4 @@
+use crate::certification::Certification;
 use crate::client::UnionWalletClient;
 use crate::controller::nested_voting::api::CastMyNestedVoteRequest;
 use crate::controller::voting::api::CastMyVoteRequest;
@@ -28,7 +29,8 @@ impl NestedVotingService {
         vote: MultiChoiceVote,
     ) -> Result<(), NestedVotingError> {
         // checking inputs
-        if !vote.shares_info.is_signature_valid() {
+        // shares info should be issued by this union
+        if !Certification::with(|it| it.is_certified(&vote.shares_info.hash())) {
             return Err(NestedVotingError::SharesInfoInvalidSignature);
         }
 
@@ -1,10 +1,13 @@
+use crate::repository::permission::model::Permission;
 use crate::repository::permission::types::{PermissionId, PermissionTarget};
 use crate::service::permission::types::{
     PermissionError, PermissionService, _get_all_read_endpoints, _get_all_write_endpoints,
-    _get_voting_endpoints, ALLOW_READ_PERMISSION_ID, ALLOW_SEND_FEEDBACK_PERMISSION_ID,
-    ALLOW_VOTE_PERMISSION_ID, ALLOW_WRITE_PERMISSION_ID,
+    _get_voting_endpoints, ALLOW_COMMENT_PERMISSION_ID, ALLOW_MODERATE_COMMENTS_PERMISSION_ID,
+    ALLOW_READ_PERMISSION_ID, ALLOW_SEND_FEEDBACK_PERMISSION_ID, ALLOW_VOTE_PERMISSION_ID,
+    ALLOW_WRITE_PERMISSION_ID,
 };
 use candid::Principal;
+use shared::mvc::HasRepository;
 
 pub mod crud;
 pub mod types;
@@ -55,6 +58,47 @@ impl PermissionService {
         .unwrap();
 
         assert_eq!(voting_participation_permission_id, ALLOW_VOTE_PERMISSION_ID);
+
+        let (comment_permission_id, moderate_comments_permission_id) =
+            PermissionService::create_comment_permissions();
+
+        assert_eq!(comment_permission_id, ALLOW_COMMENT_PERMISSION_ID);
+        assert_eq!(
+            moderate_comments_permission_id,
+            ALLOW_MODERATE_COMMENTS_PERMISSION_ID
+        );
+    }
+
+    // unions created before comments were introduced get these permissions on upgrade
+    pub fn init_missing_comment_permissions() -> Option<(PermissionId, PermissionId)> {
+        if !Permission::repo()
+            .get_permissions_by_target(&PermissionTarget::Comment)
+            .is_empty()
+        {
+            return None;
+        }
+
+        Some(PermissionService::create_comment_permissions())
+    }
+
+    fn create_comment_permissions() -> (PermissionId, PermissionId) {
+        let comment_permission_id = PermissionService::create_permission(
+            String::from("Comments"),
+            String::from(
+                "Non-deletable default permission. Allows to comment votings and choices.",
+            ),
+            vec![PermissionTarget::Comment],
+        )
+        .unwrap();
+
+        let moderate_comments_permission_id = PermissionService::create_permission(
+            String::from("Comments moderation"),
+            String::from("Non-deletable default permission. Allows to delete comments of anyone."),
+            vec![PermissionTarget::ModerateComments],
+        )
+        .unwrap();
+
+        (comment_permission_id, moderate_comments_permission_id)
     }
 
     pub fn assert_not_default(id: PermissionId) -> Result<(), PermissionError> {
@@ -62,6 +106,8 @@ impl PermissionService {
             || id == ALLOW_READ_PERMISSION_ID
             || id == ALLOW_SEND_FEEDBACK_PERMISSION_ID
             || id == ALLOW_VOTE_PERMISSION_ID
+            || id == ALLOW_COMMENT_PERMISSION_ID
+            || id == ALLOW_MODERATE_COMMENTS_PERMISSION_ID
         {
             Err(PermissionError::UnableToEditDefaultPermission)
         } else {
@@ -8,6 +8,8 @@ pub const ALLOW_WRITE_PERMISSION_ID: PermissionId = ZERO_ID;
 pub const ALLOW_READ_PERMISSION_ID: PermissionId = 1;
 pub const ALLOW_SEND_FEEDBACK_PERMISSION_ID: PermissionId = 2;
 pub const ALLOW_VOTE_PERMISSION_ID: PermissionId = 3;
+pub const ALLOW_COMMENT_PERMISSION_ID: PermissionId = 4;
+pub const ALLOW_MODERATE_COMMENTS_PERMISSION_ID: PermissionId = 5;
 
 pub struct PermissionService;
 
@@ -59,6 +61,7 @@ pub fn _get_all_write_endpoints(union_canister_id: Principal) -> Vec<RemoteCallE
         RemoteCallEndpoint::new(union_canister_id, "update_voting_choice"),
         RemoteCallEndpoint::new(union_canister_id, "delete_voting_choice"),
         RemoteCallEndpoint::new(union_canister_id, "delete_voting"),
+        RemoteCallEndpoint::new(union_canister_id, "retry_voting_execution"),
         // VOTING CONFIGS
         RemoteCallEndpoint::new(union_canister_id, "create_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "update_voting_config"),
@@ -70,6 +73,19 @@ pub fn _get_all_write_endpoints(union_canister_id: Principal) -> Vec<RemoteCallE
         RemoteCallEndpoint::new(union_canister_id, "create_nested_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "update_nested_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "delete_nested_voting_config"),
+        // PIPELINES
+        RemoteCallEndpoint::new(union_canister_id, "create_pipeline"),
+        RemoteCallEndpoint::new(union_canister_id, "update_pipeline"),
+        RemoteCallEndpoint::new(union_canister_id, "delete_pipeline"),
+        // VOTING TEMPLATES
+        RemoteCallEndpoint::new(union_canister_id, "create_voting_template"),
+        RemoteCallEndpoint::new(union_canister_id, "update_voting_template"),
+        RemoteCallEndpoint::new(union_canister_id, "delete_voting_template"),
+        RemoteCallEndpoint::new(union_canister_id, "create_voting_from_template"),
+        // CHOICE AMENDMENTS
+        RemoteCallEndpoint::new(union_canister_id, "propose_choice_amendment"),
+        RemoteCallEndpoint::new(union_canister_id, "accept_choice_amendment"),
+        RemoteCallEndpoint::new(union_canister_id, "reject_choice_amendment"),
     ]
 }
 
@@ -110,15 +126,31 @@ pub fn _get_all_read_endpoints(union_canister_id: Principal) -> Vec<RemoteCallEn
         RemoteCallEndpoint::new(union_canister_id, "get_voting_choice"),
         RemoteCallEndpoint::new(union_canister_id, "list_voting_choices"),
         RemoteCallEndpoint::new(union_canister_id, "get_voting_results"),
+        RemoteCallEndpoint::new(union_canister_id, "get_vote_power"),
+        // VOTE DELEGATIONS
+        RemoteCallEndpoint::new(union_canister_id, "list_vote_delegations"),
         // VOTING CONFIGS
         RemoteCallEndpoint::new(union_canister_id, "get_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "list_voting_configs"),
+        RemoteCallEndpoint::new(union_canister_id, "simulate_voting_config"),
         // NESTED VOTINGS
         RemoteCallEndpoint::new(union_canister_id, "get_nested_voting"),
         RemoteCallEndpoint::new(union_canister_id, "list_nested_votings"),
         // NESTED VOTING CONFIGS
         RemoteCallEndpoint::new(union_canister_id, "get_nested_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "list_nested_voting_configs"),
+        // PIPELINES
+        RemoteCallEndpoint::new(union_canister_id, "get_pipeline"),
+        RemoteCallEndpoint::new(union_canister_id, "list_pipelines"),
+        // VOTING TEMPLATES
+        RemoteCallEndpoint::new(union_canister_id, "get_voting_template"),
+        RemoteCallEndpoint::new(union_canister_id, "list_voting_templates"),
+        // COMMENTS
+        RemoteCallEndpoint::new(union_canister_id, "get_comment"),
+        RemoteCallEndpoint::new(union_canister_id, "list_comments"),
+        // CHOICE AMENDMENTS
+        RemoteCallEndpoint::new(union_canister_id, "get_choice_amendment"),
+        RemoteCallEndpoint::new(union_canister_id, "list_choice_amendments"),
     ]
 }
 
@@ -131,6 +163,9 @@ pub fn _get_voting_endpoints(union_canister_id: Principal) -> Vec<RemoteCallEndp
         RemoteCallEndpoint::new(union_canister_id, "update_voting_choice"),
         RemoteCallEndpoint::new(union_canister_id, "delete_voting_choice"),
         RemoteCallEndpoint::new(union_canister_id, "delete_voting"),
+        RemoteCallEndpoint::new(union_canister_id, "retry_voting_execution"),
+        RemoteCallEndpoint::new(union_canister_id, "create_voting_from_template"),
+        RemoteCallEndpoint::new(union_canister_id, "propose_choice_amendment"),
         // VOTINGS QUERY
         RemoteCallEndpoint::new(union_canister_id, "get_voting"),
         RemoteCallEndpoint::new(union_canister_id, "list_votings"),
@@ -140,6 +175,7 @@ pub fn _get_voting_endpoints(union_canister_id: Principal) -> Vec<RemoteCallEndp
         // VOTING CONFIGS QUERY
         RemoteCallEndpoint::new(union_canister_id, "get_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "list_voting_configs"),
+        RemoteCallEndpoint::new(union_canister_id, "simulate_voting_config"),
         // NESTED VOTINGS UPDATE
         RemoteCallEndpoint::new(union_canister_id, "create_nested_voting"),
         RemoteCallEndpoint::new(union_canister_id, "delete_nested_voting"),
@@ -149,5 +185,17 @@ pub fn _get_voting_endpoints(union_canister_id: Principal) -> Vec<RemoteCallEndp
         // NESTED VOTING CONFIGS QUERY
         RemoteCallEndpoint::new(union_canister_id, "get_nested_voting_config"),
         RemoteCallEndpoint::new(union_canister_id, "list_nested_voting_configs"),
+        // PIPELINES QUERY
+        RemoteCallEndpoint::new(union_canister_id, "get_pipeline"),
+        RemoteCallEndpoint::new(union_canister_id, "list_pipelines"),
+        // VOTING TEMPLATES QUERY
+        RemoteCallEndpoint::new(union_canister_id, "get_voting_template"),
+        RemoteCallEndpoint::new(union_canister_id, "list_voting_templates"),
+        // COMMENTS QUERY
+        RemoteCallEndpoint::new(union_canister_id, "get_comment"),
+        RemoteCallEndpoint::new(union_canister_id, "list_comments"),
+        // CHOICE AMENDMENTS QUERY
+        RemoteCallEndpoint::new(union_canister_id, "get_choice_amendment"),
+        RemoteCallEndpoint::new(union_canister_id, "list_choice_amendments"),
     ]
 }
@@ -0,0 +1,72 @@
+use crate::repository::pipeline::model::Pipeline;
+use crate::repository::pipeline::types::{PipelineFilter, PipelineId};
+use crate::repository::voting_config::model::VotingConfig;
+use crate::service::pipeline::types::{PipelineError, PipelineService};
+use shared::mvc::{HasRepository, Repository};
+use shared::pageable::{Page, PageRequest};
+use shared::types::wallet::VotingConfigId;
+
+impl PipelineService {
+    pub fn create_pipeline(
+        name: String,
+        description: String,
+        stages: Vec<VotingConfigId>,
+    ) -> Result<PipelineId, PipelineError> {
+        PipelineService::assert_voting_configs_exist(&stages)?;
+
+        let it =
+            Pipeline::new(name, description, stages).map_err(PipelineError::ValidationError)?;
+
+        Ok(Pipeline::repo().save(it))
+    }
+
+    pub fn update_pipeline(
+        id: &PipelineId,
+        name_opt: Option<String>,
+        description_opt: Option<String>,
+        stages_opt: Option<Vec<VotingConfigId>>,
+    ) -> Result<(), PipelineError> {
+        let mut it = PipelineService::get_pipeline(id)?;
+
+        if let Some(stages) = &stages_opt {
+            PipelineService::assert_voting_configs_exist(stages)?;
+        }
+
+        it.update(name_opt, description_opt, stages_opt)
+            .map_err(PipelineError::ValidationError)?;
+
+        Pipeline::repo().save(it);
+
+        Ok(())
+    }
+
+    // votings started under the pipeline keep going, since each of them has a copy of the stages
+    #[inline(always)]
+    pub fn delete_pipeline(id: &PipelineId) -> Result<Pipeline, PipelineError> {
+        Pipeline::repo()
+            .delete(id)
+            .ok_or(PipelineError::PipelineNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn get_pipeline(id: &PipelineId) -> Result<Pipeline, PipelineError> {
+        Pipeline::repo()
+            .get(id)
+            .ok_or(PipelineError::PipelineNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn list_pipelines(page_req: &PageRequest<PipelineFilter, ()>) -> Page<Pipeline> {
+        Pipeline::repo().list(page_req)
+    }
+
+    fn assert_voting_configs_exist(stages: &Vec<VotingConfigId>) -> Result<(), PipelineError> {
+        for id in stages {
+            VotingConfig::repo()
+                .get(id)
+                .ok_or(PipelineError::VotingConfigNotFound(*id))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use crate::repository::pipeline::types::PipelineId;
+use shared::types::wallet::VotingConfigId;
+use shared::validation::ValidationError;
+
+pub struct PipelineService;
+
+#[derive(Debug)]
+pub enum PipelineError {
+    ValidationError(ValidationError),
+    VotingConfigNotFound(VotingConfigId),
+    PipelineNotFound(PipelineId),
+}
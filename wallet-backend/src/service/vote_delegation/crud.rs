@@ -0,0 +1,73 @@
+use crate::repository::vote_delegation::model::VoteDelegation;
+use crate::repository::vote_delegation::types::{VoteDelegationFilter, VoteDelegationId};
+use crate::service::group::types::GroupService;
+use crate::service::vote_delegation::types::{VoteDelegationError, VoteDelegationService};
+use crate::service::voting_config::types::VotingConfigService;
+use candid::Principal;
+use shared::mvc::{HasRepository, Model, Repository};
+use shared::pageable::{Page, PageRequest};
+use shared::types::wallet::{GroupId, VotingConfigId};
+
+impl VoteDelegationService {
+    pub fn create_vote_delegation(
+        delegator: Principal,
+        delegate: Principal,
+        group_id: GroupId,
+        voting_config_id: Option<VotingConfigId>,
+        timestamp: u64,
+    ) -> Result<VoteDelegationId, VoteDelegationError> {
+        if delegator == delegate {
+            return Err(VoteDelegationError::UnableToDelegateToSelf);
+        }
+
+        GroupService::get_group(group_id)
+            .map_err(|_| VoteDelegationError::GroupNotFound(group_id))?;
+
+        if let Some(id) = &voting_config_id {
+            VotingConfigService::get_voting_config(id)
+                .map_err(|_| VoteDelegationError::VotingConfigNotFound(*id))?;
+        }
+
+        if let Some(it) =
+            VoteDelegationService::find_vote_delegation(&delegator, group_id, voting_config_id)
+        {
+            return Err(VoteDelegationError::VoteDelegationAlreadyExists(
+                it.get_id().unwrap(),
+            ));
+        }
+
+        let delegation =
+            VoteDelegation::new(delegator, delegate, group_id, voting_config_id, timestamp);
+
+        Ok(VoteDelegation::repo().save(delegation))
+    }
+
+    pub fn revoke_vote_delegation(
+        id: &VoteDelegationId,
+        caller: Principal,
+    ) -> Result<VoteDelegation, VoteDelegationError> {
+        let delegation = VoteDelegationService::get_vote_delegation(id)?;
+
+        if delegation.get_delegator() != caller {
+            return Err(VoteDelegationError::CallerIsNotDelegator);
+        }
+
+        Ok(VoteDelegation::repo().delete(id).unwrap())
+    }
+
+    #[inline(always)]
+    pub fn get_vote_delegation(
+        id: &VoteDelegationId,
+    ) -> Result<VoteDelegation, VoteDelegationError> {
+        VoteDelegation::repo()
+            .get(id)
+            .ok_or(VoteDelegationError::VoteDelegationNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn list_vote_delegations(
+        page_req: &PageRequest<VoteDelegationFilter, ()>,
+    ) -> Page<VoteDelegation> {
+        VoteDelegation::repo().list(page_req)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::repository::vote_delegation::model::VoteDelegation;
+use crate::service::vote_delegation::types::VoteDelegationService;
+use candid::Principal;
+use shared::mvc::{HasRepository, Repository};
+use shared::types::wallet::{GroupId, VotingConfigId};
+use std::collections::BTreeSet;
+
+pub mod crud;
+pub mod types;
+
+impl VoteDelegationService {
+    // a delegation scoped to the voting config takes precedence over the group-wide one
+    pub fn get_delegate_of(
+        delegator: &Principal,
+        group_id: GroupId,
+        voting_config_id: VotingConfigId,
+    ) -> Option<Principal> {
+        VoteDelegationService::find_vote_delegation(delegator, group_id, Some(voting_config_id))
+            .or_else(|| VoteDelegationService::find_vote_delegation(delegator, group_id, None))
+            .map(|it| it.get_delegate())
+    }
+
+    // everyone, whose voting power in votings of this voting config goes to the delegate
+    pub fn list_delegators_of(
+        delegate: &Principal,
+        group_id: GroupId,
+        voting_config_id: VotingConfigId,
+    ) -> BTreeSet<Principal> {
+        VoteDelegation::repo()
+            .get_vote_delegations_by_delegate(delegate)
+            .iter()
+            .map(|id| VoteDelegation::repo().get(id).unwrap())
+            .filter(|it| it.get_group_id() == group_id)
+            .map(|it| it.get_delegator())
+            .filter(|delegator| {
+                VoteDelegationService::get_delegate_of(delegator, group_id, voting_config_id)
+                    == Some(*delegate)
+            })
+            .collect()
+    }
+
+    pub fn find_vote_delegation(
+        delegator: &Principal,
+        group_id: GroupId,
+        voting_config_id: Option<VotingConfigId>,
+    ) -> Option<VoteDelegation> {
+        VoteDelegation::repo()
+            .get_vote_delegations_by_delegator(delegator)
+            .iter()
+            .map(|id| VoteDelegation::repo().get(id).unwrap())
+            .find(|it| {
+                it.get_group_id() == group_id && it.get_voting_config_id() == voting_config_id
+            })
+    }
+}
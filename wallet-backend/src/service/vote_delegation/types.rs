@@ -0,0 +1,14 @@
+use crate::repository::vote_delegation::types::VoteDelegationId;
+use shared::types::wallet::{GroupId, VotingConfigId};
+
+pub struct VoteDelegationService;
+
+#[derive(Debug)]
+pub enum VoteDelegationError {
+    GroupNotFound(GroupId),
+    VotingConfigNotFound(VotingConfigId),
+    VoteDelegationNotFound(VoteDelegationId),
+    VoteDelegationAlreadyExists(VoteDelegationId),
+    UnableToDelegateToSelf,
+    CallerIsNotDelegator,
+}
@@ -1,8 +1,15 @@
 use crate::repository::choice::model::Choice;
+use crate::repository::comment::types::CommentTarget;
+use crate::repository::pipeline::model::Pipeline;
+use crate::repository::pipeline::types::PipelineId;
 use crate::repository::voting::model::Voting;
-use crate::repository::voting::types::VotingSort;
+use crate::repository::voting::types::{LockedBondStatus, PipelineStage, VotingFilter, VotingSort};
 use crate::repository::voting_config::model::VotingConfig;
 use crate::service::choice::types::ChoiceService;
+use crate::service::choice_amendment::types::ChoiceAmendmentService;
+use crate::service::comment::types::CommentService;
+use crate::service::events::EventsService;
+use crate::service::group::types::GroupService;
 use crate::service::voting::types::{VotingError, VotingService};
 use crate::{cron_dequeue, CronService};
 use candid::Principal;
@@ -16,6 +23,51 @@ impl VotingService {
         name: String,
         description: String,
         winners_need: u32,
+        pipeline_id_opt: Option<PipelineId>,
+        proposer: Principal,
+        timestamp: u64,
+    ) -> Result<VotingId, VotingError> {
+        let pipeline_stage_opt = match pipeline_id_opt {
+            Some(pipeline_id) => {
+                let pipeline = Pipeline::repo()
+                    .get(&pipeline_id)
+                    .ok_or(VotingError::PipelineNotFound(pipeline_id))?;
+
+                // next stages are only created automatically
+                if pipeline.get_stages()[0] != voting_config_id {
+                    return Err(VotingError::VotingConfigIsNotFirstPipelineStage(
+                        pipeline_id,
+                    ));
+                }
+
+                Some(PipelineStage {
+                    pipeline_id,
+                    stages: pipeline.get_stages().clone(),
+                    stage: 0,
+                    prev_voting: None,
+                    next_voting: None,
+                })
+            }
+            None => None,
+        };
+
+        VotingService::create_voting_at_stage(
+            voting_config_id,
+            name,
+            description,
+            winners_need,
+            pipeline_stage_opt,
+            proposer,
+            timestamp,
+        )
+    }
+
+    pub(crate) fn create_voting_at_stage(
+        voting_config_id: VotingConfigId,
+        name: String,
+        description: String,
+        winners_need: u32,
+        pipeline_stage_opt: Option<PipelineStage>,
         proposer: Principal,
         timestamp: u64,
     ) -> Result<VotingId, VotingError> {
@@ -25,8 +77,14 @@ impl VotingService {
 
         VotingService::assert_winners_need_is_fine(&vc, winners_need)?;
 
-        let voting = Voting::new(
-            voting_config_id,
+        // an approved stage can't be held back by the proposer's other votings
+        if !matches!(&pipeline_stage_opt, Some(it) if it.is_automatic()) {
+            VotingService::assert_open_votings_limit_not_reached(&vc, proposer)?;
+        }
+
+        // the voting config is frozen, so its later updates don't affect this voting
+        let mut voting = Voting::new(
+            vc.clone(),
             name,
             description,
             winners_need,
@@ -35,14 +93,22 @@ impl VotingService {
         )
         .map_err(VotingError::ValidationError)?;
 
+        if let Some(pipeline_stage) = pipeline_stage_opt {
+            voting.init_pipeline_stage(pipeline_stage);
+        }
+
+        VotingService::lock_proposal_bond(&mut voting, timestamp)?;
+
         let id = Voting::repo().save(voting);
         let mut voting = Voting::repo().get(&id).unwrap();
 
         let (rejection_choice, approval_choice) =
             ChoiceService::create_rejection_and_approval_choices(id);
         voting.init_rejection_and_approval_choices(rejection_choice, approval_choice);
+        voting.init_abstain_choice(ChoiceService::create_abstain_choice(id));
 
         CronService::schedule_round_end(&mut voting, &vc, timestamp);
+        EventsService::emit_voting_created_event(&voting, timestamp);
 
         Ok(Voting::repo().save(voting))
     }
@@ -56,13 +122,8 @@ impl VotingService {
     ) -> Result<(), VotingError> {
         let mut voting = VotingService::get_voting(id)?;
 
-        // unwrapping because it should exist if it is listed
-        let vc = VotingConfig::repo()
-            .get(voting.get_voting_config_id())
-            .unwrap();
-
         if let Some(winners_need) = &new_winners_need {
-            VotingService::assert_winners_need_is_fine(&vc, *winners_need)?;
+            VotingService::assert_winners_need_is_fine(voting.get_voting_config(), *winners_need)?;
         }
 
         voting
@@ -73,13 +134,30 @@ impl VotingService {
         Ok(())
     }
 
-    pub fn delete_voting(id: &VotingId) -> Result<(), VotingError> {
-        let voting = Voting::repo().delete(id).unwrap();
+    pub fn delete_voting(id: &VotingId, timestamp: u64) -> Result<(), VotingError> {
+        let voting = VotingService::get_voting(id)?;
+
+        // the voting is gone before it is finished, so there is nobody to punish;
+        // this is the only step that can fail, so it goes first, before anything is changed
+        if let Some(bond) = voting.get_locked_bond() {
+            if matches!(bond.status, LockedBondStatus::Locked) {
+                GroupService::unlock_shares(
+                    bond.group_id,
+                    voting.get_proposer(),
+                    voting.get_proposer(),
+                    bond.quantity.clone(),
+                    timestamp,
+                )
+                .map_err(VotingError::ProposalBondError)?;
+            }
+        }
 
         if let Some(task_id) = voting.get_cron_task() {
             cron_dequeue(task_id);
         }
 
+        Voting::repo().delete(id).unwrap();
+
         Choice::repo()
             .delete(&voting.get_rejection_choice())
             .unwrap();
@@ -88,6 +166,14 @@ impl VotingService {
             .delete(&voting.get_approval_choice())
             .unwrap();
 
+        if let Some(abstain_choice) = voting.get_abstain_choice() {
+            Choice::repo().delete(&abstain_choice).unwrap();
+        }
+
+        if let Some(veto_choice) = voting.get_veto_choice() {
+            Choice::repo().delete(&veto_choice).unwrap();
+        }
+
         for result in voting.get_losers() {
             for choice in result.get_choices() {
                 Choice::repo().delete(choice).unwrap();
@@ -102,8 +188,12 @@ impl VotingService {
 
         for choice in voting.get_choices() {
             Choice::repo().delete(choice).unwrap();
+            CommentService::delete_comments_of(&CommentTarget::Choice(*choice));
+            ChoiceAmendmentService::delete_amendments_of(choice);
         }
 
+        CommentService::delete_comments_of(&CommentTarget::Voting(*id));
+
         Ok(())
     }
 
@@ -115,7 +205,7 @@ impl VotingService {
     }
 
     #[inline(always)]
-    pub fn list_votings(page_req: &PageRequest<(), VotingSort>) -> Page<Voting> {
+    pub fn list_votings(page_req: &PageRequest<VotingFilter, VotingSort>) -> Page<Voting> {
         Voting::repo().list(page_req)
     }
 }
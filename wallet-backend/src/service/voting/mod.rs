@@ -1,257 +1,1746 @@
+use crate::cron_dequeue;
 use crate::repository::choice::model::Choice;
+use crate::repository::nested_voting::types::RemoteVotingId;
 use crate::repository::token::model::Token;
 use crate::repository::voting::model::Voting;
-use crate::repository::voting::types::{RoundResult, VotingStatus};
+use crate::repository::voting::types::{
+    DelegatedVote, LockedBondStatus, OverSubscription, PipelineStage, RankedBallot, RoundExtension,
+    RoundId, RoundResult, VoteCommitment, VotingStatus,
+};
 use crate::repository::voting_config::model::VotingConfig;
-use crate::repository::voting_config::types::Fraction;
+use crate::repository::voting_config::types::{
+    Fraction, OverSubscriptionPolicy, RejectedBondPolicy, TallyMode, ThresholdValue,
+};
 use crate::service::choice::types::ChoiceService;
 use crate::service::cron::CronService;
-use crate::service::voting::types::{Vote, VotingError, VotingService};
+use crate::service::events::EventsService;
+use crate::service::group::types::GroupService;
+use crate::service::history_ledger::HistoryLedgerService;
+use crate::service::vote_delegation::types::VoteDelegationService;
+use crate::service::voting::types::{
+    RoundDecision, RoundNext, RoundOutcome, RoundResolution, RoundSimulation, RoundTally,
+    SimulatedRound, ValidatedVote, Vote, VotingError, VotingService, CAST_MY_VOTES_MAX_LEN,
+};
 use crate::service::voting_config::types::VotingConfigService;
 use bigdecimal::{BigDecimal, One};
-use candid::{Nat, Principal};
+use candid::{Encode, Nat, Principal};
+use ic_cdk::{id, print};
+use sha2::{Digest, Sha256};
 use shared::mvc::{HasRepository, Model, Repository};
+use shared::remote_call::ProgramExecutionResult;
 use shared::types::history_ledger::SharesInfo;
-use shared::types::wallet::{ChoiceId, GroupId, Shares, VotingId};
-use std::collections::BTreeMap;
+use shared::types::wallet::{ChoiceId, GroupId, ProgramExecutedWith, Shares, VotingId};
+use std::collections::{BTreeMap, BTreeSet};
 
 pub mod crud;
 pub mod types;
 
 impl VotingService {
+    // balances supplied by the voter are ignored - the wallet fetches them from the history ledger itself
+    pub async fn cast_my_vote(
+        id: &VotingId,
+        vote: Vote,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), VotingError> {
+        let (vote, delegated_shares_info) =
+            VotingService::fetch_vote_shares_info(id, vote, caller).await?;
+
+        VotingService::cast_vote(id, vote, delegated_shares_info, caller, timestamp)
+    }
+
+    // balances of all the votes are fetched first; then every vote is validated against the state
+    // before the batch, and only after that the valid ones are applied with nothing awaited in between
+    pub async fn cast_my_votes(
+        votes: Vec<(VotingId, Vote)>,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<Vec<Result<(), VotingError>>, VotingError> {
+        if votes.len() > CAST_MY_VOTES_MAX_LEN {
+            return Err(VotingError::TooManyVotesInBatch(CAST_MY_VOTES_MAX_LEN));
+        }
+
+        let mut voted_groups = BTreeSet::new();
+        let mut fetched = Vec::new();
+
+        for (id, vote) in votes {
+            let group_id = vote.get_shares_info().group_id;
+
+            // a later vote of the same group would silently override the earlier one
+            if !voted_groups.insert((id, group_id)) {
+                fetched.push(Err(VotingError::DuplicateVoteInBatch(id, group_id)));
+                continue;
+            }
+
+            let result = VotingService::fetch_vote_shares_info(&id, vote, caller)
+                .await
+                .map(|(vote, delegated_shares_info)| (id, vote, delegated_shares_info));
+
+            fetched.push(result);
+        }
+
+        let validated: Vec<_> = fetched
+            .into_iter()
+            .map(|it| {
+                let (id, vote, delegated_shares_info) = it?;
+                let voting = VotingService::get_voting(&id)?;
+                let vote = VotingService::validate_vote(
+                    &voting,
+                    vote,
+                    delegated_shares_info,
+                    caller,
+                    timestamp,
+                )?;
+
+                Ok((id, vote))
+            })
+            .collect();
+
+        let mut touched = BTreeMap::new();
+        let mut results = Vec::new();
+
+        for result in validated {
+            let (id, vote) = match result {
+                Ok(it) => it,
+                Err(e) => {
+                    results.push(Err(e));
+                    continue;
+                }
+            };
+
+            let (voting, _, anti_sniping, veto) = touched.entry(id).or_insert_with(|| {
+                let voting = VotingService::get_voting(&id).unwrap();
+                let vc = voting.get_voting_config();
+
+                // taken before any vote of the batch is applied to this voting
+                let passing_before =
+                    if VotingService::is_in_anti_sniping_window(&voting, vc, timestamp) {
+                        Some(VotingService::list_current_passing_choices(&voting, vc))
+                    } else {
+                        None
+                    };
+
+                (voting, passing_before, false, false)
+            });
+
+            *anti_sniping |= vote.anti_sniping;
+            *veto |= vote.veto;
+
+            VotingService::apply_validated_vote(voting, vote, caller, timestamp);
+            results.push(Ok(()));
+        }
+
+        // an early finish or a veto is only checked once all the votes of the batch are counted
+        for (mut voting, passing_before, anti_sniping, veto) in touched.into_values() {
+            let vc = voting.get_voting_config().clone();
+
+            VotingService::handle_new_votes(
+                &mut voting,
+                &vc,
+                passing_before.filter(|_| anti_sniping),
+                veto,
+                caller,
+                timestamp,
+            );
+            Voting::repo().save(voting);
+        }
+
+        Ok(results)
+    }
+
     pub fn cast_vote(
         id: &VotingId,
         vote: Vote,
+        delegated_shares_info: Vec<SharesInfo>,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), VotingError> {
+        let mut voting = VotingService::get_voting(id)?;
+        let vc = voting.get_voting_config().clone();
+
+        let vote =
+            VotingService::validate_vote(&voting, vote, delegated_shares_info, caller, timestamp)?;
+        let veto = vote.veto;
+
+        // a late vote that changes the outcome gives others a chance to react
+        let passing_before = if vote.anti_sniping {
+            Some(VotingService::list_current_passing_choices(&voting, &vc))
+        } else {
+            None
+        };
+
+        VotingService::apply_validated_vote(&mut voting, vote, caller, timestamp);
+        VotingService::handle_new_votes(&mut voting, &vc, passing_before, veto, caller, timestamp);
+
+        Voting::repo().save(voting);
+
+        Ok(())
+    }
+
+    // only checks the vote, the voting is not changed
+    fn validate_vote(
+        voting: &Voting,
+        vote: Vote,
+        delegated_shares_info: Vec<SharesInfo>,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<ValidatedVote, VotingError> {
+        let vc = voting.get_voting_config();
+
+        // vetoes are the only votes accepted after the voting is finished
+        if let Vote::Veto(_) = &vote {
+            if !matches!(voting.get_status(), VotingStatus::AwaitingExecution) {
+                return Err(VotingError::VetoOnlyAllowedDuringExecutionDelay);
+            }
+        } else if !matches!(voting.get_status(), VotingStatus::Round(_)) {
+            return Err(VotingError::VotingOnlyAllowedDuringRounds);
+        }
+
+        // approvals, rejections and abstentions are never quadratic
+        let quadratic =
+            matches!(vote, Vote::Common(_)) && matches!(vc.get_tally_mode(), TallyMode::Quadratic);
+        let anti_sniping = matches!(vote, Vote::Common(_))
+            && VotingService::is_in_anti_sniping_window(voting, vc, timestamp);
+        let veto = matches!(vote, Vote::Veto(_));
+
+        let (weights, shares_info, ranking) = match vote {
+            Vote::Rejection(s) => {
+                VotingService::assert_can_reject(vc, &s.shares_info.group_id)?;
+
+                (
+                    vec![(voting.get_rejection_choice(), Fraction::from(1usize))],
+                    s.shares_info,
+                    None,
+                )
+            }
+            Vote::Approval(s) => {
+                VotingService::assert_can_approve(vc, &s.shares_info.group_id)?;
+
+                (
+                    vec![(voting.get_approval_choice(), Fraction::from(1usize))],
+                    s.shares_info,
+                    None,
+                )
+            }
+            Vote::Abstain(s) => {
+                VotingService::assert_can_abstain(vc, &s.shares_info.group_id)?;
+
+                let abstain_choice = voting
+                    .get_abstain_choice()
+                    .ok_or(VotingError::AbstainNotSupportedByVoting)?;
+
+                (
+                    vec![(abstain_choice, Fraction::from(1usize))],
+                    s.shares_info,
+                    None,
+                )
+            }
+            Vote::Veto(s) => {
+                VotingService::assert_can_veto(vc, &s.shares_info.group_id)?;
+
+                (
+                    vec![(voting.get_veto_choice().unwrap(), Fraction::from(1usize))],
+                    s.shares_info,
+                    None,
+                )
+            }
+            Vote::Common(m) => {
+                VotingService::assert_can_vote(vc, &m.shares_info.group_id)?;
+
+                if matches!(vc.get_tally_mode(), TallyMode::InstantRunoff) {
+                    return Err(VotingError::VoteTypeNotAllowedByVotingConfig);
+                }
+
+                // in commit-reveal mode common votes are only accepted through commitments
+                if vc.get_commit_reveal_settings().is_some()
+                    && !matches!(voting.get_status(), VotingStatus::Round(0))
+                {
+                    return Err(VotingError::VoteShouldBeCommitted);
+                }
+
+                let weights = VotingService::get_common_vote_weights(voting, m.vote)?;
+
+                (weights, m.shares_info, None)
+            }
+            Vote::Ranked(r) => {
+                VotingService::assert_can_vote(vc, &r.shares_info.group_id)?;
+
+                if !matches!(vc.get_tally_mode(), TallyMode::InstantRunoff) {
+                    return Err(VotingError::VoteTypeNotAllowedByVotingConfig);
+                }
+
+                VotingService::assert_ranking_valid(voting, &r.ranking)?;
+
+                // the whole balance goes to the most preferred choice until it is eliminated
+                (
+                    vec![(r.ranking[0], Fraction::from(1usize))],
+                    r.shares_info,
+                    Some(r.ranking),
+                )
+            }
+        };
+
+        // an early finish relies on votes which are already cast being final
+        if !veto
+            && vc.get_round_settings().early_finish
+            && (voting.has_direct_vote(&shares_info.group_id, &caller)
+                || voting.has_delegated_vote(&shares_info.group_id, &caller))
+        {
+            return Err(VotingError::VoteAlreadyCast);
+        }
+
+        VotingService::assert_shares_info_valid(&shares_info, voting, caller)?;
+
+        for info in &delegated_shares_info {
+            VotingService::assert_delegated_shares_info_valid(info, &shares_info, voting, caller)?;
+        }
+
+        Ok(ValidatedVote {
+            weights,
+            ranking,
+            quadratic,
+            anti_sniping,
+            veto,
+            shares_info,
+            delegated_shares_info,
+        })
+    }
+
+    fn apply_validated_vote(
+        voting: &mut Voting,
+        vote: ValidatedVote,
+        caller: Principal,
+        timestamp: u64,
+    ) {
+        let group_id = vote.shares_info.group_id;
+
+        VotingService::apply_votes(voting, vote, caller, timestamp);
+        EventsService::emit_vote_cast_event(voting, caller, group_id, timestamp);
+    }
+
+    // passing choices before the votes are only supplied, if the votes are cast in the anti-sniping window
+    fn handle_new_votes(
+        voting: &mut Voting,
+        vc: &VotingConfig,
+        passing_before: Option<(Vec<ChoiceId>, Vec<ChoiceId>)>,
+        veto: bool,
+        caller: Principal,
+        timestamp: u64,
+    ) {
+        if let Some(passing_before) = passing_before {
+            if passing_before != VotingService::list_current_passing_choices(voting, vc) {
+                VotingService::extend_round(voting, vc, caller, timestamp);
+            }
+        }
+
+        if vc.get_round_settings().early_finish && VotingService::is_outcome_decided(voting, vc) {
+            if let Some(task_id) = voting.get_cron_task() {
+                cron_dequeue(task_id);
+            }
+
+            VotingService::try_finish_voting(voting, vc, timestamp);
+        }
+
+        if veto {
+            VotingService::try_veto(voting, vc, timestamp);
+        }
+    }
+
+    pub async fn commit_my_vote(
+        id: &VotingId,
+        shares_info: SharesInfo,
+        hash: Vec<u8>,
         caller: Principal,
         timestamp: u64,
     ) -> Result<(), VotingError> {
+        let group_id = shares_info.group_id;
+        let shares_info = VotingService::fetch_shares_info(id, group_id, caller).await?;
+
+        let delegated_shares_info =
+            VotingService::fetch_delegated_shares_info(id, group_id, caller).await?;
+
+        VotingService::commit_vote(
+            id,
+            shares_info,
+            delegated_shares_info,
+            hash,
+            caller,
+            timestamp,
+        )
+    }
+
+    pub fn commit_vote(
+        id: &VotingId,
+        shares_info: SharesInfo,
+        delegated_shares_info: Vec<SharesInfo>,
+        hash: Vec<u8>,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), VotingError> {
+        let mut voting = VotingService::get_voting(id)?;
+        let vc = voting.get_voting_config().clone();
+
+        if vc.get_commit_reveal_settings().is_none() {
+            return Err(VotingError::VoteTypeNotAllowedByVotingConfig);
+        }
+
+        match voting.get_status() {
+            VotingStatus::Round(0) => return Err(VotingError::VoteTypeNotAllowedByVotingConfig),
+            VotingStatus::Round(_) => {}
+            _ => return Err(VotingError::VotingOnlyAllowedDuringRounds),
+        };
+
+        VotingService::assert_can_vote(&vc, &shares_info.group_id)?;
+        VotingService::assert_shares_info_valid(&shares_info, &voting, caller)?;
+
+        for info in &delegated_shares_info {
+            VotingService::assert_delegated_shares_info_valid(info, &shares_info, &voting, caller)?;
+        }
+
+        let group_id = shares_info.group_id;
+        let commitment = VoteCommitment {
+            hash,
+            shares_info,
+            delegated_shares_info,
+            revealed: false,
+        };
+
+        voting.set_commitment(group_id, caller, commitment, timestamp);
+        Voting::repo().add_voter(id, caller);
+        Voting::repo().save(voting);
+
+        Ok(())
+    }
+
+    pub fn reveal_vote(
+        id: &VotingId,
+        group_id: GroupId,
+        vote: BTreeMap<ChoiceId, Fraction>,
+        salt: Vec<u8>,
+        caller: Principal,
+        timestamp: u64,
+    ) -> Result<(), VotingError> {
+        let mut voting = VotingService::get_voting(id)?;
+        let vc = voting.get_voting_config().clone();
+
+        if !matches!(voting.get_status(), VotingStatus::Reveal(_)) {
+            return Err(VotingError::RevealOnlyAllowedDuringRevealPhase);
+        }
+
+        let commitment = voting
+            .get_commitment(&group_id, &caller)
+            .cloned()
+            .ok_or(VotingError::CommitmentNotFound)?;
+
+        if commitment.revealed {
+            return Err(VotingError::CommitmentAlreadyRevealed);
+        }
+
+        if VotingService::hash_vote(&vote, &salt) != commitment.hash {
+            return Err(VotingError::CommitmentMismatch);
+        }
+
+        let weights = VotingService::get_common_vote_weights(&voting, vote)?;
+        let quadratic = matches!(vc.get_tally_mode(), TallyMode::Quadratic);
+
+        let vote = ValidatedVote {
+            weights,
+            ranking: None,
+            quadratic,
+            anti_sniping: false,
+            veto: false,
+            shares_info: commitment.shares_info,
+            delegated_shares_info: commitment.delegated_shares_info,
+        };
+
+        voting.mark_commitment_revealed(&group_id, &caller, timestamp);
+        VotingService::apply_validated_vote(&mut voting, vote, caller, timestamp);
+
+        Voting::repo().save(voting);
+
+        Ok(())
+    }
+
+    pub fn should_reveal(voting: &Voting, vc: &VotingConfig) -> bool {
+        vc.get_commit_reveal_settings().is_some()
+            && matches!(voting.get_status(), VotingStatus::Round(r) if *r > 0)
+    }
+
+    // unrevealed commitments count as abstentions: their shares participate in the quorum only
+    pub fn finish_reveal(voting: &mut Voting, timestamp: u64) {
+        let round = match voting.get_status() {
+            VotingStatus::Reveal(r) => *r,
+            _ => unreachable!(),
+        };
+
+        let commitments = voting.end_reveal(timestamp);
+
+        for (group_id, by_principal) in &commitments {
+            for (principal, commitment) in by_principal {
+                if commitment.revealed {
+                    continue;
+                }
+
+                VotingService::remove_prev_vote(voting, *group_id, *principal);
+                voting.add_unrevealed_shares(
+                    round,
+                    *group_id,
+                    commitment.shares_info.balance.clone(),
+                    timestamp,
+                );
+
+                for info in &commitment.delegated_shares_info {
+                    if voting.has_direct_vote(group_id, &info.principal_id)
+                        || by_principal.contains_key(&info.principal_id)
+                    {
+                        continue;
+                    }
+
+                    VotingService::remove_prev_vote(voting, *group_id, info.principal_id);
+                    voting.add_unrevealed_shares(round, *group_id, info.balance.clone(), timestamp);
+                }
+            }
+        }
+    }
+
+    // sha256 of the candid-encoded (vote, salt) pair
+    pub fn hash_vote(vote: &BTreeMap<ChoiceId, Fraction>, salt: &Vec<u8>) -> Vec<u8> {
+        let bytes = Encode!(vote, salt).expect("Unable to encode vote");
+
+        Sha256::digest(&bytes).to_vec()
+    }
+
+    fn get_common_vote_weights(
+        voting: &Voting,
+        vote: BTreeMap<ChoiceId, Fraction>,
+    ) -> Result<Vec<(ChoiceId, Fraction)>, VotingError> {
+        let total_fraction: BigDecimal = vote.values().map(|f| f.0.abs()).sum();
+
+        if total_fraction > BigDecimal::one() {
+            return Err(VotingError::VoteFractionTooBig);
+        }
+
+        vote.into_iter()
+            .map(|(id, f)| {
+                if !voting.get_choices().contains(&id) {
+                    return Err(VotingError::ChoiceNotFound(id));
+                }
+
+                Ok((id, Fraction(f.0.abs())))
+            })
+            .collect()
+    }
+
+    fn apply_votes(voting: &mut Voting, vote: ValidatedVote, caller: Principal, timestamp: u64) {
+        let ValidatedVote {
+            weights,
+            ranking,
+            quadratic,
+            shares_info,
+            delegated_shares_info,
+            ..
+        } = vote;
+
+        // delegators are not included - only those who voted by themselves
+        Voting::repo().add_voter(&voting.get_id().unwrap(), shares_info.principal_id);
+        voting.set_direct_vote(
+            shares_info.group_id,
+            shares_info.principal_id,
+            shares_info.balance.clone(),
+            timestamp,
+        );
+        VotingService::apply_vote(
+            voting,
+            &weights,
+            &ranking,
+            quadratic,
+            shares_info,
+            timestamp,
+        );
+
+        let early_finish = voting.get_voting_config().get_round_settings().early_finish;
+
+        for info in delegated_shares_info {
+            // delegators who voted by themselves are not affected by their delegate
+            if voting.has_direct_vote(&info.group_id, &info.principal_id) {
+                continue;
+            }
+
+            // with early finish, a vote made on their behalf is final as well
+            if early_finish && voting.has_delegated_vote(&info.group_id, &info.principal_id) {
+                continue;
+            }
+
+            let delegated_vote = DelegatedVote {
+                delegate: caller,
+                shares: info.balance.clone(),
+            };
+            voting.set_delegated_vote(info.group_id, info.principal_id, delegated_vote, timestamp);
+
+            VotingService::apply_vote(voting, &weights, &ranking, quadratic, info, timestamp);
+        }
+    }
+
+    pub fn get_vote_power(
+        voting_id: &VotingId,
+        group_id: GroupId,
+        voter: Principal,
+    ) -> Result<(Shares, BTreeMap<Principal, Shares>), VotingError> {
+        let voting = VotingService::get_voting(voting_id)?;
+
+        let own = voting
+            .get_direct_vote(&group_id, &voter)
+            .cloned()
+            .unwrap_or_default();
+        let delegated = voting.list_delegated_votes_of(&group_id, &voter);
+
+        Ok((own, delegated))
+    }
+
+    // the same vote is applied to the caller's own shares and to the shares of each delegator
+    fn apply_vote(
+        voting: &mut Voting,
+        weights: &[(ChoiceId, Fraction)],
+        ranking: &Option<Vec<ChoiceId>>,
+        quadratic: bool,
+        shares_info: SharesInfo,
+        timestamp: u64,
+    ) {
+        VotingService::remove_prev_vote(voting, shares_info.group_id, shares_info.principal_id);
+        voting.remove_ranked_ballot(&shares_info.group_id, &shares_info.principal_id, timestamp);
+
+        // thresholds only see the capped shares, the raw ones are recorded separately
+        let cap_opt = voting.get_voting_config().get_voting_power_cap().clone();
+        let balance = match &cap_opt {
+            Some(cap) => cap.apply(shares_info.balance.clone(), &shares_info.total_supply),
+            None => shares_info.balance.clone(),
+        };
+
+        if let Some(ranking) = ranking {
+            let ballot = RankedBallot::new(ranking.clone(), balance.clone());
+            voting.set_ranked_ballot(
+                shares_info.group_id,
+                shares_info.principal_id,
+                ballot,
+                timestamp,
+            );
+        }
+
+        let choices = weights
+            .iter()
+            .map(|(id, weight)| {
+                let choice = Choice::repo().get(id).unwrap();
+                let shares: Nat = (weight.clone() * Fraction::from(balance.clone())).into();
+                let raw_shares_opt: Option<Nat> = cap_opt
+                    .as_ref()
+                    .map(|_| (weight.clone() * Fraction::from(shares_info.balance.clone())).into());
+
+                (choice, shares, raw_shares_opt)
+            })
+            .collect();
+
+        VotingService::put_vote(
+            voting,
+            choices,
+            quadratic,
+            shares_info.total_supply,
+            shares_info.group_id,
+            shares_info.principal_id,
+            timestamp,
+        );
+    }
+
+    pub fn has_next_stage(voting: &Voting) -> bool {
+        voting
+            .get_pipeline_stage()
+            .as_ref()
+            .and_then(|it| it.get_next_voting_config())
+            .is_some()
+    }
+
+    // winners of an intermediate stage are not executed - they are voted on again at the next stage
+    pub fn start_next_stage(voting: &mut Voting, timestamp: u64) -> Result<VotingId, VotingError> {
+        let stage = voting.get_pipeline_stage().clone().unwrap();
+        let next_vc_id = stage.get_next_voting_config().unwrap();
+
+        let next_vc = VotingConfig::repo()
+            .get(&next_vc_id)
+            .ok_or(VotingError::VotingConfigNotFound(next_vc_id))?;
+
+        let winners: Vec<_> = voting
+            .get_winners()
+            .iter()
+            .flat_map(|it| it.get_choices().iter())
+            .map(|id| Choice::repo().get(id).unwrap())
+            .collect();
+
+        // checked in advance, so a failure doesn't leave a half-made voting behind
+        if !winners
+            .iter()
+            .all(|it| VotingConfigService::does_program_fit(&next_vc, it.get_program()))
+        {
+            return Err(VotingError::ProgramNotAllowedByVotingConfig);
+        }
+
+        let next_id = VotingService::create_voting_at_stage(
+            next_vc_id,
+            voting.get_name().clone(),
+            voting.get_description().clone(),
+            voting.get_winners_need(),
+            Some(PipelineStage {
+                pipeline_id: stage.pipeline_id,
+                stages: stage.stages.clone(),
+                stage: stage.stage + 1,
+                prev_voting: voting.get_id(),
+                next_voting: None,
+            }),
+            voting.get_proposer(),
+            timestamp,
+        )?;
+
+        let mut next_voting = Voting::repo().get(&next_id).unwrap();
+
+        for winner in winners {
+            let choice = Choice::new(
+                winner.get_name().clone(),
+                winner.get_description().clone(),
+                winner.get_program().clone(),
+                RemoteVotingId::Common(next_id),
+            )
+            .unwrap();
+
+            next_voting.add_choice(Choice::repo().save(choice), timestamp);
+        }
+
+        Voting::repo().save(next_voting);
+        voting.set_next_stage_voting(next_id, timestamp);
+
+        Ok(next_id)
+    }
+
+    pub fn try_finish_voting(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let round = match voting.get_status() {
+            VotingStatus::Round(r) => *r,
+            _ => unreachable!("CRON BUG DETECTED, REPORT TO SASHA"),
+        };
+
+        VotingService::finish_round(voting, vc, timestamp);
+        VotingService::settle_locked_bond(voting, timestamp);
+        EventsService::emit_voting_round_ended_event(voting, round, timestamp);
+    }
+
+    // the proposer pays the bond once per proposal - the bond of the first pipeline stage is
+    // already returned by the time the next stage is started, so it isn't asked for again
+    fn lock_proposal_bond(voting: &mut Voting, timestamp: u64) -> Result<(), VotingError> {
+        if matches!(voting.get_pipeline_stage(), Some(it) if it.is_automatic()) {
+            return Ok(());
+        }
+
+        let bond = match voting.get_voting_config().get_proposal_bond() {
+            Some(it) => it.clone(),
+            None => return Ok(()),
+        };
+
+        GroupService::lock_shares(
+            bond.group_id,
+            voting.get_proposer(),
+            bond.quantity.clone(),
+            timestamp,
+        )
+        .map_err(VotingError::ProposalBondError)?;
+        voting.init_locked_bond(bond.group_id, bond.quantity);
+
+        Ok(())
+    }
+
+    // the bond is only lost, if the proposal is rejected - otherwise it goes back to the proposer
+    fn settle_locked_bond(voting: &mut Voting, timestamp: u64) {
+        let bond = match voting.get_locked_bond() {
+            Some(it) if matches!(it.status, LockedBondStatus::Locked) => it.clone(),
+            _ => return,
+        };
+
+        let on_rejection = voting
+            .get_voting_config()
+            .get_proposal_bond()
+            .as_ref()
+            .map(|it| it.on_rejection)
+            .unwrap_or(RejectedBondPolicy::Burn);
+        let proposer = voting.get_proposer();
+
+        let (res, status) = match voting.get_status() {
+            VotingStatus::Rejected => match on_rejection {
+                RejectedBondPolicy::Burn => (
+                    GroupService::burn_locked_shares(bond.group_id, proposer, bond.quantity),
+                    LockedBondStatus::Burned,
+                ),
+                RejectedBondPolicy::Keep => (
+                    GroupService::unlock_shares(
+                        bond.group_id,
+                        proposer,
+                        id(),
+                        bond.quantity,
+                        timestamp,
+                    ),
+                    LockedBondStatus::Kept,
+                ),
+            },
+            VotingStatus::AwaitingExecution | VotingStatus::Fail(_) => (
+                GroupService::unlock_shares(
+                    bond.group_id,
+                    proposer,
+                    proposer,
+                    bond.quantity,
+                    timestamp,
+                ),
+                LockedBondStatus::Returned,
+            ),
+            _ => return,
+        };
+
+        match res {
+            Ok(_) => voting.set_locked_bond_status(status, timestamp),
+            Err(e) => print(format!("Unable to settle the proposal bond: {:?}", e)),
+        }
+    }
+
+    fn finish_round(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let tally = VotingService::tally_round(voting);
+        let decision = VotingService::decide_round(vc, &tally);
+
+        VotingService::apply_round_outcome(voting, vc, tally.round, decision.outcome, timestamp);
+    }
+
+    fn tally_round(voting: &Voting) -> RoundTally {
+        let round = match voting.get_status() {
+            VotingStatus::Round(r) => *r,
+            _ => unreachable!("CRON BUG DETECTED, REPORT TO SASHA"),
+        };
+
+        let rejection_choice = Choice::repo().get(&voting.get_rejection_choice()).unwrap();
+        let approval_choice = Choice::repo().get(&voting.get_approval_choice()).unwrap();
+
+        let mut votes_by_choice = BTreeMap::new();
+        let mut spent_shares_by_group = BTreeMap::new();
+
+        for id in voting.get_choices() {
+            let choice = Choice::repo().get(id).unwrap();
+            votes_by_choice.insert(
+                *id,
+                ChoiceService::list_total_voted_shares_by_group(&choice),
+            );
+
+            // the quorum is about participation, so it is measured in shares spent
+            VotingService::add_shares_by_group(
+                &mut spent_shares_by_group,
+                ChoiceService::list_total_spent_shares_by_group(&choice),
+            );
+        }
+
+        // abstentions only count towards the quorum
+        if let Some(id) = voting.get_abstain_choice() {
+            let abstain_choice = Choice::repo().get(&id).unwrap();
+
+            VotingService::add_shares_by_group(
+                &mut spent_shares_by_group,
+                ChoiceService::list_total_spent_shares_by_group(&abstain_choice),
+            );
+        }
+
+        // unrevealed commitments are abstentions too - they still count towards the quorum
+        VotingService::add_shares_by_group(
+            &mut spent_shares_by_group,
+            voting.get_unrevealed_shares_by_group(round),
+        );
+
+        RoundTally {
+            round,
+            winners_left: voting
+                .get_winners_need()
+                .saturating_sub(VotingService::count_winners(voting)),
+            total_voting_power_by_group: voting.get_total_voting_power_by_group().clone(),
+            approval_votes: ChoiceService::list_total_voted_shares_by_group(&approval_choice),
+            rejection_votes: ChoiceService::list_total_voted_shares_by_group(&rejection_choice),
+            votes_by_choice,
+            spent_shares_by_group,
+        }
+    }
+
+    // decides how the round ends without touching any state, so it is shared with the simulator
+    fn decide_round(vc: &VotingConfig, tally: &RoundTally) -> RoundDecision {
+        let total = &tally.total_voting_power_by_group;
+
+        let mut decision = RoundDecision {
+            rejection_reached: vc.get_rejection_threshold().is_reached(
+                total,
+                &tally.rejection_votes,
+                vc.get_group_weights(),
+            ),
+            approval_reached: None,
+            quorum_reached: None,
+            win: Vec::new(),
+            next_round: Vec::new(),
+            outcome: RoundOutcome::Rejected,
+        };
+
+        if decision.rejection_reached {
+            return decision;
+        }
+
+        if tally.round == 0 {
+            // TODO: also check winners/choices compatibility
+            if let Some(cc) = vc.get_choices_count() {
+                if !cc.contains(tally.votes_by_choice.len() as u32) {
+                    decision.outcome = RoundOutcome::Fail(String::from("Choices count is invalid"));
+                    return decision;
+                }
+            }
+
+            let approval_reached = vc.get_approval_threshold().is_reached(
+                total,
+                &tally.approval_votes,
+                vc.get_group_weights(),
+            );
+
+            decision.approval_reached = Some(approval_reached);
+            decision.outcome = if approval_reached {
+                RoundOutcome::Approved
+            } else {
+                RoundOutcome::Fail(String::from("Not enough approvals"))
+            };
+
+            return decision;
+        }
+
+        let quorum_reached = vc.get_quorum_threshold().is_reached(
+            total,
+            &tally.spent_shares_by_group,
+            vc.get_group_weights(),
+        );
+        decision.quorum_reached = Some(quorum_reached);
+
+        if !quorum_reached {
+            decision.outcome = RoundOutcome::Fail(String::from("Not enough votes"));
+            return decision;
+        }
+
+        if let TallyMode::InstantRunoff = vc.get_tally_mode() {
+            decision.outcome = RoundOutcome::InstantRunoff;
+            return decision;
+        }
+
+        let (win, next_round) = VotingService::split_choices_by_thresholds(
+            vc,
+            total,
+            tally.votes_by_choice.clone().into_iter().collect(),
+        );
+
+        decision.outcome = if win.is_empty() && next_round.is_empty() {
+            RoundOutcome::Fail(String::from("No worthy choices to continue"))
+        } else if win.len() as u32 > tally.winners_left {
+            VotingService::decide_over_subscription(vc, tally, win.clone())
+        } else if !win.is_empty() {
+            let next = if win.len() as u32 == tally.winners_left {
+                RoundNext::Success
+            } else {
+                RoundNext::NextRound
+            };
+
+            RoundOutcome::Resolved(RoundResolution {
+                winners: win.clone(),
+                losers: Vec::new(),
+                over_subscription: None,
+                next,
+            })
+        } else {
+            let losers: Vec<_> = tally
+                .votes_by_choice
+                .keys()
+                .filter(|id| !next_round.contains(id))
+                .cloned()
+                .collect();
+
+            let next = if (next_round.len() as u32) < tally.winners_left {
+                RoundNext::Fail(String::from("Not enough choices to continue"))
+            } else {
+                RoundNext::NextRound
+            };
+
+            RoundOutcome::Resolved(RoundResolution {
+                winners: Vec::new(),
+                losers,
+                over_subscription: None,
+                next,
+            })
+        };
+
+        decision.win = win;
+        decision.next_round = next_round;
+
+        decision
+    }
+
+    fn apply_round_outcome(
+        voting: &mut Voting,
+        vc: &VotingConfig,
+        round: RoundId,
+        outcome: RoundOutcome,
+        timestamp: u64,
+    ) {
+        let resolution = match outcome {
+            RoundOutcome::Rejected => {
+                voting.reject(timestamp);
+                return;
+            }
+            RoundOutcome::Approved => {
+                voting.next_round(timestamp);
+                CronService::schedule_round_start(voting, vc, timestamp);
+                return;
+            }
+            RoundOutcome::InstantRunoff => {
+                VotingService::run_instant_runoff(voting, vc, timestamp);
+                return;
+            }
+            RoundOutcome::Fail(reason) => {
+                voting.finish_fail(reason, timestamp);
+                return;
+            }
+            RoundOutcome::Resolved(it) => it,
+        };
+
+        if !resolution.winners.is_empty() {
+            let mut new_winners = RoundResult::new(round);
+
+            for choice_id in resolution.winners {
+                voting.remove_choice(&choice_id, timestamp);
+                new_winners.add_choice(choice_id);
+            }
+
+            if let Some(over_subscription) = &resolution.over_subscription {
+                new_winners.set_over_subscription(over_subscription.clone());
+            }
+
+            voting.add_winner(new_winners, timestamp);
+        }
+
+        if !resolution.losers.is_empty() {
+            let mut new_losers = RoundResult::new(round);
+
+            for choice_id in resolution.losers {
+                voting.remove_choice(&choice_id, timestamp);
+                new_losers.add_choice(choice_id);
+            }
+
+            if let Some(over_subscription) = resolution.over_subscription {
+                new_losers.set_over_subscription(over_subscription);
+            }
+
+            voting.add_loser(new_losers, timestamp);
+        }
+
+        match resolution.next {
+            RoundNext::Success => VotingService::finish_success(voting, vc, timestamp),
+            RoundNext::NextRound => {
+                voting.next_round(timestamp);
+                CronService::schedule_round_start(voting, vc, timestamp);
+            }
+            RoundNext::Fail(reason) => voting.finish_fail(reason, timestamp),
+        }
+    }
+
+    // decides the end of a round against hypothetical numbers, nothing is saved
+    pub fn simulate_round(
+        vc: &VotingConfig,
+        round: SimulatedRound,
+    ) -> Result<RoundSimulation, VotingError> {
+        // eliminations depend on individual ballots, which are not known here
+        if matches!(vc.get_tally_mode(), TallyMode::InstantRunoff) {
+            return Err(VotingError::SimulationNotSupportedByTallyMode);
+        }
+
+        // without quadratic voting shares spent are the same as votes
+        let mut spent_shares_by_group = match round.spent_shares_by_group_opt {
+            Some(it) => it,
+            None => {
+                let mut spent = BTreeMap::new();
+                for votes in round.votes_by_choice.values() {
+                    VotingService::add_shares_by_group(&mut spent, votes.clone());
+                }
+
+                spent
+            }
+        };
+        VotingService::add_shares_by_group(&mut spent_shares_by_group, round.abstain_votes);
+        VotingService::add_shares_by_group(
+            &mut spent_shares_by_group,
+            round.unrevealed_shares_by_group,
+        );
+
+        let tally = RoundTally {
+            round: round.round,
+            winners_left: round.winners_left,
+            total_voting_power_by_group: round.total_voting_power_by_group,
+            approval_votes: round.approval_votes,
+            rejection_votes: round.rejection_votes,
+            votes_by_choice: round.votes_by_choice,
+            spent_shares_by_group,
+        };
+
+        let decision = VotingService::decide_round(vc, &tally);
+
+        let (winners, losers, over_subscription, status) = match decision.outcome {
+            RoundOutcome::Rejected => (Vec::new(), Vec::new(), None, VotingStatus::Rejected),
+            RoundOutcome::Approved => (
+                Vec::new(),
+                Vec::new(),
+                None,
+                VotingStatus::PreRound(tally.round + 1),
+            ),
+            RoundOutcome::InstantRunoff => unreachable!(),
+            RoundOutcome::Fail(reason) => {
+                (Vec::new(), Vec::new(), None, VotingStatus::Fail(reason))
+            }
+            RoundOutcome::Resolved(it) => {
+                let status = match it.next {
+                    RoundNext::Success => VotingStatus::AwaitingExecution,
+                    RoundNext::NextRound => VotingStatus::PreRound(tally.round + 1),
+                    RoundNext::Fail(reason) => VotingStatus::Fail(reason),
+                };
+
+                (it.winners, it.losers, it.over_subscription, status)
+            }
+        };
+
+        Ok(RoundSimulation {
+            rejection_reached: decision.rejection_reached,
+            approval_reached: decision.approval_reached,
+            quorum_reached: decision.quorum_reached,
+            win: decision.win,
+            next_round: decision.next_round,
+            winners,
+            losers,
+            over_subscription,
+            status,
+        })
+    }
+
+    fn add_shares_by_group(
+        acc: &mut BTreeMap<GroupId, Shares>,
+        shares_by_group: impl IntoIterator<Item = (GroupId, Shares)>,
+    ) {
+        for (group_id, shares) in shares_by_group {
+            let prev_shares = acc.get(&group_id).cloned().unwrap_or_default();
+            acc.insert(group_id, prev_shares + shares);
+        }
+    }
+
+    // which choices would win or pass to the next round, if the round ended right now
+    fn list_passing_choices(
+        voting: &Voting,
+        vc: &VotingConfig,
+        choices: &[Choice],
+    ) -> (Vec<ChoiceId>, Vec<ChoiceId>) {
+        let votes_by_choice = choices
+            .iter()
+            .map(|choice| {
+                (
+                    choice.get_id().unwrap(),
+                    ChoiceService::list_total_voted_shares_by_group(choice),
+                )
+            })
+            .collect();
+
+        VotingService::split_choices_by_thresholds(
+            vc,
+            voting.get_total_voting_power_by_group(),
+            votes_by_choice,
+        )
+    }
+
+    fn list_current_passing_choices(
+        voting: &Voting,
+        vc: &VotingConfig,
+    ) -> (Vec<ChoiceId>, Vec<ChoiceId>) {
+        let choices: Vec<_> = voting
+            .get_choices()
+            .iter()
+            .map(|id| Choice::repo().get(id).unwrap())
+            .collect();
+
+        VotingService::list_passing_choices(voting, vc, &choices)
+    }
+
+    // checks whether the voting power, which is not cast yet, is unable to change the outcome of
+    // the current round; votes which are already cast are final, since they can't be changed
+    // while early finish is on
+    fn is_outcome_decided(voting: &Voting, vc: &VotingConfig) -> bool {
+        let round = match voting.get_status() {
+            VotingStatus::Round(r) => *r,
+            _ => return false,
+        };
+
+        let mut thresholds = vec![vc.get_rejection_threshold()];
+        if round == 0 {
+            thresholds.push(vc.get_approval_threshold());
+        } else {
+            thresholds.push(vc.get_quorum_threshold());
+            thresholds.push(vc.get_win_threshold());
+            thresholds.push(vc.get_next_round_threshold());
+        }
+
+        if !thresholds.iter().all(|th| th.is_monotonic()) {
+            return false;
+        }
+
+        // the voting power of groups nobody voted from yet is unknown
+        let total = voting.get_total_voting_power_by_group();
+        if !thresholds
+            .iter()
+            .flat_map(|th| th.list_groups())
+            .all(|group_id| total.contains_key(&group_id))
+        {
+            return false;
+        }
+
+        let rejection_choice = Choice::repo().get(&voting.get_rejection_choice()).unwrap();
+        let choices: Vec<_> = if round == 0 {
+            vec![Choice::repo().get(&voting.get_approval_choice()).unwrap()]
+        } else {
+            voting
+                .get_choices()
+                .iter()
+                .map(|id| Choice::repo().get(id).unwrap())
+                .collect()
+        };
+
+        let mut cast_shares_by_group =
+            ChoiceService::list_total_spent_shares_by_group(&rejection_choice);
+        let mut spent_shares_by_group = BTreeMap::<GroupId, Shares>::new();
+
+        let abstain_choice_opt = voting
+            .get_abstain_choice()
+            .map(|id| Choice::repo().get(&id).unwrap());
+
+        for choice in choices.iter().chain(abstain_choice_opt.iter()) {
+            for (group_id, shares) in ChoiceService::list_total_spent_shares_by_group(choice) {
+                let prev_cast = cast_shares_by_group
+                    .get(&group_id)
+                    .cloned()
+                    .unwrap_or_default();
+                cast_shares_by_group.insert(group_id, prev_cast + shares.clone());
+
+                let prev_spent = spent_shares_by_group
+                    .get(&group_id)
+                    .cloned()
+                    .unwrap_or_default();
+                spent_shares_by_group.insert(group_id, prev_spent + shares);
+            }
+        }
+
+        let uncast_shares_by_group: BTreeMap<_, _> = total
+            .iter()
+            .map(|(group_id, total_shares)| {
+                let cast = cast_shares_by_group
+                    .get(group_id)
+                    .cloned()
+                    .unwrap_or_default();
+                let uncast = if *total_shares > cast {
+                    total_shares.clone() - cast
+                } else {
+                    Shares::default()
+                };
+
+                (*group_id, uncast)
+            })
+            .collect();
+
+        let is_decided = |threshold: &ThresholdValue, votes: &BTreeMap<GroupId, Shares>| {
+            VotingService::get_threshold_outcome(
+                threshold,
+                total,
+                votes,
+                &uncast_shares_by_group,
+                vc.get_group_weights(),
+            )
+        };
+
+        let rejection_votes = ChoiceService::list_total_voted_shares_by_group(&rejection_choice);
+        match is_decided(vc.get_rejection_threshold(), &rejection_votes) {
+            Some(true) => return true,
+            Some(false) => {}
+            None => return false,
+        }
+
+        if round == 0 {
+            // choices can still be added or removed, until the round is over
+            if vc.get_choices_count().is_some() {
+                return false;
+            }
+
+            let approval_votes = ChoiceService::list_total_voted_shares_by_group(&choices[0]);
+
+            return is_decided(vc.get_approval_threshold(), &approval_votes).is_some();
+        }
+
+        match is_decided(vc.get_quorum_threshold(), &spent_shares_by_group) {
+            Some(true) => {}
+            Some(false) => return true,
+            None => return false,
+        }
+
+        let mut win_count = 0u32;
+
+        for choice in &choices {
+            let votes = ChoiceService::list_total_voted_shares_by_group(choice);
+
+            match is_decided(vc.get_win_threshold(), &votes) {
+                Some(true) => win_count += 1,
+                Some(false) => {
+                    if is_decided(vc.get_next_round_threshold(), &votes).is_none() {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        // other policies depend on the exact scores, which may still change
+        let slots_left = voting
+            .get_winners_need()
+            .saturating_sub(VotingService::count_winners(voting));
+
+        win_count <= slots_left
+            || matches!(
+                vc.get_over_subscription_policy(),
+                OverSubscriptionPolicy::Fail
+            )
+    }
+
+    // returns whether a monotonic threshold is reached, if that can't be changed by the uncast shares
+    fn get_threshold_outcome(
+        threshold: &ThresholdValue,
+        total: &BTreeMap<GroupId, Shares>,
+        votes: &BTreeMap<GroupId, Shares>,
+        uncast: &BTreeMap<GroupId, Shares>,
+        weights: &BTreeMap<GroupId, Fraction>,
+    ) -> Option<bool> {
+        let mut max_votes = votes.clone();
+        for (group_id, shares) in uncast {
+            let prev_votes = max_votes.get(group_id).cloned().unwrap_or_default();
+            max_votes.insert(*group_id, prev_votes + shares.clone());
+        }
+
+        let reached = threshold.is_reached(total, votes, weights);
+
+        if reached == threshold.is_reached(total, &max_votes, weights) {
+            Some(reached)
+        } else {
+            None
+        }
+    }
+
+    fn is_in_anti_sniping_window(voting: &Voting, vc: &VotingConfig, timestamp: u64) -> bool {
+        let settings = match &vc.get_round_settings().anti_sniping {
+            Some(s) => s,
+            None => return false,
+        };
+
+        // round 0 only collects approvals and rejections, so there is nothing to snipe
+        let round = match voting.get_status() {
+            VotingStatus::Round(r) if *r > 0 => *r,
+            _ => return false,
+        };
+
+        let round_ends_at = match voting.get_round_ends_at() {
+            Some(it) => it,
+            None => return false,
+        };
+
+        voting.count_round_extensions(round) < settings.max_extensions
+            && timestamp + settings.window >= round_ends_at
+    }
+
+    fn extend_round(voting: &mut Voting, vc: &VotingConfig, caller: Principal, timestamp: u64) {
+        let round = match voting.get_status() {
+            VotingStatus::Round(r) => *r,
+            _ => unreachable!(),
+        };
+
+        let extension = vc
+            .get_round_settings()
+            .anti_sniping
+            .as_ref()
+            .unwrap()
+            .extension;
+        let round_ends_at = voting.get_round_ends_at().unwrap() + extension;
+
+        voting.add_round_extension(
+            RoundExtension {
+                round,
+                caused_by: caller,
+                timestamp,
+                round_ends_at,
+            },
+            timestamp,
+        );
+        CronService::reschedule_round_end(voting, round_ends_at, timestamp);
+    }
+
+    fn split_choices_by_thresholds(
+        vc: &VotingConfig,
+        total_votes_by_group: &BTreeMap<GroupId, Shares>,
+        votes_by_choice: Vec<(ChoiceId, BTreeMap<GroupId, Shares>)>,
+    ) -> (Vec<ChoiceId>, Vec<ChoiceId>) {
+        let mut win = vec![];
+        let mut next_round = vec![];
+
+        for (choice_id, votes_per_group) in votes_by_choice {
+            if vc.get_win_threshold().is_reached(
+                total_votes_by_group,
+                &votes_per_group,
+                vc.get_group_weights(),
+            ) {
+                win.push(choice_id);
+            } else if vc.get_next_round_threshold().is_reached(
+                total_votes_by_group,
+                &votes_per_group,
+                vc.get_group_weights(),
+            ) {
+                next_round.push(choice_id);
+            }
+        }
+
+        (win, next_round)
+    }
+
+    pub async fn retry_execution(
+        id: &VotingId,
+        choice_id: &ChoiceId,
+        timestamp: u64,
+    ) -> Result<ProgramExecutionResult, VotingError> {
         let mut voting = VotingService::get_voting(id)?;
-        let vc = VotingConfigService::get_voting_config(voting.get_voting_config_id()).unwrap();
 
-        if !matches!(voting.get_status(), VotingStatus::Round(_)) {
-            return Err(VotingError::VotingOnlyAllowedDuringRounds);
+        if !matches!(voting.get_status(), VotingStatus::Executed) {
+            return Err(VotingError::VotingInInvalidStatus(*id));
+        }
+
+        let failed_call_index = voting
+            .get_execution_results()
+            .get(choice_id)
+            .and_then(|it| it.get_failed_call_index())
+            .ok_or(VotingError::ExecutionNotFailed(*choice_id))?;
+
+        let program = ChoiceService::get_choice(choice_id)
+            .map_err(|_| VotingError::ChoiceNotFound(*choice_id))?
+            .get_program()
+            .clone();
+
+        if !VotingConfigService::does_program_fit(voting.get_voting_config(), &program) {
+            return Err(VotingError::ProgramNotAllowedByVotingConfig);
         }
 
-        let (choices, shares_info) = match vote {
-            Vote::Rejection(s) => {
-                VotingService::assert_can_reject(&vc, &s.shares_info.group_id)?;
+        // the same program can't be retried twice at once
+        if !voting.start_execution_retry(*choice_id, timestamp) {
+            return Err(VotingError::ExecutionAlreadyRetrying(*choice_id));
+        }
+
+        let prev_result = voting
+            .get_execution_results()
+            .get(choice_id)
+            .cloned()
+            .unwrap();
+        Voting::repo().save(voting);
 
-                let balance = s.shares_info.balance.clone();
-                let rejection_choice = Choice::repo().get(&voting.get_rejection_choice()).unwrap();
+        let result = match (prev_result, program.execute_from(failed_call_index).await) {
+            (
+                ProgramExecutionResult::RemoteCallSequence(mut results),
+                ProgramExecutionResult::RemoteCallSequence(new_results),
+            ) => {
+                results.truncate(failed_call_index);
+                results.extend(new_results);
 
-                (vec![(rejection_choice, balance)], s.shares_info)
+                ProgramExecutionResult::RemoteCallSequence(results)
             }
-            Vote::Approval(s) => {
-                VotingService::assert_can_approve(&vc, &s.shares_info.group_id)?;
+            (_, new_result) => new_result,
+        };
+
+        let mut voting = VotingService::get_voting(id)?;
+        voting.finish_execution_retry(*choice_id, result.clone(), timestamp);
+
+        EventsService::emit_program_executed_event(
+            voting.get_proposer(),
+            ProgramExecutedWith::WithVotingConfig(*voting.get_voting_config_id()),
+            program,
+            result.clone(),
+            timestamp,
+        );
+
+        Voting::repo().save(voting);
+
+        Ok(result)
+    }
 
-                let balance = s.shares_info.balance.clone();
-                let approval_choice = Choice::repo().get(&voting.get_approval_choice()).unwrap();
+    fn finish_success(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        voting.finish_success(timestamp);
+
+        if vc.get_veto_threshold().is_some() {
+            let veto_choice = ChoiceService::create_veto_choice(voting.get_id().unwrap());
+            voting.init_veto_choice(veto_choice);
+        }
+
+        CronService::schedule_voting_execution(voting, vc, timestamp);
+    }
 
-                (vec![(approval_choice, balance)], s.shares_info)
+    fn try_veto(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let (veto_choice_id, veto_threshold) =
+            match (voting.get_veto_choice(), vc.get_veto_threshold()) {
+                (Some(id), Some(th)) => (id, th),
+                _ => return,
+            };
+
+        let veto_choice = Choice::repo().get(&veto_choice_id).unwrap();
+        let veto_votes_per_group = ChoiceService::list_total_voted_shares_by_group(&veto_choice);
+
+        if veto_threshold.is_reached(
+            voting.get_total_voting_power_by_group(),
+            &veto_votes_per_group,
+            vc.get_group_weights(),
+        ) {
+            if let Some(task_id) = voting.get_cron_task() {
+                cron_dequeue(task_id);
             }
-            Vote::Common(m) => {
-                VotingService::assert_can_vote(&vc, &m.shares_info.group_id)?;
 
-                let total_fraction: BigDecimal = m.vote.iter().map(|(_, f)| f.0.abs()).sum();
+            voting.veto(timestamp);
+            EventsService::emit_voting_finished_event(voting, timestamp);
+        }
+    }
 
-                if total_fraction > BigDecimal::one() {
-                    return Err(VotingError::VoteFractionTooBig);
-                }
+    fn decide_over_subscription(
+        vc: &VotingConfig,
+        tally: &RoundTally,
+        win: Vec<ChoiceId>,
+    ) -> RoundOutcome {
+        let slots_left = tally.winners_left;
+        let mut scores: Vec<(ChoiceId, Fraction)> = win
+            .into_iter()
+            .map(|id| {
+                let score = VotingService::calculate_score(
+                    &tally.total_voting_power_by_group,
+                    &tally.votes_by_choice[&id],
+                    vc.get_group_weights(),
+                );
+
+                (id, score)
+            })
+            .collect();
 
-                let votes = m
-                    .vote
-                    .into_iter()
-                    .map(|(id, f)| {
-                        let shares: Nat = (Fraction(f.0.abs())
-                            * Fraction::from(m.shares_info.balance.clone()))
-                        .into();
-                        (id, shares)
-                    })
-                    .collect::<Vec<_>>();
+        // ties are broken by choice id, so the outcome is always deterministic
+        scores.sort_by(|(id_a, a), (id_b, b)| b.cmp(a).then(id_a.cmp(id_b)));
 
-                let choices = votes
-                    .into_iter()
-                    .map(|(id, shares)| {
-                        assert!(voting.get_choices().contains(&id));
-                        let choice = Choice::repo().get(&id).unwrap();
+        // choices scored the same as the last winner slot can't be told apart
+        let cutoff_score = scores[slots_left as usize].1.clone();
+        let tied_count = scores
+            .iter()
+            .filter(|(_, score)| *score == cutoff_score)
+            .count();
 
-                        (choice, shares)
-                    })
-                    .collect();
+        let mut policy = vc.get_over_subscription_policy();
+
+        // without a tie for the last slots, or if every remaining choice is tied, a run-off has
+        // nothing to decide
+        if matches!(policy, OverSubscriptionPolicy::RunOff)
+            && (slots_left == 0
+                || scores[slots_left as usize - 1].1 != cutoff_score
+                || tied_count == tally.votes_by_choice.len())
+        {
+            policy = OverSubscriptionPolicy::HighestShares;
+        }
 
-                (choices, m.shares_info)
+        let over_subscription = Some(OverSubscription {
+            policy,
+            slots_left,
+            scores: scores.clone(),
+        });
+
+        let resolution = match policy {
+            OverSubscriptionPolicy::HighestShares => {
+                let (winners, losers) = scores
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .enumerate()
+                    .partition::<Vec<_>, _>(|(idx, _)| (*idx as u32) < slots_left);
+
+                RoundResolution {
+                    winners: winners.into_iter().map(|(_, id)| id).collect(),
+                    losers: losers.into_iter().map(|(_, id)| id).collect(),
+                    over_subscription,
+                    next: RoundNext::Success,
+                }
             }
+            // choices above the tie take their slots, only the tied ones continue
+            OverSubscriptionPolicy::RunOff => RoundResolution {
+                winners: scores
+                    .iter()
+                    .filter(|(_, score)| *score > cutoff_score)
+                    .map(|(id, _)| *id)
+                    .collect(),
+                losers: tally
+                    .votes_by_choice
+                    .keys()
+                    .filter(|id| {
+                        !scores
+                            .iter()
+                            .any(|(score_id, score)| score_id == *id && *score >= cutoff_score)
+                    })
+                    .cloned()
+                    .collect(),
+                over_subscription,
+                next: RoundNext::NextRound,
+            },
+            OverSubscriptionPolicy::Fail => RoundResolution {
+                winners: Vec::new(),
+                losers: scores.into_iter().map(|(id, _)| id).collect(),
+                over_subscription,
+                next: RoundNext::Fail(String::from("Too many winners")),
+            },
         };
 
-        VotingService::assert_shares_info_valid(&shares_info, &voting, caller)?;
+        RoundOutcome::Resolved(resolution)
+    }
 
-        VotingService::remove_prev_vote(&voting, shares_info.group_id, shares_info.principal_id);
-        VotingService::put_vote(
-            &mut voting,
-            choices,
-            shares_info.total_supply,
-            shares_info.group_id,
-            shares_info.principal_id,
-            timestamp,
-        );
+    // every elimination is recorded as a separate round, so the voting history shows the whole runoff
+    fn run_instant_runoff(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
+        let mut prev_scores = Vec::new();
 
-        Voting::repo().save(voting);
+        loop {
+            let tally = VotingService::tally_round(voting);
+            let round = tally.round;
 
-        Ok(())
-    }
+            let mut win = vec![];
+            let mut scores = BTreeMap::new();
 
-    pub fn try_finish_voting(voting: &mut Voting, vc: &VotingConfig, timestamp: u64) {
-        match voting.get_status() {
-            VotingStatus::Round(r) => {
-                let rejection_choice = Choice::repo().get(&voting.get_rejection_choice()).unwrap();
-                let rejection_votes_per_group =
-                    ChoiceService::list_total_voted_shares_by_group(&rejection_choice);
-
-                if vc.get_rejection_threshold().is_reached(
-                    voting.get_total_voting_power_by_group(),
-                    &rejection_votes_per_group,
+            for (id, votes_per_group) in &tally.votes_by_choice {
+                if vc.get_win_threshold().is_reached(
+                    &tally.total_voting_power_by_group,
+                    votes_per_group,
+                    vc.get_group_weights(),
                 ) {
-                    voting.reject(timestamp);
-                    return;
+                    win.push(*id);
                 }
 
-                if *r == 0 {
-                    // TODO: also check winners/choices compatibility
-                    if let Some(cc) = vc.get_choices_count() {
-                        if !cc.contains(voting.get_choices().len() as u32) {
-                            voting.finish_fail(String::from("Choices count is invalid"), timestamp);
-                            return;
-                        }
-                    }
+                let score = VotingService::calculate_score(
+                    &tally.total_voting_power_by_group,
+                    votes_per_group,
+                    vc.get_group_weights(),
+                );
+                scores.insert(*id, score);
+            }
 
-                    let approval_choice =
-                        Choice::repo().get(&voting.get_approval_choice()).unwrap();
-                    let approval_votes_per_group =
-                        ChoiceService::list_total_voted_shares_by_group(&approval_choice);
-
-                    if vc.get_approval_threshold().is_reached(
-                        voting.get_total_voting_power_by_group(),
-                        &approval_votes_per_group,
-                    ) {
-                        voting.next_round(timestamp);
-                        CronService::schedule_round_start(voting, vc, timestamp);
-                    } else {
-                        voting.finish_fail(String::from("Not enough approvals"), timestamp);
-                    }
+            if !win.is_empty() {
+                if win.len() as u32 > tally.winners_left {
+                    let outcome = VotingService::decide_over_subscription(vc, &tally, win);
+                    VotingService::apply_round_outcome(voting, vc, round, outcome, timestamp);
 
                     return;
                 }
 
-                let mut total_used_votes_by_group = BTreeMap::<GroupId, Shares>::new();
+                let all_slots_taken = win.len() as u32 == tally.winners_left;
+                let mut new_winners = RoundResult::new(round);
 
-                let choices: Vec<_> = voting
-                    .get_choices()
-                    .iter()
-                    .map(|id| Choice::repo().get(id).unwrap())
-                    .collect();
-
-                for choice in &choices {
-                    let common_votes_per_group =
-                        ChoiceService::list_total_voted_shares_by_group(choice);
-
-                    // sum total used vp for all gops to understand if the quorum was reached
-                    for (group_id, votes) in common_votes_per_group {
-                        let prev_votes = total_used_votes_by_group
-                            .get(&group_id)
-                            .cloned()
-                            .unwrap_or_default();
-                        total_used_votes_by_group.insert(group_id, prev_votes + votes);
-                    }
+                for choice_id in win {
+                    voting.remove_choice(&choice_id, timestamp);
+                    new_winners.add_choice(choice_id);
                 }
 
-                if vc.get_quorum_threshold().is_reached(
-                    voting.get_total_voting_power_by_group(),
-                    &total_used_votes_by_group,
-                ) {
-                    let mut win = vec![];
-                    let mut next_round = vec![];
-
-                    for choice in choices {
-                        let mut won = false;
-                        let votes_per_group =
-                            ChoiceService::list_total_voted_shares_by_group(&choice);
-
-                        if vc
-                            .get_win_threshold()
-                            .is_reached(voting.get_total_voting_power_by_group(), &votes_per_group)
-                        {
-                            win.push(choice.get_id().unwrap());
-                            won = true;
-                        }
-
-                        if !won
-                            && vc.get_next_round_threshold().is_reached(
-                                voting.get_total_voting_power_by_group(),
-                                &votes_per_group,
-                            )
-                        {
-                            next_round.push(choice.get_id().unwrap());
-                        }
-                    }
+                voting.add_winner(new_winners, timestamp);
 
-                    if win.is_empty() && next_round.is_empty() {
+                if all_slots_taken {
+                    VotingService::finish_success(voting, vc, timestamp);
+                    return;
+                }
+            } else {
+                let loser = match VotingService::pick_instant_runoff_loser(&scores, &prev_scores) {
+                    Some(id) => id,
+                    None => {
                         voting
                             .finish_fail(String::from("No worthy choices to continue"), timestamp);
-                    } else if !win.is_empty() {
-                        // TODO: what if we have more winners than we need?
+                        return;
+                    }
+                };
 
-                        let mut new_winners = RoundResult::new(*r);
-                        let mut cur_winners_count: u32 =
-                            voting.get_winners().iter().map(|it| it.len() as u32).sum();
+                let mut new_losers = RoundResult::new(round);
+                voting.remove_choice(&loser, timestamp);
+                new_losers.add_choice(loser);
 
-                        for choice_id in win {
-                            voting.remove_choice(&choice_id, timestamp);
-                            new_winners.add_choice(choice_id);
+                for (group_id, principal, choice_id, shares) in
+                    voting.transfer_ranked_ballots(new_losers.get_choices(), timestamp)
+                {
+                    let mut choice = Choice::repo().get(&choice_id).unwrap();
+                    let mut token = ChoiceService::get_token_for_group(&mut choice, group_id);
+                    ChoiceService::cast_vote(&mut token, principal, shares);
 
-                            cur_winners_count += 1;
+                    Token::repo().save(token);
+                    Choice::repo().save(choice);
+                }
 
-                            if cur_winners_count == voting.get_winners_need() {
-                                voting.add_winner(new_winners, timestamp);
-                                voting.finish_success(timestamp);
-                                CronService::schedule_voting_execution(voting, timestamp);
+                voting.add_loser(new_losers, timestamp);
+            }
 
-                                return;
-                            }
-                        }
+            prev_scores.push(scores);
 
-                        voting.add_winner(new_winners, timestamp);
-                        voting.next_round(timestamp);
-                        CronService::schedule_round_start(voting, vc, timestamp);
-                    } else {
-                        let mut new_losers = RoundResult::new(*r);
-
-                        for choice_id in voting.get_choices().clone() {
-                            if !next_round.contains(&choice_id) {
-                                voting.remove_choice(&choice_id, timestamp);
-                                new_losers.add_choice(choice_id);
-                            }
-                        }
-
-                        voting.add_loser(new_losers, timestamp);
-
-                        if voting.get_winners().len() + voting.get_choices().len()
-                            < voting.get_winners_need() as usize
-                        {
-                            voting.finish_fail(
-                                String::from("Not enough choices to continue"),
-                                timestamp,
-                            );
-                        } else {
-                            voting.next_round(timestamp);
-                            CronService::schedule_round_start(voting, vc, timestamp);
-                        }
-                    }
-                } else {
-                    voting.finish_fail(String::from("Not enough votes"), timestamp);
-                }
+            let choices_left = voting.get_choices().len() as u32;
+
+            if VotingService::count_winners(voting) + choices_left < voting.get_winners_need() {
+                voting.finish_fail(String::from("Not enough choices to continue"), timestamp);
+                return;
+            }
+
+            voting.next_round(timestamp);
+            voting.start_round(timestamp);
+        }
+    }
+
+    // only one choice is eliminated at a time - ties for the lowest score are broken by the scores
+    // of previous eliminations (the latest first), and then by id (the latest choice goes first)
+    fn pick_instant_runoff_loser(
+        scores: &BTreeMap<ChoiceId, Fraction>,
+        prev_scores: &[BTreeMap<ChoiceId, Fraction>],
+    ) -> Option<ChoiceId> {
+        scores.keys().cloned().min_by(|a, b| {
+            std::iter::once(scores)
+                .chain(prev_scores.iter().rev())
+                .map(|it| it.get(a).cmp(&it.get(b)))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| b.cmp(a))
+        })
+    }
+
+    // sum of the fractions of each group's voting power given to the choice, each multiplied by
+    // the group's weight
+    fn calculate_score(
+        total: &BTreeMap<GroupId, Shares>,
+        voted: &BTreeMap<GroupId, Shares>,
+        weights: &BTreeMap<GroupId, Fraction>,
+    ) -> Fraction {
+        let mut score = Fraction::default();
+
+        for (group_id, votes) in voted {
+            let total_shares = total.get(group_id).cloned().unwrap_or_default();
+            let weight = weights
+                .get(group_id)
+                .cloned()
+                .unwrap_or_else(|| Fraction::from(1usize));
+
+            if total_shares != Shares::default() {
+                score += weight * Fraction::from(votes.clone()) / Fraction::from(total_shares);
             }
-            _ => unreachable!("CRON BUG DETECTED, REPORT TO SASHA"),
         }
+
+        score
+    }
+
+    fn count_winners(voting: &Voting) -> u32 {
+        voting.get_winners().iter().map(|it| it.len() as u32).sum()
     }
 
     pub fn reset_approval_choice(voting: &Voting) {
@@ -304,11 +1793,38 @@ impl VotingService {
         Ok(result)
     }
 
+    pub fn get_voting_spent_shares(
+        voting_id: &VotingId,
+    ) -> Result<BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>, VotingError> {
+        let voting = VotingService::get_voting(voting_id)?;
+        let choices = VotingService::get_voting_choices(&voting);
+
+        let result = choices
+            .iter()
+            .map(|it| {
+                (
+                    it.get_id().unwrap(),
+                    ChoiceService::list_total_spent_shares_by_group(it),
+                )
+            })
+            .collect();
+
+        Ok(result)
+    }
+
     fn get_voting_choices(voting: &Voting) -> Vec<Choice> {
         let mut choices = Vec::new();
         choices.push(Choice::repo().get(&voting.get_approval_choice()).unwrap());
         choices.push(Choice::repo().get(&voting.get_rejection_choice()).unwrap());
 
+        if let Some(id) = voting.get_abstain_choice() {
+            choices.push(Choice::repo().get(&id).unwrap());
+        }
+
+        if let Some(id) = voting.get_veto_choice() {
+            choices.push(Choice::repo().get(&id).unwrap());
+        }
+
         for id in voting.get_choices() {
             choices.push(Choice::repo().get(id).unwrap());
         }
@@ -330,8 +1846,8 @@ impl VotingService {
 
     fn remove_prev_vote(voting: &Voting, group_id: GroupId, principal: Principal) {
         let choices = match voting.get_status() {
-            VotingStatus::Round(r) => {
-                if *r == 0 {
+            VotingStatus::Round(r) | VotingStatus::Reveal(r) => {
+                let mut list: Vec<Choice> = if *r == 0 {
                     vec![
                         ChoiceService::get_choice(&voting.get_approval_choice()).unwrap(),
                         ChoiceService::get_choice(&voting.get_rejection_choice()).unwrap(),
@@ -346,7 +1862,16 @@ impl VotingService {
                     list.push(ChoiceService::get_choice(&voting.get_rejection_choice()).unwrap());
 
                     list
+                };
+
+                if let Some(id) = voting.get_abstain_choice() {
+                    list.push(ChoiceService::get_choice(&id).unwrap());
                 }
+
+                list
+            }
+            VotingStatus::AwaitingExecution => {
+                vec![ChoiceService::get_choice(&voting.get_veto_choice().unwrap()).unwrap()]
             }
             _ => unreachable!(),
         };
@@ -354,6 +1879,8 @@ impl VotingService {
         for mut choice in choices {
             let mut token = ChoiceService::get_token_for_group(&mut choice, group_id);
             ChoiceService::revert_vote(&mut token, principal);
+            ChoiceService::revert_spent_shares(&choice, &group_id, principal);
+            ChoiceService::revert_raw_shares(&choice, &group_id, principal);
 
             Token::repo().save(token);
             Choice::repo().save(choice);
@@ -362,15 +1889,35 @@ impl VotingService {
 
     fn put_vote(
         voting: &mut Voting,
-        choices: Vec<(Choice, Shares)>,
+        choices: Vec<(Choice, Shares, Option<Shares>)>,
+        quadratic: bool,
         total_supply: Shares,
         group_id: GroupId,
         principal: Principal,
         timestamp: u64,
     ) {
-        for (mut choice, shares) in choices {
+        for (mut choice, shares, raw_shares_opt) in choices {
             let mut token = ChoiceService::get_token_for_group(&mut choice, group_id);
-            ChoiceService::cast_vote(&mut token, principal, shares);
+
+            if let Some(raw_shares) = raw_shares_opt {
+                let mut raw_token = ChoiceService::get_raw_token_for_group(&mut choice, group_id);
+                ChoiceService::cast_vote(&mut raw_token, principal, raw_shares);
+                Token::repo().save(raw_token);
+            }
+
+            if quadratic {
+                // the voter pays with shares, but the choice only receives the square root of them
+                let votes = Nat(shares.0.sqrt());
+
+                let mut spent_token =
+                    ChoiceService::get_spent_token_for_group(&mut choice, group_id);
+                ChoiceService::cast_vote(&mut spent_token, principal, shares);
+                Token::repo().save(spent_token);
+
+                ChoiceService::cast_vote(&mut token, principal, votes);
+            } else {
+                ChoiceService::cast_vote(&mut token, principal, shares);
+            }
 
             Token::repo().save(token);
             Choice::repo().save(choice);
@@ -392,6 +1939,41 @@ impl VotingService {
         Ok(())
     }
 
+    fn assert_open_votings_limit_not_reached(
+        vc: &VotingConfig,
+        proposer: Principal,
+    ) -> Result<(), VotingError> {
+        if let Some(max) = vc.get_max_open_votings_per_proposer() {
+            let open = Voting::repo().count_open_votings_of(&proposer, &vc.get_id().unwrap());
+
+            if open >= max {
+                return Err(VotingError::TooManyOpenVotings(max));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn assert_ranking_valid(voting: &Voting, ranking: &Vec<ChoiceId>) -> Result<(), VotingError> {
+        if ranking.is_empty() {
+            return Err(VotingError::InvalidVote);
+        }
+
+        let mut unique = BTreeSet::new();
+
+        for choice_id in ranking {
+            if !voting.get_choices().contains(choice_id) {
+                return Err(VotingError::ChoiceNotFound(*choice_id));
+            }
+
+            if !unique.insert(*choice_id) {
+                return Err(VotingError::InvalidVote);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn is_editable(voting: &Voting) -> bool {
         match voting.get_status() {
             VotingStatus::Round(r) => *r == 0,
@@ -399,6 +1981,22 @@ impl VotingService {
         }
     }
 
+    // abstentions only matter for the quorum
+    fn assert_can_abstain(vc: &VotingConfig, group_id: &GroupId) -> Result<(), VotingError> {
+        if vc.get_quorum_threshold().list_groups().contains(group_id) {
+            Ok(())
+        } else {
+            Err(VotingError::VoterCantAbstain)
+        }
+    }
+
+    fn assert_can_veto(vc: &VotingConfig, group_id: &GroupId) -> Result<(), VotingError> {
+        match vc.get_veto_threshold() {
+            Some(veto) if veto.list_groups().contains(group_id) => Ok(()),
+            _ => Err(VotingError::VoterCantVeto),
+        }
+    }
+
     fn assert_can_approve(vc: &VotingConfig, group_id: &GroupId) -> Result<(), VotingError> {
         if vc.get_approval_threshold().list_groups().contains(group_id) {
             Ok(())
@@ -431,15 +2029,98 @@ impl VotingService {
         }
     }
 
+    async fn fetch_shares_info(
+        id: &VotingId,
+        group_id: GroupId,
+        principal: Principal,
+    ) -> Result<SharesInfo, VotingError> {
+        VotingService::fetch_shares_info_opt(id, group_id, principal)
+            .await?
+            .ok_or(VotingError::InsufficientSharesBalance)
+    }
+
+    async fn fetch_shares_info_opt(
+        id: &VotingId,
+        group_id: GroupId,
+        principal: Principal,
+    ) -> Result<Option<SharesInfo>, VotingError> {
+        let voting = VotingService::get_voting(id)?;
+
+        if let Some(shares_info) = voting.get_cached_shares_info(&group_id, &principal) {
+            return Ok(Some(shares_info.clone()));
+        }
+
+        let shares_info = match HistoryLedgerService::get_shares_info_of_at(
+            group_id,
+            principal,
+            voting.get_created_at(),
+        )
+        .await
+        .map_err(VotingError::HistoryLedgerError)?
+        {
+            Some(it) => it,
+            None => return Ok(None),
+        };
+
+        // the voting could have changed while we were waiting for the response
+        let mut voting = VotingService::get_voting(id)?;
+        voting.cache_shares_info(shares_info.clone());
+        Voting::repo().save(voting);
+
+        Ok(Some(shares_info))
+    }
+
+    async fn fetch_vote_shares_info(
+        id: &VotingId,
+        mut vote: Vote,
+        caller: Principal,
+    ) -> Result<(Vote, Vec<SharesInfo>), VotingError> {
+        let group_id = vote.get_shares_info().group_id;
+        let shares_info = VotingService::fetch_shares_info(id, group_id, caller).await?;
+        vote.set_shares_info(shares_info);
+
+        let delegated_shares_info =
+            VotingService::fetch_delegated_shares_info(id, group_id, caller).await?;
+
+        Ok((vote, delegated_shares_info))
+    }
+
+    // the delegate can't pick whom to vote for - every delegator, who didn't vote by themselves
+    // and had shares when the voting was created, is included
+    async fn fetch_delegated_shares_info(
+        id: &VotingId,
+        group_id: GroupId,
+        delegate: Principal,
+    ) -> Result<Vec<SharesInfo>, VotingError> {
+        let voting = VotingService::get_voting(id)?;
+        let delegators = VoteDelegationService::list_delegators_of(
+            &delegate,
+            group_id,
+            *voting.get_voting_config_id(),
+        );
+
+        let mut result = Vec::new();
+
+        for delegator in delegators {
+            if voting.has_direct_vote(&group_id, &delegator) {
+                continue;
+            }
+
+            let info_opt = VotingService::fetch_shares_info_opt(id, group_id, delegator).await?;
+
+            if let Some(info) = info_opt.filter(|it| it.balance != Shares::default()) {
+                result.push(info);
+            }
+        }
+
+        Ok(result)
+    }
+
     fn assert_shares_info_valid(
         shares_info: &SharesInfo,
         voting: &Voting,
         caller: Principal,
     ) -> Result<(), VotingError> {
-        if !shares_info.is_signature_valid() {
-            return Err(VotingError::SharesInfoSignatureInvalid);
-        }
-
         if voting.get_created_at() != shares_info.timestamp {
             return Err(VotingError::SharesInfoTimestampInvalid);
         }
@@ -454,4 +2135,299 @@ impl VotingService {
 
         Ok(())
     }
+
+    fn assert_delegated_shares_info_valid(
+        shares_info: &SharesInfo,
+        caller_shares_info: &SharesInfo,
+        voting: &Voting,
+        caller: Principal,
+    ) -> Result<(), VotingError> {
+        if voting.get_created_at() != shares_info.timestamp {
+            return Err(VotingError::SharesInfoTimestampInvalid);
+        }
+
+        if shares_info.balance == Shares::default() {
+            return Err(VotingError::InsufficientSharesBalance);
+        }
+
+        let delegate = VoteDelegationService::get_delegate_of(
+            &shares_info.principal_id,
+            caller_shares_info.group_id,
+            *voting.get_voting_config_id(),
+        );
+
+        if shares_info.group_id != caller_shares_info.group_id || delegate != Some(caller) {
+            return Err(VotingError::VotingPowerNotDelegated(
+                shares_info.principal_id,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::voting::model::Voting;
+    use crate::repository::voting::types::{PipelineStage, VotingStatus};
+    use crate::repository::voting_config::model::VotingConfig;
+    use crate::repository::voting_config::types::{
+        Fraction, FractionOf, OverSubscriptionPolicy, ProposalBond, QuantityOf, RejectedBondPolicy,
+        RoundSettings, TallyMode, Target, ThresholdValue, VotingConfigParams, VotingConfigUpdate,
+    };
+    use crate::service::voting::types::{
+        RoundNext, RoundOutcome, RoundTally, SingleChoiceVote, Vote, VotingError, VotingService,
+    };
+    use candid::Principal;
+    use shared::mvc::Model;
+    use shared::types::history_ledger::SharesInfo;
+    use shared::types::wallet::{ChoiceId, GroupId, Shares};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn fraction_of_group(fraction: f64) -> ThresholdValue {
+        ThresholdValue::FractionOf(FractionOf {
+            fraction: Fraction::from(fraction),
+            target: Target::Group(1),
+        })
+    }
+
+    fn voting_config(
+        early_finish: bool,
+        over_subscription: OverSubscriptionPolicy,
+    ) -> VotingConfig {
+        let mut vc = VotingConfig::new(VotingConfigParams {
+            name: String::from("Test"),
+            description: String::from("No veto threshold"),
+            choices_count: None,
+            winners_count: None,
+            permissions: BTreeSet::new(),
+            round: RoundSettings {
+                round_duration: 100,
+                round_delay: 0,
+                execution_delay: 0,
+                anti_sniping: None,
+                early_finish,
+            },
+            tally_mode: TallyMode::Common,
+            commit_reveal: None,
+            over_subscription,
+            voting_power_cap: None,
+            group_weights: BTreeMap::new(),
+            proposal_bond: None,
+            max_open_votings_per_proposer: None,
+            veto: None,
+            approval: ThresholdValue::QuantityOf(QuantityOf {
+                quantity: Shares::from(0),
+                target: Target::Group(1),
+            }),
+            quorum: fraction_of_group(0.5),
+            rejection: fraction_of_group(0.5),
+            win: fraction_of_group(0.2),
+            next_round: fraction_of_group(0.1),
+        })
+        .unwrap();
+        vc._init_id(1);
+
+        vc
+    }
+
+    fn voting(vc: VotingConfig) -> Voting {
+        let mut voting = Voting::new(
+            vc,
+            String::from("Test voting"),
+            String::from("Test voting"),
+            1,
+            Principal::anonymous(),
+            0,
+        )
+        .unwrap();
+        voting._init_id(1);
+        voting.init_rejection_and_approval_choices(100, 101);
+
+        voting
+    }
+
+    #[test]
+    fn direct_votes_dont_outlive_the_round() {
+        let mut voting = voting(voting_config(false, OverSubscriptionPolicy::HighestShares));
+        let voter = Principal::management_canister();
+
+        voting.set_direct_vote(1, voter, Shares::from(10), 1);
+        assert!(voting.has_direct_vote(&1, &voter));
+
+        voting.next_round(2);
+        assert!(!voting.has_direct_vote(&1, &voter));
+    }
+
+    #[test]
+    fn early_finish_doesnt_allow_to_change_the_vote() {
+        let voter = Principal::management_canister();
+        let approval = |voting: &Voting| {
+            let vote = Vote::Approval(SingleChoiceVote {
+                shares_info: SharesInfo {
+                    balance: Shares::from(10),
+                    total_supply: Shares::from(100),
+                    timestamp: 0,
+                    group_id: 1,
+                    principal_id: voter,
+                    signature: None,
+                },
+            });
+
+            VotingService::validate_vote(voting, vote, Vec::new(), voter, 1)
+        };
+
+        let mut early = voting(voting_config(true, OverSubscriptionPolicy::HighestShares));
+        assert!(approval(&early).is_ok());
+
+        early.set_direct_vote(1, voter, Shares::from(10), 1);
+        assert!(matches!(
+            approval(&early),
+            Err(VotingError::VoteAlreadyCast)
+        ));
+
+        let mut common = voting(voting_config(false, OverSubscriptionPolicy::HighestShares));
+        common.set_direct_vote(1, voter, Shares::from(10), 1);
+        assert!(approval(&common).is_ok());
+    }
+
+    #[test]
+    fn early_finish_without_veto_awaits_execution() {
+        let vc = voting_config(true, OverSubscriptionPolicy::HighestShares);
+        let mut voting = voting(vc.clone());
+
+        voting.finish_success(1);
+        VotingService::try_veto(&mut voting, &vc, 2);
+
+        assert!(matches!(
+            voting.get_status(),
+            VotingStatus::AwaitingExecution
+        ));
+    }
+
+    fn scores(scores: &[(ChoiceId, f64)]) -> BTreeMap<ChoiceId, Fraction> {
+        scores
+            .iter()
+            .map(|(id, score)| (*id, Fraction::from(*score)))
+            .collect()
+    }
+
+    #[test]
+    fn instant_runoff_ties_are_broken_by_previous_scores() {
+        let cur = scores(&[(1, 0.2), (2, 0.2), (3, 0.6)]);
+        let prev = vec![
+            scores(&[(1, 0.1), (2, 0.3), (3, 0.5), (4, 0.1)]),
+            scores(&[(1, 0.2), (2, 0.2), (3, 0.5), (4, 0.1)]),
+        ];
+
+        assert_eq!(
+            VotingService::pick_instant_runoff_loser(&cur, &prev),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn instant_runoff_full_ties_eliminate_the_latest_choice() {
+        let cur = scores(&[(1, 0.5), (2, 0.5)]);
+
+        assert_eq!(VotingService::pick_instant_runoff_loser(&cur, &[]), Some(2));
+        assert_eq!(
+            VotingService::pick_instant_runoff_loser(&BTreeMap::new(), &[]),
+            None
+        );
+    }
+
+    fn shares_of_group(shares: u64) -> BTreeMap<GroupId, Shares> {
+        vec![(1, Shares::from(shares))].into_iter().collect()
+    }
+
+    fn over_subscribed_tally() -> RoundTally {
+        RoundTally {
+            round: 1,
+            winners_left: 2,
+            total_voting_power_by_group: shares_of_group(100),
+            approval_votes: BTreeMap::new(),
+            rejection_votes: BTreeMap::new(),
+            votes_by_choice: vec![
+                (1, shares_of_group(40)),
+                (2, shares_of_group(25)),
+                (3, shares_of_group(25)),
+                (4, shares_of_group(5)),
+            ]
+            .into_iter()
+            .collect(),
+            spent_shares_by_group: shares_of_group(95),
+        }
+    }
+
+    #[test]
+    fn over_subscription_runs_off_only_tied_choices() {
+        let vc = voting_config(false, OverSubscriptionPolicy::RunOff);
+        let decision = VotingService::decide_round(&vc, &over_subscribed_tally());
+
+        assert_eq!(decision.win, vec![1, 2, 3]);
+        match decision.outcome {
+            RoundOutcome::Resolved(it) => {
+                assert_eq!(it.winners, vec![1]);
+                assert_eq!(it.losers, vec![4]);
+                assert!(matches!(it.next, RoundNext::NextRound));
+            }
+            _ => panic!("The round should be resolved"),
+        }
+    }
+
+    #[test]
+    fn over_subscription_seats_highest_shares() {
+        let vc = voting_config(false, OverSubscriptionPolicy::HighestShares);
+        let decision = VotingService::decide_round(&vc, &over_subscribed_tally());
+
+        match decision.outcome {
+            RoundOutcome::Resolved(it) => {
+                assert_eq!(it.winners, vec![1, 2]);
+                assert_eq!(it.losers, vec![3]);
+                assert!(matches!(it.next, RoundNext::Success));
+            }
+            _ => panic!("The round should be resolved"),
+        }
+    }
+
+    #[test]
+    fn vote_for_unknown_choice_is_an_error() {
+        let vc = voting_config(false, OverSubscriptionPolicy::HighestShares);
+        let voting = voting(vc);
+
+        let vote = vec![(42, Fraction::from(0.5))].into_iter().collect();
+
+        assert!(matches!(
+            VotingService::get_common_vote_weights(&voting, vote),
+            Err(VotingError::ChoiceNotFound(42))
+        ));
+    }
+
+    #[test]
+    fn next_pipeline_stage_doesnt_lock_another_bond() {
+        let mut vc = voting_config(false, OverSubscriptionPolicy::HighestShares);
+        vc.update(VotingConfigUpdate {
+            proposal_bond_opt: Some(Some(ProposalBond {
+                group_id: 1,
+                quantity: Shares::from(10),
+                on_rejection: RejectedBondPolicy::Burn,
+            })),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // the proposer has no shares left to lock - the bond of the first stage is already settled
+        let mut next_stage = voting(vc);
+        next_stage.init_pipeline_stage(PipelineStage {
+            pipeline_id: 1,
+            stages: vec![1, 1],
+            stage: 1,
+            prev_voting: Some(1),
+            next_voting: None,
+        });
+
+        assert!(VotingService::lock_proposal_bond(&mut next_stage, 1).is_ok());
+        assert!(next_stage.get_locked_bond().is_none());
+    }
 }
@@ -1,13 +1,19 @@
+use crate::repository::pipeline::types::PipelineId;
+use crate::repository::voting::types::{OverSubscription, RoundId, VotingStatus};
 use crate::repository::voting_config::types::{Fraction, LenInterval};
-use candid::{CandidType, Deserialize};
+use crate::service::group::types::GroupError;
+use crate::service::history_ledger::HistoryLedgerError;
+use candid::{CandidType, Deserialize, Principal};
 use shared::types::history_ledger::SharesInfo;
-use shared::types::wallet::{ChoiceId, ProfileId, VotingConfigId, VotingId};
+use shared::types::wallet::{ChoiceId, GroupId, ProfileId, Shares, VotingConfigId, VotingId};
 use shared::validation::ValidationError;
 use std::collections::BTreeMap;
 
+pub const CAST_MY_VOTES_MAX_LEN: usize = 50;
+
 pub struct VotingService;
 
-#[derive(Debug)]
+#[derive(CandidType, Deserialize, Debug)]
 pub enum VotingError {
     ValidationError(ValidationError),
     VotingConfigNotFound(VotingConfigId),
@@ -22,11 +28,113 @@ pub enum VotingError {
     VoteFractionTooBig,
     VoterCantApprove,
     VoterCantReject,
+    VoterCantVeto,
+    VoterCantAbstain,
+    AbstainNotSupportedByVoting,
+    VetoOnlyAllowedDuringExecutionDelay,
+    ExecutionNotFailed(ChoiceId),
+    ExecutionAlreadyRetrying(ChoiceId),
+    SimulationNotSupportedByTallyMode,
     VoterCantVote,
-    SharesInfoSignatureInvalid,
     SharesInfoTimestampInvalid,
     InsufficientSharesBalance,
     SharesInfoDoesntBelongToVoter,
+    VoteTypeNotAllowedByVotingConfig,
+    VotingPowerNotDelegated(Principal),
+    VoteShouldBeCommitted,
+    RevealOnlyAllowedDuringRevealPhase,
+    CommitmentNotFound,
+    CommitmentAlreadyRevealed,
+    CommitmentMismatch,
+    HistoryLedgerError(HistoryLedgerError),
+    TooManyOpenVotings(u32),
+    ProposalBondError(GroupError),
+    PipelineNotFound(PipelineId),
+    VotingConfigIsNotFirstPipelineStage(PipelineId),
+    TooManyVotesInBatch(usize),
+    DuplicateVoteInBatch(VotingId, GroupId),
+    VoteAlreadyCast,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RoundSimulation {
+    pub rejection_reached: bool,
+    pub approval_reached: Option<bool>,
+    pub quorum_reached: Option<bool>,
+    pub win: Vec<ChoiceId>,
+    pub next_round: Vec<ChoiceId>,
+    pub winners: Vec<ChoiceId>,
+    pub losers: Vec<ChoiceId>,
+    pub over_subscription: Option<OverSubscription>,
+    pub status: VotingStatus,
+}
+
+// hypothetical votes of a round, which is simulated against a voting config
+pub struct SimulatedRound {
+    pub round: RoundId,
+    pub winners_left: u32,
+    pub total_voting_power_by_group: BTreeMap<GroupId, Shares>,
+    pub approval_votes: BTreeMap<GroupId, Shares>,
+    pub rejection_votes: BTreeMap<GroupId, Shares>,
+    pub abstain_votes: BTreeMap<GroupId, Shares>,
+    pub votes_by_choice: BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>,
+    // shares spent on choices, only differ from votes in quadratic tally mode
+    pub spent_shares_by_group_opt: Option<BTreeMap<GroupId, Shares>>,
+    pub unrevealed_shares_by_group: BTreeMap<GroupId, Shares>,
+}
+
+// everything the end of a round depends on, so real and simulated rounds are decided the same way
+pub struct RoundTally {
+    pub round: RoundId,
+    pub winners_left: u32,
+    pub total_voting_power_by_group: BTreeMap<GroupId, Shares>,
+    pub approval_votes: BTreeMap<GroupId, Shares>,
+    pub rejection_votes: BTreeMap<GroupId, Shares>,
+    pub votes_by_choice: BTreeMap<ChoiceId, BTreeMap<GroupId, Shares>>,
+    // shares spent on choices, abstentions and unrevealed commitments - the quorum counts them all
+    pub spent_shares_by_group: BTreeMap<GroupId, Shares>,
+}
+
+pub struct RoundDecision {
+    pub rejection_reached: bool,
+    pub approval_reached: Option<bool>,
+    pub quorum_reached: Option<bool>,
+    pub win: Vec<ChoiceId>,
+    pub next_round: Vec<ChoiceId>,
+    pub outcome: RoundOutcome,
+}
+
+pub enum RoundOutcome {
+    Rejected,
+    Approved,
+    // eliminations depend on individual ballots, so they are decided separately
+    InstantRunoff,
+    Fail(String),
+    Resolved(RoundResolution),
+}
+
+pub struct RoundResolution {
+    pub winners: Vec<ChoiceId>,
+    pub losers: Vec<ChoiceId>,
+    pub over_subscription: Option<OverSubscription>,
+    pub next: RoundNext,
+}
+
+pub enum RoundNext {
+    Success,
+    NextRound,
+    Fail(String),
+}
+
+// a vote, which is checked against the voting, but is not applied yet
+pub struct ValidatedVote {
+    pub weights: Vec<(ChoiceId, Fraction)>,
+    pub ranking: Option<Vec<ChoiceId>>,
+    pub quadratic: bool,
+    pub anti_sniping: bool,
+    pub veto: bool,
+    pub shares_info: SharesInfo,
+    pub delegated_shares_info: Vec<SharesInfo>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -40,9 +148,40 @@ pub struct MultiChoiceVote {
     pub vote: BTreeMap<ChoiceId, Fraction>,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct RankedChoiceVote {
+    pub shares_info: SharesInfo,
+    pub ranking: Vec<ChoiceId>,
+}
+
 #[derive(CandidType, Deserialize)]
 pub enum Vote {
     Rejection(SingleChoiceVote),
     Approval(SingleChoiceVote),
     Common(MultiChoiceVote),
+    Ranked(RankedChoiceVote),
+    Veto(SingleChoiceVote),
+    Abstain(SingleChoiceVote),
+}
+
+impl Vote {
+    pub fn get_shares_info(&self) -> &SharesInfo {
+        match self {
+            Vote::Rejection(v) | Vote::Approval(v) | Vote::Veto(v) | Vote::Abstain(v) => {
+                &v.shares_info
+            }
+            Vote::Common(v) => &v.shares_info,
+            Vote::Ranked(v) => &v.shares_info,
+        }
+    }
+
+    pub fn set_shares_info(&mut self, shares_info: SharesInfo) {
+        match self {
+            Vote::Rejection(v) | Vote::Approval(v) | Vote::Veto(v) | Vote::Abstain(v) => {
+                v.shares_info = shares_info
+            }
+            Vote::Common(v) => v.shares_info = shares_info,
+            Vote::Ranked(v) => v.shares_info = shares_info,
+        }
+    }
 }
@@ -1,135 +1,120 @@
-use crate::repository::permission::types::PermissionId;
+use crate::repository::pipeline::model::Pipeline;
 use crate::repository::voting::model::Voting;
 use crate::repository::voting_config::model::VotingConfig;
 use crate::repository::voting_config::types::{
-    LenInterval, RoundSettings, ThresholdValue, VotingConfigFilter,
+    VotingConfigFilter, VotingConfigParams, VotingConfigUpdate,
 };
+use crate::repository::voting_template::model::VotingTemplate;
 use crate::service::voting_config::types::{VotingConfigError, VotingConfigService};
 use shared::mvc::{HasRepository, Repository};
 use shared::pageable::{Page, PageRequest};
 use shared::types::wallet::VotingConfigId;
-use std::collections::BTreeSet;
 
 impl VotingConfigService {
     pub fn create_voting_config(
-        name: String,
-        description: String,
-        choices_count: Option<LenInterval>,
-        winners_count: Option<LenInterval>,
-        permissions: BTreeSet<PermissionId>,
-        round: RoundSettings,
-        approval: ThresholdValue,
-        quorum: ThresholdValue,
-        rejection: ThresholdValue,
-        win: ThresholdValue,
-        next_round: ThresholdValue,
+        params: VotingConfigParams,
     ) -> Result<VotingConfigId, VotingConfigError> {
-        VotingConfigService::assert_permissions_exist(&permissions)?;
+        VotingConfigService::assert_permissions_exist(&params.permissions)?;
 
-        for group in approval.list_groups() {
+        for group in params.approval.list_groups() {
             VotingConfigService::assert_group_exists(&group)?;
         }
 
-        for group in rejection.list_groups() {
+        for group in params.rejection.list_groups() {
             VotingConfigService::assert_group_exists(&group)?;
         }
 
-        for group in quorum.list_groups() {
+        for group in params.quorum.list_groups() {
             VotingConfigService::assert_group_exists(&group)?;
         }
 
-        for group in win.list_groups() {
+        for group in params.win.list_groups() {
             VotingConfigService::assert_group_exists(&group)?;
         }
 
-        for group in next_round.list_groups() {
+        for group in params.next_round.list_groups() {
             VotingConfigService::assert_group_exists(&group)?;
         }
 
-        let vc = VotingConfig::new(
-            name,
-            description,
-            choices_count,
-            winners_count,
-            permissions,
-            round,
-            approval,
-            quorum,
-            rejection,
-            win,
-            next_round,
-        )
-        .map_err(VotingConfigError::ValidationError)?;
+        if let Some(veto) = &params.veto {
+            for group in veto.list_groups() {
+                VotingConfigService::assert_group_exists(&group)?;
+            }
+        }
+
+        for group in params.group_weights.keys() {
+            VotingConfigService::assert_group_exists(group)?;
+        }
+
+        if let Some(bond) = &params.proposal_bond {
+            VotingConfigService::assert_proposal_bond_group_is_fine(bond)?;
+        }
+
+        let vc = VotingConfig::new(params).map_err(VotingConfigError::ValidationError)?;
 
         Ok(VotingConfig::repo().save(vc))
     }
 
     pub fn update_voting_config(
         id: VotingConfigId,
-        name_opt: Option<String>,
-        description_opt: Option<String>,
-        choices_count_opt: Option<Option<LenInterval>>,
-        winners_count_opt: Option<Option<LenInterval>>,
-        permissions_opt: Option<BTreeSet<PermissionId>>,
-        round_opt: Option<RoundSettings>,
-        approval_opt: Option<ThresholdValue>,
-        quorum_opt: Option<ThresholdValue>,
-        rejection_opt: Option<ThresholdValue>,
-        win_opt: Option<ThresholdValue>,
-        next_round_opt: Option<ThresholdValue>,
+        update: VotingConfigUpdate,
     ) -> Result<(), VotingConfigError> {
         VotingConfigService::assert_not_default(id)?;
 
-        if let Some(permissions) = &permissions_opt {
+        if let Some(permissions) = &update.permissions_opt {
             VotingConfigService::assert_permissions_exist(permissions)?;
         }
 
-        if let Some(approval) = &approval_opt {
+        if let Some(approval) = &update.approval_opt {
             for group in approval.list_groups() {
                 VotingConfigService::assert_group_exists(&group)?;
             }
         }
 
-        if let Some(rejection) = &rejection_opt {
+        if let Some(rejection) = &update.rejection_opt {
             for group in rejection.list_groups() {
                 VotingConfigService::assert_group_exists(&group)?;
             }
         }
 
-        if let Some(quorum) = &quorum_opt {
+        if let Some(quorum) = &update.quorum_opt {
             for group in quorum.list_groups() {
                 VotingConfigService::assert_group_exists(&group)?;
             }
         }
 
-        if let Some(win) = &win_opt {
+        if let Some(win) = &update.win_opt {
             for group in win.list_groups() {
                 VotingConfigService::assert_group_exists(&group)?;
             }
         }
 
-        if let Some(next_round) = &next_round_opt {
+        if let Some(next_round) = &update.next_round_opt {
             for group in next_round.list_groups() {
                 VotingConfigService::assert_group_exists(&group)?;
             }
         }
 
+        if let Some(Some(veto)) = &update.veto_opt {
+            for group in veto.list_groups() {
+                VotingConfigService::assert_group_exists(&group)?;
+            }
+        }
+
+        if let Some(group_weights) = &update.group_weights_opt {
+            for group in group_weights.keys() {
+                VotingConfigService::assert_group_exists(group)?;
+            }
+        }
+
+        if let Some(Some(bond)) = &update.proposal_bond_opt {
+            VotingConfigService::assert_proposal_bond_group_is_fine(bond)?;
+        }
+
         let mut vc = VotingConfigService::get_voting_config(&id)?;
 
-        vc.update(
-            name_opt,
-            description_opt,
-            choices_count_opt,
-            winners_count_opt,
-            permissions_opt,
-            round_opt,
-            approval_opt,
-            quorum_opt,
-            rejection_opt,
-            win_opt,
-            next_round_opt,
-        )
-        .map_err(VotingConfigError::ValidationError)?;
+        vc.update(update)
+            .map_err(VotingConfigError::ValidationError)?;
 
         VotingConfig::repo().save(vc);
 
@@ -143,6 +128,14 @@ impl VotingConfigService {
             return Err(VotingConfigError::HasRelatedVotings);
         }
 
+        if Pipeline::repo().voting_config_has_related_pipelines(&id) {
+            return Err(VotingConfigError::HasRelatedPipelines);
+        }
+
+        if VotingTemplate::repo().voting_config_has_related_voting_templates(&id) {
+            return Err(VotingConfigError::HasRelatedVotingTemplates);
+        }
+
         VotingConfig::repo()
             .delete(&id)
             .ok_or(VotingConfigError::VotingConfigNotFound(id))
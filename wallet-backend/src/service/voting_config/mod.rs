@@ -3,7 +3,8 @@ use crate::repository::permission::model::Permission;
 use crate::repository::permission::types::PermissionId;
 use crate::repository::voting_config::model::VotingConfig;
 use crate::repository::voting_config::types::{
-    Fraction, FractionOf, QuantityOf, RoundSettings, Target, ThresholdValue,
+    Fraction, FractionOf, OverSubscriptionPolicy, ProposalBond, QuantityOf, RoundSettings,
+    TallyMode, Target, ThresholdValue, VotingConfigParams,
 };
 use crate::service::group::types::HAS_PROFILE_GROUP_ID;
 use crate::service::permission::types::{
@@ -16,64 +17,83 @@ use shared::mvc::{HasRepository, Repository};
 use shared::remote_call::Program;
 use shared::time::{hours, mins};
 use shared::types::wallet::{GroupId, Shares, VotingConfigId};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub mod crud;
 pub mod types;
 
 impl VotingConfigService {
     pub fn init_default_voting_configs() {
-        let emergency_voting_config_id = VotingConfigService::create_voting_config(
-            String::from("Emergency"),
-            String::from("Non-deletable voting config. Allows to call ANY method of this digital organization if 100% of 'Has Profile' group wants it."),
-            None,
-            None,
-            vec![ALLOW_WRITE_PERMISSION_ID].into_iter().collect(),
-            RoundSettings { round_duration: mins(1), round_delay: 0 },
-            ThresholdValue::QuantityOf(QuantityOf { quantity: Shares::from(0), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
-            ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
-            ThresholdValue::QuantityOf(QuantityOf { quantity: Shares::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
-            ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
-            ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
-        ).unwrap();
+        let emergency_voting_config_id = VotingConfigService::create_voting_config(VotingConfigParams {
+            name: String::from("Emergency"),
+            description: String::from("Non-deletable voting config. Allows to call ANY method of this digital organization if 100% of 'Has Profile' group wants it."),
+            choices_count: None,
+            winners_count: None,
+            permissions: vec![ALLOW_WRITE_PERMISSION_ID].into_iter().collect(),
+            round: RoundSettings { round_duration: mins(1), round_delay: 0, execution_delay: 0, anti_sniping: None, early_finish: false },
+            tally_mode: TallyMode::Common,
+            commit_reveal: None,
+            over_subscription: OverSubscriptionPolicy::HighestShares,
+            voting_power_cap: None,
+            group_weights: BTreeMap::new(),
+            proposal_bond: None,
+            max_open_votings_per_proposer: None,
+            veto: None,
+            approval: ThresholdValue::QuantityOf(QuantityOf { quantity: Shares::from(0), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
+            quorum: ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
+            rejection: ThresholdValue::QuantityOf(QuantityOf { quantity: Shares::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
+            win: ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
+            next_round: ThresholdValue::FractionOf(FractionOf { fraction: Fraction::from(1), target: Target::Group(HAS_PROFILE_GROUP_ID) }),
+        }).unwrap();
 
         assert_eq!(emergency_voting_config_id, EMERGENCY_VOTING_CONFIG_ID);
 
-        let feedback_voting_config_id = VotingConfigService::create_voting_config(
-            String::from("Feedback"),
-            String::from(
+        let feedback_voting_config_id = VotingConfigService::create_voting_config(VotingConfigParams {
+            name: String::from("Feedback"),
+            description: String::from(
                 "Default non-deletable voting config. Allows anyone from the 'Has profile' group to create votings with empty programs.",
             ),
-            None,
-            None,
-            vec![ALLOW_SEND_FEEDBACK_PERMISSION_ID]
+            choices_count: None,
+            winners_count: None,
+            permissions: vec![ALLOW_SEND_FEEDBACK_PERMISSION_ID]
                 .into_iter()
                 .collect(),
-            RoundSettings {
+            round: RoundSettings {
                 round_duration: mins(1),
                 round_delay: 0,
+                execution_delay: 0,
+                anti_sniping: None,
+                early_finish: false,
             },
-            ThresholdValue::FractionOf(FractionOf {
+            tally_mode: TallyMode::Common,
+            commit_reveal: None,
+            over_subscription: OverSubscriptionPolicy::HighestShares,
+            voting_power_cap: None,
+            group_weights: BTreeMap::new(),
+            proposal_bond: None,
+            max_open_votings_per_proposer: None,
+            veto: None,
+            approval: ThresholdValue::FractionOf(FractionOf {
                 fraction: Fraction::from(0.1),
                 target: Target::Group(HAS_PROFILE_GROUP_ID),
             }),
-            ThresholdValue::FractionOf(FractionOf {
+            quorum: ThresholdValue::FractionOf(FractionOf {
                 fraction: Fraction::from(0.3),
                 target: Target::Group(HAS_PROFILE_GROUP_ID),
             }),
-            ThresholdValue::FractionOf(FractionOf {
+            rejection: ThresholdValue::FractionOf(FractionOf {
                 fraction: Fraction::from(0.2),
                 target: Target::Group(HAS_PROFILE_GROUP_ID),
             }),
-            ThresholdValue::FractionOf(FractionOf {
+            win: ThresholdValue::FractionOf(FractionOf {
                 fraction: Fraction::from(0.66667),
                 target: Target::Group(HAS_PROFILE_GROUP_ID),
             }),
-            ThresholdValue::FractionOf(FractionOf {
+            next_round: ThresholdValue::FractionOf(FractionOf {
                 fraction: Fraction::from(0.2),
                 target: Target::Group(HAS_PROFILE_GROUP_ID),
             }),
-        )
+        })
         .unwrap();
 
         assert_eq!(feedback_voting_config_id, FEEDBACK_VOTING_CONFIG_ID);
@@ -117,4 +137,13 @@ impl VotingConfigService {
             .map(|_| ())
             .ok_or(VotingConfigError::GroupDoesntExist(*group_id))
     }
+
+    // everyone has exactly one share of the 'Has profile' group, so there is nothing to lock
+    fn assert_proposal_bond_group_is_fine(bond: &ProposalBond) -> Result<(), VotingConfigError> {
+        if bond.group_id == HAS_PROFILE_GROUP_ID {
+            return Err(VotingConfigError::UnableToBondHasProfileGroupShares);
+        }
+
+        VotingConfigService::assert_group_exists(&bond.group_id)
+    }
 }
@@ -12,7 +12,10 @@ pub enum VotingConfigError {
     ValidationError(ValidationError),
     PermissionDoesntExist(PermissionId),
     GroupDoesntExist(GroupId),
+    UnableToBondHasProfileGroupShares,
     UnableToEditDefaultVotingConfig,
     HasRelatedVotings,
+    HasRelatedPipelines,
+    HasRelatedVotingTemplates,
     VotingConfigNotFound(VotingConfigId),
 }
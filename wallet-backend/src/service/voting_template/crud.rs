@@ -0,0 +1,228 @@
+use crate::repository::nested_voting::types::RemoteVotingId;
+use crate::repository::voting_config::model::VotingConfig;
+use crate::repository::voting_template::model::VotingTemplate;
+use crate::repository::voting_template::types::{
+    replace_placeholders, TemplateChoice, TemplateParamKind, TemplateParamValue,
+    VotingTemplateFilter, VotingTemplateId,
+};
+use crate::service::choice::types::ChoiceService;
+use crate::service::voting::types::VotingService;
+use crate::service::voting_config::types::VotingConfigService;
+use crate::service::voting_template::types::{VotingTemplateError, VotingTemplateService};
+use candid::Principal;
+use shared::mvc::{HasRepository, Repository};
+use shared::pageable::{Page, PageRequest};
+use shared::remote_call::{Program, RemoteCallArgs, RemoteCallPayload};
+use shared::types::wallet::{VotingConfigId, VotingId};
+use std::collections::BTreeMap;
+
+impl VotingTemplateService {
+    pub fn create_voting_template(
+        voting_config_id: VotingConfigId,
+        name: String,
+        description: String,
+        winners_need: u32,
+        params: BTreeMap<String, TemplateParamKind>,
+        choices: Vec<TemplateChoice>,
+    ) -> Result<VotingTemplateId, VotingTemplateError> {
+        VotingConfig::repo()
+            .get(&voting_config_id)
+            .ok_or(VotingTemplateError::VotingConfigNotFound(voting_config_id))?;
+
+        let it = VotingTemplate::new(
+            voting_config_id,
+            name,
+            description,
+            winners_need,
+            params,
+            choices,
+        )
+        .map_err(VotingTemplateError::ValidationError)?;
+
+        Ok(VotingTemplate::repo().save(it))
+    }
+
+    pub fn update_voting_template(
+        id: &VotingTemplateId,
+        name_opt: Option<String>,
+        description_opt: Option<String>,
+        winners_need_opt: Option<u32>,
+        params_opt: Option<BTreeMap<String, TemplateParamKind>>,
+        choices_opt: Option<Vec<TemplateChoice>>,
+    ) -> Result<(), VotingTemplateError> {
+        let mut it = VotingTemplateService::get_voting_template(id)?;
+
+        it.update(
+            name_opt,
+            description_opt,
+            winners_need_opt,
+            params_opt,
+            choices_opt,
+        )
+        .map_err(VotingTemplateError::ValidationError)?;
+
+        VotingTemplate::repo().save(it);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn delete_voting_template(
+        id: &VotingTemplateId,
+    ) -> Result<VotingTemplate, VotingTemplateError> {
+        VotingTemplate::repo()
+            .delete(id)
+            .ok_or(VotingTemplateError::VotingTemplateNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn get_voting_template(
+        id: &VotingTemplateId,
+    ) -> Result<VotingTemplate, VotingTemplateError> {
+        VotingTemplate::repo()
+            .get(id)
+            .ok_or(VotingTemplateError::VotingTemplateNotFound(*id))
+    }
+
+    #[inline(always)]
+    pub fn list_voting_templates(
+        page_req: &PageRequest<VotingTemplateFilter, ()>,
+    ) -> Page<VotingTemplate> {
+        VotingTemplate::repo().list(page_req)
+    }
+
+    // every program is checked before the voting is created; any later error traps, reverting the whole call
+    pub fn create_voting_from_template(
+        id: &VotingTemplateId,
+        params: BTreeMap<String, TemplateParamValue>,
+        proposer: Principal,
+        timestamp: u64,
+    ) -> Result<VotingId, VotingTemplateError> {
+        let template = VotingTemplateService::get_voting_template(id)?;
+        VotingTemplateService::assert_params_match(&template, &params)?;
+
+        let vc_id = *template.get_voting_config_id();
+        let vc = VotingConfig::repo()
+            .get(&vc_id)
+            .ok_or(VotingTemplateError::VotingConfigNotFound(vc_id))?;
+
+        let plain_params: BTreeMap<_, _> = params
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_plain_string()))
+            .collect();
+
+        let candid_params: BTreeMap<_, _> = params
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_candid_string()))
+            .collect();
+
+        let mut choices = Vec::new();
+        for choice in template.get_choices() {
+            let program = VotingTemplateService::render_program(&choice.program, &candid_params)?;
+            program
+                .validate()
+                .map_err(VotingTemplateError::ValidationError)?;
+
+            if !VotingConfigService::does_program_fit(&vc, &program) {
+                return Err(VotingTemplateError::ProgramNotAllowedByVotingConfig);
+            }
+
+            choices.push(TemplateChoice {
+                name: VotingTemplateService::render(&choice.name, &plain_params)?,
+                description: VotingTemplateService::render(&choice.description, &plain_params)?,
+                program,
+            });
+        }
+
+        let voting_id = VotingService::create_voting(
+            vc_id,
+            VotingTemplateService::render(template.get_name(), &plain_params)?,
+            VotingTemplateService::render(template.get_description(), &plain_params)?,
+            template.get_winners_need(),
+            None,
+            proposer,
+            timestamp,
+        )
+        .map_err(VotingTemplateError::VotingError)?;
+
+        for choice in choices {
+            ChoiceService::create_choice(
+                choice.name,
+                choice.description,
+                choice.program,
+                RemoteVotingId::Common(voting_id),
+                timestamp,
+            )
+            .map_err(VotingTemplateError::ChoiceError)?;
+        }
+
+        Ok(voting_id)
+    }
+
+    fn assert_params_match(
+        template: &VotingTemplate,
+        params: &BTreeMap<String, TemplateParamValue>,
+    ) -> Result<(), VotingTemplateError> {
+        for (name, kind) in template.get_params() {
+            let value = params
+                .get(name)
+                .ok_or_else(|| VotingTemplateError::MissingParam(name.clone()))?;
+
+            if value.get_kind() != *kind {
+                return Err(VotingTemplateError::InvalidParamKind(name.clone(), *kind));
+            }
+        }
+
+        for name in params.keys() {
+            if !template.get_params().contains_key(name) {
+                return Err(VotingTemplateError::UnknownParam(name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_program(
+        program: &Program,
+        params: &BTreeMap<String, String>,
+    ) -> Result<Program, VotingTemplateError> {
+        match program {
+            Program::Empty => Ok(Program::Empty),
+            Program::RemoteCallSequence(seq) => {
+                let mut rendered_seq = Vec::new();
+
+                for call in seq {
+                    let args = match &call.args {
+                        RemoteCallArgs::CandidString(str_args) => RemoteCallArgs::CandidString(
+                            str_args
+                                .iter()
+                                .map(|it| VotingTemplateService::render(it, params))
+                                .collect::<Result<_, _>>()?,
+                        ),
+                        RemoteCallArgs::Encoded(blob) => RemoteCallArgs::Encoded(blob.clone()),
+                    };
+
+                    rendered_seq.push(RemoteCallPayload {
+                        endpoint: call.endpoint.clone(),
+                        args,
+                        cycles: call.cycles,
+                    });
+                }
+
+                Ok(Program::RemoteCallSequence(rendered_seq))
+            }
+        }
+    }
+
+    fn render(
+        string: &str,
+        params: &BTreeMap<String, String>,
+    ) -> Result<String, VotingTemplateError> {
+        replace_placeholders(string, |name| {
+            params
+                .get(name)
+                .cloned()
+                .ok_or_else(|| VotingTemplateError::MissingParam(name.to_string()))
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use crate::repository::voting_template::types::{TemplateParamKind, VotingTemplateId};
+use crate::service::choice::types::ChoiceError;
+use crate::service::voting::types::VotingError;
+use shared::types::wallet::VotingConfigId;
+use shared::validation::ValidationError;
+
+pub struct VotingTemplateService;
+
+#[derive(Debug)]
+pub enum VotingTemplateError {
+    ValidationError(ValidationError),
+    VotingConfigNotFound(VotingConfigId),
+    VotingTemplateNotFound(VotingTemplateId),
+    MissingParam(String),
+    UnknownParam(String),
+    InvalidParamKind(String, TemplateParamKind),
+    ProgramNotAllowedByVotingConfig,
+    VotingError(VotingError),
+    ChoiceError(ChoiceError),
+}
@@ -1,4 +1,5 @@
 use candid::{CandidType, Deserialize, Principal};
+use shared::certification::IC_ROOT_KEY;
 use shared::sorted_by_timestamp::SortedByTimestamp;
 use shared::validation::{validate_and_trim_str, ValidationError};
 
@@ -7,6 +8,8 @@ pub struct Settings {
     history_ledgers: SortedByTimestamp<Principal>,
     name: String,
     description: String,
+    // DER-encoded root key to verify certificates of other canisters with; the IC mainnet one, if not set
+    ic_root_key_opt: Option<Vec<u8>>,
 }
 
 impl Settings {
@@ -14,12 +17,14 @@ impl Settings {
         history_ledger: Principal,
         name: String,
         description: String,
+        ic_root_key_opt: Option<Vec<u8>>,
         timestamp: u64,
     ) -> Result<Self, ValidationError> {
         let mut it = Self {
             history_ledgers: SortedByTimestamp::default(),
             name: Self::process_name(name)?,
             description: Self::process_description(description)?,
+            ic_root_key_opt,
         };
 
         it.add_history_ledger(history_ledger, timestamp);
@@ -61,6 +66,10 @@ impl Settings {
         self.history_ledgers.get_all()
     }
 
+    pub fn get_ic_root_key(&self) -> &[u8] {
+        self.ic_root_key_opt.as_deref().unwrap_or(IC_ROOT_KEY)
+    }
+
     pub fn get() -> &'static mut Settings {
         get_settings()
     }
@@ -76,11 +85,23 @@ impl Settings {
 
 static mut SETTINGS: Option<Settings> = None;
 
-pub fn init_settings(history_ledger: Principal, name: String, description: String, timestamp: u64) {
+pub fn init_settings(
+    history_ledger: Principal,
+    name: String,
+    description: String,
+    ic_root_key_opt: Option<Vec<u8>>,
+    timestamp: u64,
+) {
     unsafe {
         SETTINGS = Some(
-            Settings::new(history_ledger, name, description, timestamp)
-                .expect("Unable to init settings"),
+            Settings::new(
+                history_ledger,
+                name,
+                description,
+                ic_root_key_opt,
+                timestamp,
+            )
+            .expect("Unable to init settings"),
         )
     }
 }